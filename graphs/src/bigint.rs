@@ -0,0 +1,128 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Arbitrary precision unsigned integer, just enough for exact counting:
+/// multiplication and addition by machine words, division by a machine word and printing
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+	/// Little endian base 2⁶⁴ digits without leading zeros, so zero has none
+	limbs: Vec<u64>,
+}
+
+impl BigUint {
+	pub fn zero() -> Self {
+		Self::default()
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.limbs.is_empty()
+	}
+
+	fn strip(&mut self) {
+		while self.limbs.last() == Some(&0) {
+			self.limbs.pop();
+		}
+	}
+
+	pub fn mul_u64(&mut self, factor: u64) {
+		let mut carry = 0_u128;
+		for limb in &mut self.limbs {
+			let product = *limb as u128 * factor as u128 + carry;
+			*limb = product as u64;
+			carry = product >> 64;
+		}
+		if carry != 0 {
+			self.limbs.push(carry as u64);
+		}
+		self.strip();
+	}
+
+	pub fn add_u64(&mut self, term: u64) {
+		let mut carry = term;
+		for limb in &mut self.limbs {
+			if carry == 0 {
+				return;
+			}
+			let (sum, overflow) = limb.overflowing_add(carry);
+			*limb = sum;
+			carry = overflow as u64;
+		}
+		if carry != 0 {
+			self.limbs.push(carry);
+		}
+	}
+
+	/// Divides in place, returns the remainder
+	pub fn div_rem_u64(&mut self, divisor: u64) -> u64 {
+		debug_assert_ne!(divisor, 0);
+		let mut remainder = 0_u128;
+		for limb in self.limbs.iter_mut().rev() {
+			let current = remainder << 64 | *limb as u128;
+			*limb = (current / divisor as u128) as u64;
+			remainder = current % divisor as u128;
+		}
+		self.strip();
+		remainder as u64
+	}
+
+	pub fn rem_u64(&self, divisor: u64) -> u64 {
+		self.clone().div_rem_u64(divisor)
+	}
+
+	/// `None` if it doesn't fit
+	pub fn to_u128(&self) -> Option<u128> {
+		match self.limbs[..] {
+			[] => Some(0),
+			[low] => Some(low as u128),
+			[low, high] => Some((high as u128) << 64 | low as u128),
+			_ => None,
+		}
+	}
+
+	pub fn pow(base: u64, exponent: u32) -> Self {
+		let mut result = BigUint::from(1);
+		for _ in 0..exponent {
+			result.mul_u64(base);
+		}
+		result
+	}
+}
+
+impl From<u64> for BigUint {
+	fn from(value: u64) -> Self {
+		let mut result = BigUint { limbs: vec![value] };
+		result.strip();
+		result
+	}
+}
+
+impl Ord for BigUint {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.limbs.len().cmp(&other.limbs.len())
+			.then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+	}
+}
+
+impl PartialOrd for BigUint {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Display for BigUint {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		const CHUNK: u64 = 10_000_000_000_000_000_000;
+		let mut rest = self.clone();
+		let mut chunks = Vec::new();
+		while !rest.is_zero() {
+			chunks.push(rest.div_rem_u64(CHUNK));
+		}
+		match chunks.pop() {
+			None => write!(f, "0"),
+			Some(first) => {
+				write!(f, "{}", first)?;
+				chunks.iter().rev().try_for_each(|chunk| write!(f, "{:019}", chunk))
+			}
+		}
+	}
+}
@@ -0,0 +1,158 @@
+use std::collections::VecDeque;
+use crate::{DFSSpace, Graph, GraphView};
+
+/// Maximal biconnected subgraph: a 2-connected one, a bridge or an isolated vertex
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+	/// Sorted
+	pub vertexes: Vec<usize>,
+	/// Sorted edge indexes
+	pub edges: Vec<usize>,
+}
+
+/// Blocks and cut vertexes of an undirected graph. The tree (a forest for a disconnected graph)
+/// has a node for every block (`0..blocks.len()`) and for every cut vertex
+/// (`blocks.len() + i` for `cut_vertexes[i]`), a cut vertex is joined with the blocks it lies in.
+/// Loops don't affect biconnectivity, each one is put into some block of its vertex
+#[derive(Debug, Clone)]
+pub struct BlockCutTree {
+	pub blocks: Vec<Block>,
+	/// Sorted
+	pub cut_vertexes: Vec<usize>,
+	pub tree: Graph,
+	/// Blocks containing the vertex, more than one only for cut vertexes
+	pub blocks_of: Vec<Vec<usize>>,
+	/// Node of the vertex in the tree: its own for a cut vertex, its only block otherwise
+	pub node_of: Vec<usize>,
+	/// Rooted forest for path queries: parent of a node and its depth
+	parent: Vec<Option<usize>>,
+	depth: Vec<usize>,
+	/// Root of the tree of the node (the connected component)
+	root: Vec<usize>,
+}
+
+impl BlockCutTree {
+	pub fn new<G: GraphView>(graph: &G) -> Self {
+		let n = graph.vertexes();
+		let (cut_vertexes, components) = DFSSpace::new(graph).find_cutting_points_and_blocks(graph);
+		let mut ends = vec![(0, 0); graph.edge_index_bound()];
+		for v in 0..n {
+			for edge in graph.neighbours(v) {
+				ends[edge.edge_index] = (v, edge.to);
+			}
+		}
+
+		let mut blocks = Vec::new();
+		let mut loops = Vec::new();
+		for edges in components {
+			let (from, to) = ends[edges[0]];
+			if edges.len() == 1 && from == to {
+				loops.push(edges[0]);
+				continue;
+			}
+			let mut vertexes = edges.iter().flat_map(|&e| [ends[e].0, ends[e].1]).collect::<Vec<_>>();
+			vertexes.sort();
+			vertexes.dedup();
+			let mut edges = edges;
+			edges.sort();
+			blocks.push(Block { vertexes, edges });
+		}
+		let mut blocks_of = vec![Vec::new(); n];
+		for (b, block) in blocks.iter().enumerate() {
+			for &v in &block.vertexes {
+				blocks_of[v].push(b);
+			}
+		}
+		for (v, own) in blocks_of.iter_mut().enumerate().filter(|(_, own)| own.is_empty()) {
+			own.push(blocks.len());
+			blocks.push(Block { vertexes: vec![v], edges: Vec::new() });
+		}
+		for edge_index in loops {
+			let block = &mut blocks[blocks_of[ends[edge_index].0][0]];
+			let position = block.edges.binary_search(&edge_index).unwrap_err();
+			block.edges.insert(position, edge_index);
+		}
+
+		let mut tree = Graph::new(blocks.len() + cut_vertexes.len());
+		let mut node_of = blocks_of.iter().map(|blocks| blocks[0]).collect::<Vec<_>>();
+		for (i, &v) in cut_vertexes.iter().enumerate() {
+			node_of[v] = blocks.len() + i;
+			for &b in &blocks_of[v] {
+				tree.add_undirected_edge(b, blocks.len() + i);
+			}
+		}
+
+		let nodes = tree.vertexes();
+		let mut parent = vec![None; nodes];
+		let mut depth = vec![0; nodes];
+		let mut root = vec![usize::MAX; nodes];
+		for start in 0..nodes {
+			if root[start] != usize::MAX {
+				continue;
+			}
+			root[start] = start;
+			let mut queue = VecDeque::from([start]);
+			while let Some(node) = queue.pop_front() {
+				for edge in &tree.edges[node] {
+					if root[edge.to] == usize::MAX {
+						root[edge.to] = start;
+						parent[edge.to] = Some(node);
+						depth[edge.to] = depth[node] + 1;
+						queue.push_back(edge.to);
+					}
+				}
+			}
+		}
+
+		BlockCutTree { blocks, cut_vertexes, tree, blocks_of, node_of, parent, depth, root }
+	}
+
+	pub fn is_cut_vertex(&self, v: usize) -> bool {
+		self.blocks_of[v].len() > 1
+	}
+
+	/// Vertex of a cut node, `None` for a block node
+	pub fn cut_vertex_of_node(&self, node: usize) -> Option<usize> {
+		node.checked_sub(self.blocks.len()).map(|i| self.cut_vertexes[i])
+	}
+
+	pub fn are_connected(&self, u: usize, v: usize) -> bool {
+		self.root[self.node_of[u]] == self.root[self.node_of[v]]
+	}
+
+	/// Some block contains both vertexes (no vertex separates them and they are connected)
+	pub fn same_block(&self, u: usize, v: usize) -> bool {
+		self.blocks_of[u].iter().any(|b| self.blocks_of[v].contains(b))
+	}
+
+	/// Nodes of the tree path between the nodes of `u` and `v`, `None` if they aren't connected
+	pub fn path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+		if !self.are_connected(u, v) {
+			return None;
+		}
+		let (mut a, mut b) = (self.node_of[u], self.node_of[v]);
+		let (mut from_a, mut from_b) = (Vec::new(), Vec::new());
+		while a != b {
+			if self.depth[a] >= self.depth[b] {
+				from_a.push(a);
+				a = self.parent[a].unwrap();
+			} else {
+				from_b.push(b);
+				b = self.parent[b].unwrap();
+			}
+		}
+		from_a.push(a);
+		from_a.extend(from_b.into_iter().rev());
+		Some(from_a)
+	}
+
+	/// Cut vertexes (other than `u` and `v`) whose removal disconnects `u` from `v`,
+	/// in the order from `u` to `v`. `None` if they aren't connected anyway
+	pub fn separating_vertexes(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+		let path = self.path(u, v)?;
+		Some(path.into_iter()
+			.filter_map(|node| self.cut_vertex_of_node(node))
+			.filter(|&w| w != u && w != v)
+			.collect())
+	}
+}
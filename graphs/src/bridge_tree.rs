@@ -0,0 +1,132 @@
+use crate::{BinaryLifting, DFSSpace, Decomposition, EdgeSubgraph, Graph, GraphView, RootedTree};
+
+/// 2-edge-connected components of an undirected graph joined by the bridges.
+/// It's a tree for a connected graph and a forest otherwise
+#[derive(Debug, Clone)]
+pub struct BridgeTree {
+	/// Components of the vertexes, they are the nodes of the tree
+	pub components: Decomposition,
+	/// Sorted, edge `i` of the tree is the bridge `bridges[i]`
+	pub bridges: Vec<usize>,
+	pub tree: Graph,
+	rooted: RootedTree,
+	lifting: BinaryLifting,
+	/// Connected components of the tree
+	connected: Decomposition,
+}
+
+impl BridgeTree {
+	pub fn new<G: GraphView>(graph: &G) -> Self {
+		let mut bridges = DFSSpace::new(graph).find_bridges(graph);
+		bridges.sort();
+		let mut is_bridge = vec![false; graph.edge_index_bound()];
+		for &bridge in &bridges {
+			is_bridge[bridge] = true;
+		}
+		let without_bridges = EdgeSubgraph::new(graph, |edge| !is_bridge[edge]);
+		let components = DFSSpace::new(&without_bridges).find_connected_components(&without_bridges);
+
+		let mut ends = vec![(0, 0); graph.edge_index_bound()];
+		for v in 0..graph.vertexes() {
+			for edge in graph.neighbours(v).filter(|edge| is_bridge[edge.edge_index]) {
+				ends[edge.edge_index] = (v, edge.to);
+			}
+		}
+		let mut tree = Graph::new(components.component_list.len());
+		for &bridge in &bridges {
+			let (from, to) = ends[bridge];
+			tree.add_undirected_edge(components.component_of(from), components.component_of(to));
+		}
+
+		let rooted = RootedTree::forest(&tree);
+		let lifting = BinaryLifting::new(&rooted);
+		let connected = DFSSpace::new(&tree).find_connected_components(&tree);
+		BridgeTree { components, bridges, tree, rooted, lifting, connected }
+	}
+
+	pub fn component_of(&self, v: usize) -> usize {
+		self.components.component_of(v)
+	}
+
+	pub fn are_connected(&self, u: usize, v: usize) -> bool {
+		self.connected.component_of(self.component_of(u)) == self.connected.component_of(self.component_of(v))
+	}
+
+	/// No bridge separates the vertexes: they stay connected after the removal of any edge
+	pub fn are_two_edge_connected(&self, u: usize, v: usize) -> bool {
+		self.component_of(u) == self.component_of(v)
+	}
+
+	/// Number of bridges every `u`–`v` path goes through, `None` if there is no path
+	pub fn bridges_between(&self, u: usize, v: usize) -> Option<usize> {
+		self.are_connected(u, v)
+			.then(|| self.lifting.distance(self.component_of(u), self.component_of(v)))
+	}
+
+	/// The bridges every `u`–`v` path goes through, in the order from `u` to `v`
+	pub fn bridges_on_path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+		if !self.are_connected(u, v) {
+			return None;
+		}
+		let (mut a, mut b) = (self.component_of(u), self.component_of(v));
+		let (mut from_a, mut from_b) = (Vec::new(), Vec::new());
+		while a != b {
+			if self.rooted.depth[a] >= self.rooted.depth[b] {
+				from_a.push(self.bridges[self.rooted.parent_edge[a].unwrap()]);
+				a = self.rooted.parent[a].unwrap();
+			} else {
+				from_b.push(self.bridges[self.rooted.parent_edge[b].unwrap()]);
+				b = self.rooted.parent[b].unwrap();
+			}
+		}
+		from_a.extend(from_b.into_iter().rev());
+		Some(from_a)
+	}
+
+	/// Fewest edges whose addition makes the graph connected and bridgeless:
+	/// `⌈leaves / 2⌉ + isolated nodes` of the forest (unless it is a single node).
+	/// The trees are chained leaf to leaf into one tree first, then its leaves in DFS order
+	/// are paired as `i` and `i + ⌊leaves / 2⌋`, so that every tree edge is covered by some new cycle
+	pub fn augmentation(&self) -> Vec<(usize, usize)> {
+		let nodes = self.tree.vertexes();
+		if nodes <= 1 {
+			return Vec::new();
+		}
+		let mut combined = self.tree.clone();
+		let mut added = Vec::new();
+		let mut previous_end: Option<usize> = None;
+		for component in &self.connected.component_list {
+			let leaves = component.iter().filter(|&&node| self.tree.edges[node].len() == 1).collect::<Vec<_>>();
+			// An isolated node is both ends of its chain link
+			let (first, last) = match leaves.as_slice() {
+				[] => (component[0], component[0]),
+				[first, .., last] => (**first, **last),
+				[_] => unreachable!("A tree can't have exactly one leaf"),
+			};
+			if let Some(end) = previous_end {
+				combined.add_undirected_edge(end, first);
+				added.push((end, first));
+			}
+			previous_end = Some(last);
+		}
+
+		let rooted = RootedTree::new(&combined, 0);
+		let mut leaves = Vec::new();
+		let mut stack = vec![0];
+		while let Some(node) = stack.pop() {
+			if combined.edges[node].len() == 1 {
+				leaves.push(node);
+			}
+			stack.extend(rooted.children[node].iter().rev());
+		}
+		let half = leaves.len() / 2;
+		for i in 0..leaves.len().div_ceil(2) {
+			added.push((leaves[i], leaves[i + half]));
+		}
+
+		let representative = |node: usize| self.components.component_list[node][0];
+		added.into_iter()
+			.map(|(a, b)| (representative(a), representative(b)))
+			.collect()
+	}
+}
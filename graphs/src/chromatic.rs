@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use crate::{canonical_form, CanonicalForm, DFSSpace, Graph, Polynomial};
+
+fn has_loops(graph: &Graph) -> bool {
+	graph.edges.iter().enumerate().any(|(v, edges)| edges.iter().any(|e| e.to == v))
+}
+
+/// `x (x - 1) … (x - n + 1)`
+fn falling_factorial(n: usize) -> Polynomial {
+	(0..n).fold(Polynomial::one(), |acc, i| acc * Polynomial::linear(i as i64))
+}
+
+/// Deletion–contraction with memoization and decomposition into components and blocks
+#[derive(Debug, Clone, Default)]
+pub struct ChromaticPolynomialSolver {
+	/// Isomorphic graphs share the entry
+	memo: HashMap<CanonicalForm, Polynomial>,
+}
+
+impl ChromaticPolynomialSolver {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn solve(&mut self, graph: &Graph) -> Polynomial {
+		if has_loops(graph) {
+			return Polynomial::zero();
+		}
+		// Parallel edges don't change the colourings
+		let graph = graph.deduplicated(true);
+
+		let components = DFSSpace::new(&graph).find_connected_components(&graph);
+		components.component_list.iter()
+			.map(|component| self.solve_connected(&graph.induced_subgraph(component)))
+			.fold(Polynomial::one(), |acc, p| acc * p)
+	}
+
+	/// Graph is simple and connected
+	fn solve_connected(&mut self, graph: &Graph) -> Polynomial {
+		let n = graph.vertexes();
+		let m = graph.edges();
+		if m == n * (n - 1) / 2 {
+			return falling_factorial(n);
+		}
+		if m + 1 == n {
+			// Tree
+			return Polynomial::linear(0) * Polynomial::linear(1).pow(n - 1);
+		}
+		if m == n && graph.edges.iter().all(|e| e.len() == 2) {
+			// Cycle: (x - 1)^n + (-1)^n (x - 1)
+			return Polynomial::linear(1).pow(n) + Polynomial::monomial(0, (-1_i64).pow(n as u32)) * Polynomial::linear(1);
+		}
+
+		// Blocks glued at cutting points: P(G) = P(B₁) … P(Bₖ) / x^(k - 1)
+		let (_cutting_points, blocks) = DFSSpace::new(graph).find_cutting_points_with_components(graph);
+		if blocks.component_list.len() > 1 {
+			let block_count = blocks.component_list.len();
+			let product = blocks.component_list.iter()
+				.map(|block| self.solve_connected(&graph.edge_subgraph(block).0))
+				.fold(Polynomial::one(), |acc, p| acc * p);
+			return product.div_exact(&Polynomial::monomial(block_count - 1, 1));
+		}
+
+		let key = canonical_form(graph);
+		if let Some(known) = self.memo.get(&key) {
+			return known.clone();
+		}
+
+		let result = if 4 * m > n * (n - 1) {
+			// Dense graph: P(G) = P(G + uv) + P(G / uv) for a missing edge uv brings us closer to cliques
+			let (u, v) = (0..n)
+				.flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+				.find(|&(u, v)| graph.edges[u].iter().all(|e| e.to != v))
+				.unwrap();
+			let mut added = graph.clone();
+			added.add_undirected_edge(u, v);
+			let merged = added.pull_edge(m);
+			self.solve_connected(&added) + self.solve_connected(&merged)
+		} else {
+			// Sparse graph: P(G) = P(G - e) - P(G / e) for an edge at the vertex of the smallest degree
+			let smallest_degree_vertex = (0..n).min_by_key(|&v| graph.edges[v].len()).unwrap();
+			let edge_index = graph.edges[smallest_degree_vertex][0].edge_index;
+			let without_edge = graph.remove_edges_renumbered(&[edge_index]);
+			let pulled = graph.pull_edge(edge_index);
+			self.solve(&without_edge) - self.solve_connected(&pulled)
+		};
+
+		self.memo.insert(key, result.clone());
+		result
+	}
+}
+
+/// Chromatic polynomial of a graph (of the underlying simple graph, a loop makes it zero)
+pub fn chromatic_polynomial(graph: &Graph) -> Polynomial {
+	ChromaticPolynomialSolver::new().solve(graph)
+}
+
+/// Mersenne prime 2⁶¹ - 1
+const MODULUS: u64 = (1 << 61) - 1;
+
+fn multiply_mod(a: u64, b: u64) -> u64 {
+	((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+/// Neighbourhood bitmasks of the underlying simple graph
+fn adjacency_masks(graph: &Graph) -> Vec<u64> {
+	assert!(graph.vertexes() < 64, "Too many vertexes for the subset algorithms");
+	graph.edges.iter()
+		.map(|edges| edges.iter().fold(0_u64, |mask, e| mask | (1 << e.to)))
+		.collect()
+}
+
+/// Inclusion–exclusion over vertex subsets (Björklund–Husfeldt–Koivisto)
+struct ColouringCounter {
+	n: usize,
+	/// `independent[S]` is the number of independent subsets of `S` (including the empty one), modulo `MODULUS`
+	independent: Vec<u64>,
+	/// `power[S] = independent[S]^k` for the last checked `k`
+	power: Vec<u64>,
+}
+
+impl ColouringCounter {
+	fn new(graph: &Graph) -> Self {
+		let n = graph.vertexes();
+		let adjacency = adjacency_masks(graph);
+		let mut independent = vec![0_u64; 1 << n];
+		independent[0] = 1;
+		for set in 1_usize..1 << n {
+			let v = set.trailing_zeros() as usize;
+			let without_v = set & !(1 << v);
+			let without_closed_neighbourhood = without_v & !(adjacency[v] as usize);
+			independent[set] = (independent[without_v] + independent[without_closed_neighbourhood]) % MODULUS;
+		}
+		ColouringCounter { n, power: vec![1; 1 << n], independent }
+	}
+
+	/// Moves to one more colour. Returns the number of coverings of the vertexes
+	/// by `k` independent sets (modulo a large prime): it's positive iff the graph is `k`-colourable
+	fn next(&mut self) -> u64 {
+		let mut total = 0_u64;
+		for set in 0..self.power.len() {
+			self.power[set] = multiply_mod(self.power[set], self.independent[set]);
+			if (self.n - set.count_ones() as usize) & 1 == 0 {
+				total = (total + self.power[set]) % MODULUS;
+			} else {
+				total = (total + MODULUS - self.power[set]) % MODULUS;
+			}
+		}
+		total
+	}
+}
+
+/// Whether the graph can be properly coloured with `k` colours, O(2ⁿ n).
+/// Counting is done modulo a large prime, so a false negative is possible in theory
+pub fn is_k_colourable(graph: &Graph, k: usize) -> bool {
+	if has_loops(graph) {
+		return false;
+	}
+	if graph.vertexes() == 0 {
+		return true;
+	}
+	let mut counter = ColouringCounter::new(graph);
+	(0..k).map(|_| counter.next()).last().is_some_and(|count| count != 0)
+}
+
+/// Smallest number of colours in a proper colouring, `None` if the graph has a loop. O(2ⁿ n)
+pub fn chromatic_number(graph: &Graph) -> Option<usize> {
+	if has_loops(graph) {
+		return None;
+	}
+	if graph.vertexes() == 0 {
+		return Some(0);
+	}
+	let mut counter = ColouringCounter::new(graph);
+	(1..=graph.vertexes()).find(|_| counter.next() != 0)
+}
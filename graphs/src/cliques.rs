@@ -0,0 +1,164 @@
+use crate::{smallest_last_order, Graph};
+
+/// Sorted distinct neighbours, loops are ignored
+fn sorted_neighbours(graph: &Graph) -> Vec<Vec<usize>> {
+	graph.edges.iter()
+		.enumerate()
+		.map(|(v, edges)| {
+			let mut around = edges.iter().map(|edge| edge.to).filter(|&to| to != v).collect::<Vec<_>>();
+			around.sort();
+			around.dedup();
+			around
+		})
+		.collect()
+}
+
+fn intersection(a: &[usize], b: &[usize]) -> Vec<usize> {
+	a.iter().copied().filter(|x| b.binary_search(x).is_ok()).collect()
+}
+
+fn bron_kerbosch(neighbours: &[Vec<usize>], clique: &mut Vec<usize>, mut candidates: Vec<usize>, mut excluded: Vec<usize>,
+                 cliques: &mut Vec<Vec<usize>>) {
+	if candidates.is_empty() {
+		if excluded.is_empty() {
+			let mut found = clique.clone();
+			found.sort();
+			cliques.push(found);
+		}
+		return;
+	}
+	// Every maximal clique contains the pivot or one of its non-neighbours
+	let pivot = candidates.iter()
+		.chain(&excluded)
+		.copied()
+		.max_by_key(|&u| candidates.iter().filter(|v| neighbours[u].binary_search(v).is_ok()).count())
+		.unwrap();
+	let branches = candidates.iter().copied().filter(|v| neighbours[pivot].binary_search(v).is_err()).collect::<Vec<_>>();
+	for v in branches {
+		clique.push(v);
+		bron_kerbosch(neighbours, clique, intersection(&candidates, &neighbours[v]), intersection(&excluded, &neighbours[v]), cliques);
+		clique.pop();
+		candidates.retain(|&u| u != v);
+		excluded.push(v);
+		excluded.sort();
+	}
+}
+
+/// Every maximal clique (sorted) by Bron–Kerbosch with pivoting, the outer level goes in the degeneracy order,
+/// so there are O(d 3^(d/3)) calls per vertex for degeneracy d. Loops and parallel edges are ignored
+pub fn maximal_cliques(graph: &Graph) -> Vec<Vec<usize>> {
+	let neighbours = sorted_neighbours(graph);
+	let mut order = smallest_last_order(graph);
+	order.reverse();
+	let mut position = vec![0; order.len()];
+	for (i, &v) in order.iter().enumerate() {
+		position[v] = i;
+	}
+	let mut cliques = Vec::new();
+	for (i, &v) in order.iter().enumerate() {
+		let (excluded, candidates) = neighbours[v].iter().partition(|&&u| position[u] < i);
+		bron_kerbosch(&neighbours, &mut vec![v], candidates, excluded, &mut cliques);
+	}
+	cliques
+}
+
+/// Branch and bound where a greedy colouring of the candidates bounds the clique they can add (Tomita's MCQ)
+struct MaximumClique {
+	adjacent: Vec<Vec<bool>>,
+	best: Vec<usize>,
+}
+
+impl MaximumClique {
+	/// Candidates sorted by colour with the number of colours used up to each one
+	fn colour_sort(&self, candidates: &[usize]) -> (Vec<usize>, Vec<usize>) {
+		let mut classes: Vec<Vec<usize>> = Vec::new();
+		for &v in candidates {
+			match classes.iter_mut().find(|class| class.iter().all(|&u| !self.adjacent[u][v])) {
+				Some(class) => class.push(v),
+				None => classes.push(vec![v]),
+			}
+		}
+		let mut order = Vec::with_capacity(candidates.len());
+		let mut bounds = Vec::with_capacity(candidates.len());
+		for (colour, class) in classes.into_iter().enumerate() {
+			bounds.extend(std::iter::repeat_n(colour + 1, class.len()));
+			order.extend(class);
+		}
+		(order, bounds)
+	}
+
+	fn expand(&mut self, clique: &mut Vec<usize>, candidates: &[usize]) {
+		let (order, bounds) = self.colour_sort(candidates);
+		for i in (0..order.len()).rev() {
+			if clique.len() + bounds[i] <= self.best.len() {
+				return;
+			}
+			let v = order[i];
+			clique.push(v);
+			let next = order[..i].iter().copied().filter(|&u| self.adjacent[v][u]).collect::<Vec<_>>();
+			if next.is_empty() {
+				if clique.len() > self.best.len() {
+					self.best = clique.clone();
+				}
+			} else {
+				self.expand(clique, &next);
+			}
+			clique.pop();
+		}
+	}
+}
+
+/// Largest clique among the `allowed` vertexes (sorted)
+fn maximum_clique_within(graph: &Graph, allowed: impl Fn(usize) -> bool) -> Vec<usize> {
+	let n = graph.vertexes();
+	let mut adjacent = vec![vec![false; n]; n];
+	for (v, around) in sorted_neighbours(graph).into_iter().enumerate() {
+		for u in around {
+			adjacent[v][u] = true;
+		}
+	}
+	let candidates = (0..n).filter(|&v| allowed(v)).collect::<Vec<_>>();
+	let mut search = MaximumClique { adjacent, best: Vec::new() };
+	search.expand(&mut Vec::new(), &candidates);
+	let mut best = search.best;
+	best.sort();
+	best
+}
+
+/// Largest clique (sorted), exponential in the worst case. Loops and parallel edges are ignored
+pub fn maximum_clique(graph: &Graph) -> Vec<usize> {
+	maximum_clique_within(graph, |_| true)
+}
+
+fn has_loop(graph: &Graph, v: usize) -> bool {
+	graph.edges[v].iter().any(|edge| edge.to == v)
+}
+
+/// Largest set of pairwise non-adjacent vertexes (sorted): the maximum clique of the complement.
+/// A vertex with a loop is adjacent to itself, so it's never taken
+pub fn maximum_independent_set(graph: &Graph) -> Vec<usize> {
+	maximum_clique_within(&graph.complement(), |v| !has_loop(graph, v))
+}
+
+/// Smallest set of vertexes touching every edge (sorted): the rest of a maximum independent set.
+/// Exponential, meant for small graphs, see `approximate_vertex_cover` for the large ones
+pub fn minimum_vertex_cover(graph: &Graph) -> Vec<usize> {
+	let mut independent = vec![false; graph.vertexes()];
+	for v in maximum_independent_set(graph) {
+		independent[v] = true;
+	}
+	(0..graph.vertexes()).filter(|&v| !independent[v]).collect()
+}
+
+/// Vertex cover at most twice as large as the minimum one: the vertexes with loops
+/// and both ends of a maximal matching of the other edges (sorted). O(n + m)
+pub fn approximate_vertex_cover(graph: &Graph) -> Vec<usize> {
+	let mut covered = (0..graph.vertexes()).map(|v| has_loop(graph, v)).collect::<Vec<_>>();
+	for (from, to) in graph.edge_ends() {
+		if !covered[from] && !covered[to] {
+			covered[from] = true;
+			covered[to] = true;
+		}
+	}
+	(0..graph.vertexes()).filter(|&v| covered[v]).collect()
+}
@@ -0,0 +1,359 @@
+use crate::{tree_cycle, Cycle, Decomposition, GraphView, RootedTree};
+
+/// Distinct neighbours without the vertex itself: loops and parallel edges don't matter for vertex colouring
+fn simple_neighbours<G: GraphView>(graph: &G) -> Vec<Vec<usize>> {
+	(0..graph.vertexes())
+		.map(|v| {
+			let mut around = graph.neighbours(v).map(|edge| edge.to).filter(|&to| to != v).collect::<Vec<_>>();
+			around.sort();
+			around.dedup();
+			around
+		})
+		.collect()
+}
+
+/// Order of the vertexes for the greedy colouring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColouringOrder {
+	/// By index
+	Natural,
+	/// By degree, the largest first (Welsh–Powell)
+	LargestFirst,
+	/// Reversed order of removing a vertex of the smallest degree, uses at most degeneracy + 1 colours
+	SmallestLast,
+}
+
+/// Every vertex gets the smallest colour not taken by its neighbours coloured before it.
+/// Loops are ignored (a graph with a loop has no proper colouring). O(n + m)
+pub fn greedy_colouring_in_order<G: GraphView>(graph: &G, order: &[usize]) -> Decomposition {
+	let n = graph.vertexes();
+	debug_assert_eq!(order.len(), n);
+	let mut colour = vec![usize::MAX; n];
+	// taken[c] == v + 1 if a neighbour of v has colour c
+	let mut taken = vec![0; n + 1];
+	for &v in order {
+		for edge in graph.neighbours(v) {
+			if colour[edge.to] != usize::MAX && edge.to != v {
+				taken[colour[edge.to]] = v + 1;
+			}
+		}
+		colour[v] = (0..).find(|&c| taken[c] != v + 1).unwrap();
+	}
+	Decomposition::from_component_map(colour)
+}
+
+pub fn greedy_colouring<G: GraphView>(graph: &G, order: ColouringOrder) -> Decomposition {
+	let order = match order {
+		ColouringOrder::Natural => (0..graph.vertexes()).collect(),
+		ColouringOrder::LargestFirst => {
+			let neighbours = simple_neighbours(graph);
+			let mut order = (0..graph.vertexes()).collect::<Vec<_>>();
+			order.sort_by_key(|&v| std::cmp::Reverse(neighbours[v].len()));
+			order
+		}
+		ColouringOrder::SmallestLast => smallest_last_order(graph),
+	};
+	greedy_colouring_in_order(graph, &order)
+}
+
+/// Vertexes removed one by one with the smallest degree in the rest of the graph, in the reversed order:
+/// every vertex has at most degeneracy neighbours before it. O(n + m)
+pub fn smallest_last_order<G: GraphView>(graph: &G) -> Vec<usize> {
+	let n = graph.vertexes();
+	let neighbours = simple_neighbours(graph);
+	let mut degree = neighbours.iter().map(|around| around.len()).collect::<Vec<_>>();
+	// Buckets by degree with lazy deletion: an entry is stale if the degree has changed since
+	let mut buckets = vec![Vec::new(); n];
+	for v in 0..n {
+		buckets[degree[v]].push(v);
+	}
+	let mut removed = vec![false; n];
+	let mut order = Vec::with_capacity(n);
+	let mut smallest = 0;
+	while order.len() < n {
+		let Some(v) = buckets[smallest].pop() else {
+			smallest += 1;
+			continue;
+		};
+		if removed[v] || degree[v] != smallest {
+			continue;
+		}
+		removed[v] = true;
+		order.push(v);
+		for &u in &neighbours[v] {
+			if !removed[u] {
+				degree[u] -= 1;
+				buckets[degree[u]].push(u);
+				smallest = smallest.min(degree[u]);
+			}
+		}
+	}
+	order.reverse();
+	order
+}
+
+/// Partial colouring with the number of distinct colours around every vertex
+struct Saturation<'a> {
+	neighbours: &'a [Vec<usize>],
+	colour: Vec<Option<usize>>,
+	/// `around[v][c]`: neighbours of `v` with colour `c`, grows on demand
+	around: Vec<Vec<usize>>,
+	saturation: Vec<usize>,
+}
+
+impl<'a> Saturation<'a> {
+	fn new(neighbours: &'a [Vec<usize>]) -> Self {
+		let n = neighbours.len();
+		Saturation { neighbours, colour: vec![None; n], around: vec![Vec::new(); n], saturation: vec![0; n] }
+	}
+
+	fn is_free(&self, v: usize, c: usize) -> bool {
+		self.around[v].get(c).is_none_or(|&count| count == 0)
+	}
+
+	fn assign(&mut self, v: usize, c: usize) {
+		self.colour[v] = Some(c);
+		for &u in &self.neighbours[v] {
+			if self.around[u].len() <= c {
+				self.around[u].resize(c + 1, 0);
+			}
+			self.around[u][c] += 1;
+			if self.around[u][c] == 1 {
+				self.saturation[u] += 1;
+			}
+		}
+	}
+
+	fn unassign(&mut self, v: usize) {
+		let c = self.colour[v].take().unwrap();
+		for &u in &self.neighbours[v] {
+			self.around[u][c] -= 1;
+			if self.around[u][c] == 0 {
+				self.saturation[u] -= 1;
+			}
+		}
+	}
+
+	/// Uncoloured vertex with the most distinct colours around, ties broken by degree
+	fn most_saturated(&self) -> Option<usize> {
+		(0..self.colour.len())
+			.filter(|&v| self.colour[v].is_none())
+			.max_by_key(|&v| (self.saturation[v], self.neighbours[v].len(), std::cmp::Reverse(v)))
+	}
+}
+
+/// DSATUR (Brélaz): colours the vertex with the most distinct colours around first.
+/// Optimal for bipartite graphs. Loops are ignored. O(n² + m)
+pub fn dsatur<G: GraphView>(graph: &G) -> Decomposition {
+	let neighbours = simple_neighbours(graph);
+	let mut state = Saturation::new(&neighbours);
+	while let Some(v) = state.most_saturated() {
+		let c = (0..).find(|&c| state.is_free(v, c)).unwrap();
+		state.assign(v, c);
+	}
+	Decomposition::from_component_map(state.colour.into_iter().map(Option::unwrap).collect())
+}
+
+struct BranchAndBound<'a> {
+	state: Saturation<'a>,
+	best: Vec<usize>,
+	best_count: usize,
+	/// Size of a clique: no colouring can do better
+	lower_bound: usize,
+}
+
+impl BranchAndBound<'_> {
+	fn search(&mut self, used: usize) {
+		if used >= self.best_count {
+			return;
+		}
+		let Some(v) = self.state.most_saturated() else {
+			self.best = self.state.colour.iter().map(|c| c.unwrap()).collect();
+			self.best_count = used;
+			return;
+		};
+		// A new colour is interchangeable with any other unused one, so only the first of them is tried
+		for c in 0..=used {
+			if c + 1 >= self.best_count || self.best_count == self.lower_bound {
+				return;
+			}
+			if self.state.is_free(v, c) {
+				self.state.assign(v, c);
+				self.search(used.max(c + 1));
+				self.state.unassign(v);
+			}
+		}
+	}
+}
+
+/// Colouring with the fewest colours: DSATUR branch and bound, starting from the DSATUR colouring
+/// and stopping at the size of a greedy clique. Exponential, meant for small graphs. Loops are ignored
+pub fn exact_colouring<G: GraphView>(graph: &G) -> Decomposition {
+	let n = graph.vertexes();
+	let neighbours = simple_neighbours(graph);
+	let initial = dsatur(graph);
+
+	let mut by_degree = (0..n).collect::<Vec<_>>();
+	by_degree.sort_by_key(|&v| std::cmp::Reverse(neighbours[v].len()));
+	let mut clique: Vec<usize> = Vec::new();
+	for v in by_degree {
+		if clique.iter().all(|u| neighbours[v].binary_search(u).is_ok()) {
+			clique.push(v);
+		}
+	}
+
+	let mut search = BranchAndBound {
+		state: Saturation::new(&neighbours),
+		best_count: initial.component_list.len(),
+		best: initial.component_map,
+		lower_bound: clique.len(),
+	};
+	search.search(0);
+	Decomposition::from_component_map(search.best)
+}
+
+/// Cycle of odd length, `edges[i]` joins `vertexes[i]` and `vertexes[(i + 1) % len]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OddCycle {
+	pub vertexes: Vec<usize>,
+	pub edges: Vec<usize>,
+}
+
+/// Colouring by the parity of the BFS depth or a simple odd cycle proving that there is none
+/// (a loop is an odd cycle of length 1). O(n + m)
+pub fn two_colouring<G: GraphView>(graph: &G) -> Result<Decomposition, OddCycle> {
+	let forest = RootedTree::forest(graph);
+	let side = |v: usize| forest.depth[v] & 1;
+	for u in 0..graph.vertexes() {
+		let Some(conflict) = graph.neighbours(u).find(|edge| side(edge.to) == side(u)) else {
+			continue;
+		};
+		// Tree paths up to the common ancestor make an even path, the edge closes it
+		let Cycle { vertexes, edges } = tree_cycle(&forest, u, conflict.to, conflict.edge_index);
+		return Err(OddCycle { vertexes, edges });
+	}
+	Ok(Decomposition::from_component_map((0..graph.vertexes()).map(side).collect()))
+}
+
+/// Partial edge colouring with the edge of every colour at every vertex
+struct EdgeColouring {
+	ends: Vec<(usize, usize)>,
+	colour: Vec<Option<usize>>,
+	/// `at[v][c]`: edge of colour `c` at `v`
+	at: Vec<Vec<Option<usize>>>,
+}
+
+impl EdgeColouring {
+	fn other(&self, edge: usize, v: usize) -> usize {
+		let (a, b) = self.ends[edge];
+		if a == v { b } else { a }
+	}
+
+	fn is_free(&self, v: usize, c: usize) -> bool {
+		self.at[v][c].is_none()
+	}
+
+	fn set(&mut self, edge: usize, c: Option<usize>) {
+		let (a, b) = self.ends[edge];
+		if let Some(old) = self.colour[edge] {
+			self.at[a][old] = None;
+			self.at[b][old] = None;
+		}
+		if let Some(c) = c {
+			debug_assert!(self.is_free(a, c) && self.is_free(b, c));
+			self.at[a][c] = Some(edge);
+			self.at[b][c] = Some(edge);
+		}
+		self.colour[edge] = c;
+	}
+
+	/// Misra–Gries step: colours `edge` from `u`, recolouring a fan of `u` and a path
+	fn colour_edge(&mut self, edge: usize, u: usize) {
+		let colours = self.at[u].len();
+		// Fan: coloured edges `u f` where the colour of every next one is free at the previous end
+		let mut fan = vec![(self.other(edge, u), edge)];
+		loop {
+			let last = fan.last().unwrap().0;
+			let next = (0..colours)
+				.filter(|&c| self.is_free(last, c))
+				.filter_map(|c| self.at[u][c])
+				.map(|e| (self.other(e, u), e))
+				.find(|&(w, _)| fan.iter().all(|&(f, _)| f != w));
+			match next {
+				Some(next) => fan.push(next),
+				None => break,
+			}
+		}
+		let c = (0..colours).find(|&c| self.is_free(u, c)).unwrap();
+		let d = (0..colours).find(|&c| self.is_free(fan.last().unwrap().0, c)).unwrap();
+
+		// Swap c and d on the path of these colours starting at u, then d is free at u
+		let mut path = Vec::new();
+		let (mut v, mut next) = (u, d);
+		while let Some(e) = self.at[v][next] {
+			path.push(e);
+			v = self.other(e, v);
+			next = if next == c { d } else { c };
+		}
+		let swapped = path.iter().map(|&e| if self.colour[e] == Some(c) { d } else { c }).collect::<Vec<_>>();
+		for &e in &path {
+			self.set(e, None);
+		}
+		for (&e, &colour) in path.iter().zip(&swapped) {
+			self.set(e, Some(colour));
+		}
+
+		// The first fan vertex where d is free ends the part of the fan that is rotated
+		let mut end = 0;
+		while !self.is_free(fan[end].0, d) {
+			end += 1;
+			debug_assert!(self.is_free(fan[end - 1].0, self.colour[fan[end].1].unwrap()));
+		}
+		let shifted = fan[1..=end].iter().map(|&(_, e)| self.colour[e].unwrap()).collect::<Vec<_>>();
+		for &(_, e) in &fan[1..=end] {
+			self.set(e, None);
+		}
+		for (&(_, e), &colour) in fan.iter().zip(&shifted) {
+			self.set(e, Some(colour));
+		}
+		self.set(fan[end].1, Some(d));
+	}
+}
+
+/// Misra–Gries edge colouring of a simple graph with at most Δ + 1 colours, the classes are sets of
+/// edge indexes, which have to be `0..graph.edges()` (not a graph after `remove_edges` or an `EdgeSubgraph`). O(nm)
+pub fn edge_colouring<G: GraphView>(graph: &G) -> Decomposition {
+	let n = graph.vertexes();
+	let mut ends = vec![(usize::MAX, usize::MAX); graph.edge_index_bound()];
+	let mut max_degree = 0;
+	for v in 0..n {
+		max_degree = max_degree.max(graph.degree(v));
+		for edge in graph.neighbours(v) {
+			debug_assert!(edge.to != v, "Loops can't be coloured");
+			ends[edge.edge_index] = (v, edge.to);
+		}
+	}
+	debug_assert!(ends.iter().all(|&(from, _)| from != usize::MAX), "Edge indexes should be compact");
+	debug_assert!(simple_neighbours(graph).iter().enumerate().all(|(v, around)| around.len() == graph.degree(v)),
+	              "Graph should be simple");
+
+	let mut state = EdgeColouring { ends, colour: vec![None; graph.edge_index_bound()], at: vec![vec![None; max_degree + 1]; n] };
+	for edge in 0..graph.edge_index_bound() {
+		state.colour_edge(edge, state.ends[edge].0);
+	}
+
+	// Rotations may leave some colours unused
+	let mut index_of = vec![usize::MAX; max_degree + 1];
+	let mut used = 0;
+	let component_map = state.colour.iter()
+		.map(|c| {
+			let c = c.unwrap();
+			if index_of[c] == usize::MAX {
+				index_of[c] = used;
+				used += 1;
+			}
+			index_of[c]
+		})
+		.collect();
+	Decomposition::from_component_map(component_map)
+}
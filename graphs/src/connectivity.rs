@@ -0,0 +1,238 @@
+use crate::{with_weights, FlowNetwork, Graph, GraphView, RootedTree, WeightedGraph, INFINITE_CAPACITY};
+
+/// Cut of an undirected graph: `side` and the rest of the vertexes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+	/// Total weight of the crossing edges
+	pub weight: i64,
+	/// Sorted
+	pub side: Vec<usize>,
+	/// Edge indexes with one end on the side, sorted
+	pub edges: Vec<usize>,
+}
+
+impl MinCut {
+	fn new<G: GraphView>(graph: &G, weight: i64, mut side: Vec<usize>) -> Self {
+		side.sort();
+		let mut on_side = vec![false; graph.vertexes()];
+		for &v in &side {
+			on_side[v] = true;
+		}
+		let mut edges = side.iter()
+			.flat_map(|&v| graph.neighbours(v))
+			.filter(|edge| !on_side[edge.to])
+			.map(|edge| edge.edge_index)
+			.collect::<Vec<_>>();
+		edges.sort();
+		MinCut { weight, side, edges }
+	}
+}
+
+/// Minimum cut of an undirected graph with non-negative weights by Stoer–Wagner: in every phase the last
+/// vertex of a maximum adjacency order is cut off from the rest as cheaply as anything that separates it
+/// from the previous one, then the two are merged. Loops are ignored, `None` for less than two vertexes. O(n³)
+pub fn stoer_wagner<G>(graph: &G) -> Option<MinCut>
+	where G: GraphView, G::Weight: Into<i64>
+{
+	let n = graph.vertexes();
+	if n < 2 {
+		return None;
+	}
+	let mut weight = vec![vec![0_i64; n]; n];
+	for (v, row) in weight.iter_mut().enumerate() {
+		for edge in graph.neighbours(v).filter(|edge| edge.to != v) {
+			let w = edge.weight.into();
+			debug_assert!(w >= 0, "Weights should be non-negative");
+			row[edge.to] += w;
+		}
+	}
+	// Original vertexes merged into every active one
+	let mut merged = (0..n).map(|v| vec![v]).collect::<Vec<_>>();
+	let mut active = (0..n).collect::<Vec<_>>();
+	let mut best: Option<(i64, Vec<usize>)> = None;
+	while active.len() > 1 {
+		let mut connection = vec![0_i64; n];
+		let mut added = vec![false; n];
+		let (mut previous, mut last) = (active[0], active[0]);
+		for _ in 0..active.len() {
+			let next = *active.iter()
+				.filter(|&&v| !added[v])
+				.max_by_key(|&&v| connection[v])
+				.unwrap();
+			added[next] = true;
+			for &v in &active {
+				connection[v] += weight[next][v];
+			}
+			(previous, last) = (last, next);
+		}
+		let phase = connection[last] - weight[last][last];
+		if best.as_ref().is_none_or(|(cut, _)| phase < *cut) {
+			best = Some((phase, merged[last].clone()));
+		}
+		let absorbed = weight[last].clone();
+		for (v, row) in weight.iter_mut().enumerate() {
+			row[previous] += absorbed[v];
+		}
+		let merged_row = weight.iter().map(|row| row[previous]).collect::<Vec<_>>();
+		weight[previous] = merged_row;
+		let vertexes = std::mem::take(&mut merged[last]);
+		merged[previous].extend(vertexes);
+		active.retain(|&v| v != last);
+	}
+	let (cut, side) = best.unwrap();
+	Some(MinCut::new(graph, cut, side))
+}
+
+/// Fewest edges whose removal disconnects the graph, with the cut (Stoer–Wagner with unit weights),
+/// `None` for less than two vertexes
+pub fn edge_connectivity(graph: &Graph) -> Option<MinCut> {
+	stoer_wagner(&with_weights(graph, |_| 1_i64))
+}
+
+/// Split network: vertex `v` is the arc `2v → 2v + 1` of capacity 1 (infinite for the terminals),
+/// every edge is a pair of infinite arcs between the split vertexes
+fn split_network(graph: &Graph, source: usize, sink: usize) -> FlowNetwork {
+	let n = graph.vertexes();
+	let mut network = FlowNetwork::new(2 * n);
+	for v in 0..n {
+		let capacity = if v == source || v == sink { INFINITE_CAPACITY } else { 1 };
+		network.add_arc(2 * v, 2 * v + 1, capacity, 0);
+	}
+	for (from, edges) in graph.edges.iter().enumerate() {
+		for edge in edges.iter().filter(|edge| edge.to != from) {
+			network.add_arc(2 * from + 1, 2 * edge.to, INFINITE_CAPACITY, 0);
+		}
+	}
+	network
+}
+
+/// Smallest set of other vertexes separating `source` from `sink` (sorted) by max-flow with vertex
+/// splitting, `None` if they are adjacent or the same vertex. O(n² m)
+pub fn minimum_vertex_separator_between(graph: &Graph, source: usize, sink: usize) -> Option<Vec<usize>> {
+	if source == sink || graph.edges[source].iter().any(|edge| edge.to == sink) {
+		return None;
+	}
+	let mut network = split_network(graph, source, sink);
+	network.max_flow(2 * source + 1, 2 * sink);
+	let reachable = network.reachable_in_residual(2 * source + 1);
+	Some((0..graph.vertexes()).filter(|&v| reachable[2 * v] && !reachable[2 * v + 1]).collect())
+}
+
+/// Smallest set of vertexes whose removal disconnects an undirected graph (sorted), `None` for a complete
+/// graph (parallel edges and loops aside) where there is no such set. Even's algorithm: a minimum separator
+/// misses one of the first κ + 1 vertexes, so only they are tried as sources
+pub fn minimum_vertex_separator(graph: &Graph) -> Option<Vec<usize>> {
+	let n = graph.vertexes();
+	let mut best: Option<Vec<usize>> = None;
+	let mut source = 0;
+	while source < n && best.as_ref().is_none_or(|best| source <= best.len()) {
+		for sink in source + 1..n {
+			let Some(separator) = minimum_vertex_separator_between(graph, source, sink) else {
+				continue;
+			};
+			if best.as_ref().is_none_or(|best| separator.len() < best.len()) {
+				best = Some(separator);
+			}
+		}
+		source += 1;
+	}
+	best
+}
+
+/// Fewest vertexes whose removal disconnects the graph or leaves a single vertex, `n - 1` for a complete graph
+pub fn vertex_connectivity(graph: &Graph) -> usize {
+	minimum_vertex_separator(graph).map_or(graph.vertexes().saturating_sub(1), |separator| separator.len())
+}
+
+/// Tree on the vertexes of an undirected graph where the minimum cut between any two vertexes is the
+/// lightest edge on the tree path between them, and removing that edge splits the vertexes as the cut does
+#[derive(Debug, Clone)]
+pub struct GomoryHuTree {
+	/// An edge weighs as much as the minimum cut between its ends
+	pub tree: WeightedGraph<i64>,
+	rooted: RootedTree,
+	/// `(edge_index, u, v)` of every edge of the original graph but loops, by index, to list the cut edges
+	edge_ends: Vec<(usize, usize, usize)>,
+}
+
+impl GomoryHuTree {
+	/// Gusfield's algorithm: n - 1 maximum flows in the original graph without contractions, O(n³ m).
+	/// Weights should be non-negative
+	pub fn new<G>(graph: &G) -> Self
+		where G: GraphView, G::Weight: Into<i64>
+	{
+		let n = graph.vertexes();
+		let mut network = FlowNetwork::new(n);
+		let mut edge_ends = Vec::new();
+		for v in 0..n {
+			for edge in graph.neighbours(v).filter(|edge| edge.to != v) {
+				network.add_arc(v, edge.to, edge.weight.into(), 0);
+				if v < edge.to {
+					edge_ends.push((edge.edge_index, v, edge.to));
+				}
+			}
+		}
+		edge_ends.sort();
+		// Tree rooted at 0 as parent pointers and the weights of the edges up
+		let mut parent = vec![0; n];
+		let mut weight = vec![0; n];
+		for s in 1..n {
+			let t = parent[s];
+			network.clear_flow();
+			weight[s] = network.max_flow(s, t);
+			let side = network.reachable_in_residual(s);
+			for v in 0..n {
+				if v != s && side[v] && parent[v] == t {
+					parent[v] = s;
+				}
+			}
+			// Keeps the tree a cut tree and not only an equivalent flow tree
+			if side[parent[t]] {
+				parent[s] = parent[t];
+				parent[t] = s;
+				weight.swap(s, t);
+			}
+		}
+		let mut tree = WeightedGraph::new(n);
+		for v in 1..n {
+			tree.add_weighted_undirected_edge(v, parent[v], weight[v]);
+		}
+		let rooted = RootedTree::forest(&tree);
+		GomoryHuTree { tree, rooted, edge_ends }
+	}
+
+	/// Minimum cut between two different vertexes: the lightest edge on the tree path splits the tree in two.
+	/// The side is the part with `u`, the crossing edges are those of the original graph
+	pub fn min_cut(&self, u: usize, v: usize) -> MinCut {
+		debug_assert_ne!(u, v);
+		let weight_above = |x: usize| self.tree.edges[x].iter()
+			.find(|edge| Some(edge.to) == self.rooted.parent[x])
+			.unwrap()
+			.weight;
+		// Lightest edge on the path, by its lower end
+		let (mut a, mut b) = (u, v);
+		let mut lightest: Option<usize> = None;
+		while a != b {
+			let x = if self.rooted.depth[a] >= self.rooted.depth[b] { &mut a } else { &mut b };
+			if lightest.is_none_or(|y| weight_above(*x) < weight_above(y)) {
+				lightest = Some(*x);
+			}
+			*x = self.rooted.parent[*x].unwrap();
+		}
+		let lower = lightest.unwrap();
+		let mut below = vec![false; self.tree.vertexes()];
+		for &x in &self.rooted.order {
+			below[x] = x == lower || self.rooted.parent[x].is_some_and(|p| below[p]);
+		}
+		let side = (0..self.tree.vertexes()).filter(|&x| below[x] == below[u]).collect();
+		let edges = self.edge_ends.iter()
+			.filter(|&&(_, a, b)| below[a] != below[b])
+			.map(|&(edge_index, _, _)| edge_index)
+			.collect();
+		MinCut { weight: weight_above(lower), side, edges }
+	}
+
+	pub fn min_cut_value(&self, u: usize, v: usize) -> i64 {
+		self.min_cut(u, v).weight
+	}
+}
@@ -0,0 +1,119 @@
+use std::fmt::Debug;
+use crate::{GraphView, WeightedEdge, WeightedGraph};
+
+/// Frozen graph in the compressed sparse row form: all adjacency lists in one array,
+/// so there's a single allocation and neighbours are contiguous in memory
+#[derive(Debug, Clone)]
+pub struct CsrGraph<T> {
+	/// Edges going out of `v` are `targets[offsets[v]..offsets[v + 1]]`
+	pub offsets: Vec<usize>,
+	pub targets: Vec<WeightedEdge<T>>,
+	pub total_edges: usize,
+}
+
+impl<T: Clone + Copy + Debug> CsrGraph<T> {
+	/// Counting sort of `(from, edge)` arcs by `from`, the order of arcs of a vertex is kept
+	fn from_arcs_by_source<I>(n: usize, total_edges: usize, arcs: I) -> Self
+		where I: Iterator<Item = (usize, WeightedEdge<T>)> + Clone
+	{
+		let mut offsets = vec![0; n + 1];
+		for (from, _) in arcs.clone() {
+			offsets[from + 1] += 1;
+		}
+		for v in 0..n {
+			offsets[v + 1] += offsets[v];
+		}
+		let mut next = offsets.clone();
+		// Filled out of order (every slot is written exactly once), any arc will do as a placeholder
+		let mut targets = match arcs.clone().next() {
+			Some((_, edge)) => vec![edge; offsets[n]],
+			None => Vec::new(),
+		};
+		for (from, edge) in arcs {
+			targets[next[from]] = edge;
+			next[from] += 1;
+		}
+		CsrGraph { offsets, targets, total_edges }
+	}
+
+	/// Same vertexes, edges, indexes and order of neighbours
+	pub fn from_view<G: GraphView<Weight = T>>(graph: &G) -> Self {
+		let mut offsets = Vec::with_capacity(graph.vertexes() + 1);
+		let mut targets = Vec::new();
+		offsets.push(0);
+		for v in 0..graph.vertexes() {
+			targets.extend(graph.neighbours(v));
+			offsets.push(targets.len());
+		}
+		CsrGraph { offsets, targets, total_edges: graph.edge_index_bound() }
+	}
+
+	pub fn from_graph(graph: &WeightedGraph<T>) -> Self {
+		Self::from_view(graph)
+	}
+
+	/// Built directly from `(from, to, weight)`, edge `i` is `arcs[i]`
+	/// (without the intermediate `WeightedGraph`, which matters for large inputs)
+	pub fn from_arcs(n: usize, arcs: &[(usize, usize, T)], directed: bool) -> Self {
+		let forward = arcs.iter()
+			.enumerate()
+			.map(|(i, &(from, to, weight))| (from, WeightedEdge { to, edge_index: i, weight }));
+		if directed {
+			return Self::from_arcs_by_source(n, arcs.len(), forward);
+		}
+		let backward = arcs.iter()
+			.enumerate()
+			.map(|(i, &(from, to, weight))| (to, WeightedEdge { to: from, edge_index: i, weight }));
+		Self::from_arcs_by_source(n, arcs.len(), forward.chain(backward))
+	}
+
+	/// Graph with every arc turned around (edge indexes are kept)
+	pub fn reversed<G: GraphView<Weight = T>>(graph: &G) -> Self {
+		let arcs: Vec<_> = (0..graph.vertexes())
+			.flat_map(|from| graph.neighbours(from).map(move |edge| (edge.to, WeightedEdge { to: from, ..edge })))
+			.collect();
+		Self::from_arcs_by_source(graph.vertexes(), graph.edge_index_bound(), arcs.into_iter())
+	}
+
+	pub fn vertexes(&self) -> usize {
+		self.offsets.len() - 1
+	}
+
+	pub fn edges(&self) -> usize {
+		self.total_edges
+	}
+
+	pub fn edges_of(&self, vertex: usize) -> &[WeightedEdge<T>] {
+		&self.targets[self.offsets[vertex]..self.offsets[vertex + 1]]
+	}
+
+	pub fn to_graph(&self) -> WeightedGraph<T> {
+		let mut graph = WeightedGraph::new(self.vertexes());
+		for v in 0..self.vertexes() {
+			graph.edges[v].extend_from_slice(self.edges_of(v));
+		}
+		graph.total_edges = self.total_edges;
+		graph.edge_index_bound = self.total_edges;
+		graph
+	}
+}
+
+impl<T: Clone + Copy + Debug> GraphView for CsrGraph<T> {
+	type Weight = T;
+
+	fn vertexes(&self) -> usize {
+		self.offsets.len() - 1
+	}
+
+	fn edge_index_bound(&self) -> usize {
+		self.total_edges
+	}
+
+	fn neighbours(&self, vertex: usize) -> impl Iterator<Item = WeightedEdge<T>> + '_ {
+		self.edges_of(vertex).iter().copied()
+	}
+
+	fn degree(&self, vertex: usize) -> usize {
+		self.offsets[vertex + 1] - self.offsets[vertex]
+	}
+}
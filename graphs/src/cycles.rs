@@ -0,0 +1,303 @@
+use crate::{bfs, DFSSpace, GraphView, RootedTree, VertexSubgraph};
+
+/// Cycle, `edges[i]` goes from `vertexes[i]` to `vertexes[(i + 1) % len]` (a loop has a single vertex)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+	pub vertexes: Vec<usize>,
+	pub edges: Vec<usize>,
+}
+
+/// Cycle of the tree paths from `u` and `w` up to their common ancestor, closed by the edge `w u`
+pub(crate) fn tree_cycle(tree: &RootedTree, u: usize, w: usize, edge_index: usize) -> Cycle {
+	let (mut a, mut b) = (u, w);
+	let (mut from_a, mut from_b) = (vec![a], vec![b]);
+	let (mut edges_a, mut edges_b) = (Vec::new(), Vec::new());
+	while a != b {
+		if tree.depth[a] >= tree.depth[b] {
+			edges_a.push(tree.parent_edge[a].unwrap());
+			a = tree.parent[a].unwrap();
+			from_a.push(a);
+		} else {
+			edges_b.push(tree.parent_edge[b].unwrap());
+			b = tree.parent[b].unwrap();
+			from_b.push(b);
+		}
+	}
+	from_b.pop();
+	from_a.extend(from_b.into_iter().rev());
+	edges_a.extend(edges_b.into_iter().rev());
+	edges_a.push(edge_index);
+	Cycle { vertexes: from_a, edges: edges_a }
+}
+
+/// Some cycle, see `DFSSpace::find_cycle`
+pub fn find_cycle<G: GraphView>(graph: &G, directed: bool) -> Option<Cycle> {
+	DFSSpace::new(graph).find_cycle(graph, directed)
+}
+
+/// Cycle with the fewest edges by BFS from every vertex. In a directed graph the shortest cycle through
+/// the source closes a shortest path; in an undirected one the shortest closed walk made of two tree paths
+/// and an edge off the tree is always a simple cycle. O(nm)
+pub fn shortest_cycle<G: GraphView>(graph: &G, directed: bool) -> Option<Cycle> {
+	let mut best: Option<Cycle> = None;
+	for source in 0..graph.vertexes() {
+		let paths = bfs(graph, source);
+		let parent_edge = |v: usize| paths.parent[v].map(|(_, edge_index)| edge_index);
+		for u in (0..graph.vertexes()).filter(|&u| paths.distance[u].is_some()) {
+			for edge in graph.neighbours(u) {
+				let Some(to) = paths.distance[edge.to] else {
+					continue;
+				};
+				let length = if directed {
+					if edge.to != source {
+						continue;
+					}
+					paths.distance[u].unwrap() + 1
+				} else {
+					if parent_edge(u) == Some(edge.edge_index) || parent_edge(edge.to) == Some(edge.edge_index) {
+						continue;
+					}
+					paths.distance[u].unwrap() + to + 1
+				};
+				if best.as_ref().is_some_and(|best| best.edges.len() <= length) {
+					continue;
+				}
+				let mut vertexes = paths.path_to(u).unwrap();
+				let mut edges = paths.edges_to(u).unwrap();
+				edges.push(edge.edge_index);
+				if !directed {
+					let back = paths.path_to(edge.to).unwrap();
+					vertexes.extend(back[1..].iter().rev());
+					edges.extend(paths.edges_to(edge.to).unwrap().into_iter().rev());
+				}
+				best = Some(Cycle { vertexes, edges });
+			}
+		}
+	}
+	best
+}
+
+/// Number of edges in the shortest cycle, `None` for an acyclic graph
+pub fn girth<G: GraphView>(graph: &G, directed: bool) -> Option<usize> {
+	shortest_cycle(graph, directed).map(|cycle| cycle.edges.len())
+}
+
+/// Fundamental cycles of an undirected graph with respect to a BFS spanning forest: one for every edge
+/// off the forest, by edge index. They form a basis of the cycle space, m - n + c cycles for c components
+pub fn cycle_basis<G: GraphView>(graph: &G) -> Vec<Cycle> {
+	let forest = RootedTree::forest(graph);
+	let mut ends = vec![None; graph.edge_index_bound()];
+	for v in 0..graph.vertexes() {
+		for edge in graph.neighbours(v) {
+			ends[edge.edge_index] = Some((v, edge.to));
+		}
+	}
+	ends.into_iter()
+		.enumerate()
+		.filter_map(|(edge_index, ends)| ends.map(|(u, w)| (edge_index, u, w)))
+		.filter(|&(edge_index, u, w)| forest.parent_edge[u] != Some(edge_index) && forest.parent_edge[w] != Some(edge_index))
+		.map(|(edge_index, u, w)| tree_cycle(&forest, w, u, edge_index))
+		.collect()
+}
+
+/// Cycle of a directed graph with the smallest mean weight and its total weight
+/// (the mean is `weight / cycle.edges.len()`), `None` for an acyclic graph. Karp's algorithm, O(nm)
+pub fn minimum_mean_cycle<G>(graph: &G) -> Option<(i64, Cycle)>
+	where G: GraphView, G::Weight: Into<i64>
+{
+	let n = graph.vertexes();
+	// walk[k][v]: lightest walk of exactly k edges ending at `v` and its last edge
+	let mut walk = vec![vec![None; n]; n + 1];
+	let mut last = vec![vec![None; n]; n + 1];
+	walk[0] = vec![Some(0_i64); n];
+	for k in 0..n {
+		for v in 0..n {
+			let Some(weight) = walk[k][v] else {
+				continue;
+			};
+			for edge in graph.neighbours(v) {
+				let candidate = weight + edge.weight.into();
+				if walk[k + 1][edge.to].is_none_or(|current| candidate < current) {
+					walk[k + 1][edge.to] = Some(candidate);
+					last[k + 1][edge.to] = Some((v, edge.edge_index));
+				}
+			}
+		}
+	}
+
+	// Minimum over v of the maximum over k of (walk[n][v] - walk[k][v]) / (n - k), fractions compared exactly
+	let less = |(a, b): (i64, usize), (c, d): (i64, usize)| (a as i128) * (d as i128) < (c as i128) * (b as i128);
+	let mut best: Option<((i64, usize), usize)> = None;
+	for (v, &full) in walk[n].iter().enumerate() {
+		let Some(full) = full else {
+			continue;
+		};
+		let worst = (0..n)
+			.filter_map(|k| walk[k][v].map(|weight| (full - weight, n - k)))
+			.reduce(|a, b| if less(a, b) { b } else { a })
+			.unwrap();
+		if best.is_none_or(|(mean, _)| less(worst, mean)) {
+			best = Some((worst, v));
+		}
+	}
+	let (_, end) = best?;
+
+	// Every cycle on the lightest walk of n edges to `end` has the minimum mean, take the first closed one
+	let mut steps = Vec::with_capacity(n);
+	let mut v = end;
+	for k in (1..=n).rev() {
+		let (from, edge_index) = last[k][v].unwrap();
+		steps.push((from, edge_index));
+		v = from;
+	}
+	steps.reverse();
+	let mut seen_at = vec![usize::MAX; n];
+	for (i, &(from, _)) in steps.iter().enumerate() {
+		if seen_at[from] != usize::MAX {
+			let start = seen_at[from];
+			let (vertexes, edges): (Vec<_>, Vec<_>) = steps[start..i].iter().copied().unzip();
+			let weight = edges_weight(graph, &vertexes, &edges);
+			return Some((weight, Cycle { vertexes, edges }));
+		}
+		seen_at[from] = i;
+	}
+	let start = seen_at[end];
+	let (vertexes, edges): (Vec<_>, Vec<_>) = steps[start..].iter().copied().unzip();
+	let weight = edges_weight(graph, &vertexes, &edges);
+	Some((weight, Cycle { vertexes, edges }))
+}
+
+/// Cycle of a directed graph with a negative total weight, `None` if there is none. Bellman–Ford from all
+/// vertexes at once: if something still improves in the n-th round, walking back along the last improving
+/// edges ends up on such a cycle. O(nm)
+pub fn find_negative_cycle<G>(graph: &G) -> Option<Cycle>
+	where G: GraphView, G::Weight: Into<i64>
+{
+	let n = graph.vertexes();
+	let mut distance = vec![0_i64; n];
+	let mut last = vec![None; n];
+	let mut improved = None;
+	for _ in 0..n {
+		improved = None;
+		for v in 0..n {
+			for edge in graph.neighbours(v) {
+				let candidate = distance[v] + edge.weight.into();
+				if candidate < distance[edge.to] {
+					distance[edge.to] = candidate;
+					last[edge.to] = Some((v, edge.edge_index));
+					improved = Some(edge.to);
+				}
+			}
+		}
+		improved?;
+	}
+
+	// n steps back surely get onto the cycle
+	let mut end = improved?;
+	for _ in 0..n {
+		end = last[end].unwrap().0;
+	}
+	let mut steps = Vec::new();
+	let mut v = end;
+	loop {
+		let (from, edge_index) = last[v].unwrap();
+		steps.push((from, edge_index));
+		v = from;
+		if v == end {
+			break;
+		}
+	}
+	steps.reverse();
+	let (vertexes, edges) = steps.into_iter().unzip();
+	Some(Cycle { vertexes, edges })
+}
+
+fn edges_weight<G>(graph: &G, vertexes: &[usize], edges: &[usize]) -> i64
+	where G: GraphView, G::Weight: Into<i64>
+{
+	vertexes.iter()
+		.zip(edges)
+		.map(|(&v, &edge_index)| graph.neighbours(v).find(|edge| edge.edge_index == edge_index).unwrap().weight.into())
+		.sum()
+}
+
+/// Johnson's search for the cycles through `start` in its strongly connected component
+struct Johnson<'a, G> {
+	graph: &'a G,
+	start: usize,
+	in_component: Vec<bool>,
+	blocked: Vec<bool>,
+	/// Vertexes to unblock together with the key one
+	blocked_by: Vec<Vec<usize>>,
+	vertexes: Vec<usize>,
+	edges: Vec<usize>,
+	cycles: Vec<Cycle>,
+}
+
+impl<G: GraphView> Johnson<'_, G> {
+	fn unblock(&mut self, v: usize) {
+		self.blocked[v] = false;
+		for u in std::mem::take(&mut self.blocked_by[v]) {
+			if self.blocked[u] {
+				self.unblock(u);
+			}
+		}
+	}
+
+	fn circuit(&mut self, v: usize) -> bool {
+		let mut found = false;
+		self.vertexes.push(v);
+		self.blocked[v] = true;
+		let around = self.graph.neighbours(v)
+			.filter(|edge| self.in_component[edge.to])
+			.map(|edge| (edge.to, edge.edge_index))
+			.collect::<Vec<_>>();
+		for &(to, edge_index) in &around {
+			self.edges.push(edge_index);
+			if to == self.start {
+				self.cycles.push(Cycle { vertexes: self.vertexes.clone(), edges: self.edges.clone() });
+				found = true;
+			} else if !self.blocked[to] && self.circuit(to) {
+				found = true;
+			}
+			self.edges.pop();
+		}
+		if found {
+			self.unblock(v);
+		} else {
+			for (to, _) in around {
+				if !self.blocked_by[to].contains(&v) {
+					self.blocked_by[to].push(v);
+				}
+			}
+		}
+		self.vertexes.pop();
+		found
+	}
+}
+
+/// Every simple cycle of a directed graph by Johnson's algorithm, starting at its smallest vertex
+/// (parallel edges give different cycles). The strongly connected components are found anew for every start,
+/// so it's O((n + m)(n + c)) for c cycles, meant for small graphs
+pub fn simple_cycles<G: GraphView>(graph: &G) -> Vec<Cycle> {
+	let n = graph.vertexes();
+	let mut cycles = Vec::new();
+	for start in 0..n {
+		let rest = VertexSubgraph::new(graph, |v| v >= start);
+		let (_, components) = DFSSpace::new(&rest).condensation(&rest);
+		let component = components.component_of(start);
+		let mut johnson = Johnson {
+			graph,
+			start,
+			in_component: (0..n).map(|v| v >= start && components.component_of(v) == component).collect(),
+			blocked: vec![false; n],
+			blocked_by: vec![Vec::new(); n],
+			vertexes: Vec::new(),
+			edges: Vec::new(),
+			cycles: Vec::new(),
+		};
+		johnson.circuit(start);
+		cycles.extend(johnson.cycles);
+	}
+	cycles
+}
@@ -0,0 +1,324 @@
+use crate::{CsrGraph, DFSSpace, Decomposition, Graph, GraphView};
+
+const WORD: usize = u64::BITS as usize;
+
+fn bitset(n: usize) -> Vec<u64> {
+	vec![0; n.div_ceil(WORD)]
+}
+
+fn contains(set: &[u64], i: usize) -> bool {
+	set[i / WORD] >> (i % WORD) & 1 == 1
+}
+
+fn insert(set: &mut [u64], i: usize) {
+	set[i / WORD] |= 1 << (i % WORD);
+}
+
+fn union_with(set: &mut [u64], other: &[u64]) {
+	for (word, &add) in set.iter_mut().zip(other) {
+		*word |= add;
+	}
+}
+
+/// Reachability between all pairs of vertexes of a directed graph: a bitset of the reachable
+/// strongly connected components for every component. A vertex reaches itself
+#[derive(Debug, Clone)]
+pub struct TransitiveClosure {
+	/// Strongly connected components in the topological order
+	pub components: Decomposition,
+	reachable: Vec<Vec<u64>>,
+}
+
+impl TransitiveClosure {
+	/// O(n + m c / 64) for c components
+	pub fn new<G: GraphView>(graph: &G) -> Self {
+		let (condensation, components) = DFSSpace::new(graph).condensation(graph);
+		let reachable = closure_of_sorted_dag(&condensation);
+		TransitiveClosure { components, reachable }
+	}
+
+	pub fn reaches(&self, from: usize, to: usize) -> bool {
+		contains(&self.reachable[self.components.component_of(from)], self.components.component_of(to))
+	}
+
+	/// Sorted
+	pub fn reachable_from(&self, from: usize) -> Vec<usize> {
+		(0..self.components.elements).filter(|&to| self.reaches(from, to)).collect()
+	}
+}
+
+/// Bitsets of the vertexes reachable from every vertex of a DAG whose vertexes are topologically sorted
+fn closure_of_sorted_dag(dag: &Graph) -> Vec<Vec<u64>> {
+	let n = dag.vertexes();
+	let mut reachable = vec![bitset(n); n];
+	for v in (0..n).rev() {
+		let (before, after) = reachable.split_at_mut(v + 1);
+		insert(&mut before[v], v);
+		for edge in &dag.edges[v] {
+			debug_assert!(edge.to > v);
+			union_with(&mut before[v], &after[edge.to - v - 1]);
+		}
+	}
+	reachable
+}
+
+pub fn transitive_closure<G: GraphView>(graph: &G) -> TransitiveClosure {
+	TransitiveClosure::new(graph)
+}
+
+/// Position of every vertex in a topological order, `None` if the graph has a cycle
+fn topological_positions<G: GraphView>(graph: &G) -> Option<(Vec<usize>, Vec<usize>)> {
+	let (order, acyclic) = DFSSpace::new(graph).topological_sort(graph);
+	if !acyclic {
+		return None;
+	}
+	let mut position = vec![0; order.len()];
+	for (i, &v) in order.iter().enumerate() {
+		position[v] = i;
+	}
+	Some((order, position))
+}
+
+/// Smallest graph with the same reachability as a DAG (its Hasse diagram): an edge `u → v` is kept
+/// if there is no other path from `u` to `v`. Parallel edges are merged, the edges are renumbered.
+/// `None` if the graph has a cycle. O(n m / 64)
+pub fn transitive_reduction<G: GraphView>(graph: &G) -> Option<Graph> {
+	let n = graph.vertexes();
+	let (order, position) = topological_positions(graph)?;
+	let mut sorted = Graph::new(n);
+	for v in 0..n {
+		for edge in graph.neighbours(v) {
+			sorted.add_directed_edge(position[v], position[edge.to]);
+		}
+	}
+	let reachable = closure_of_sorted_dag(&sorted);
+
+	let mut reduction = Graph::new(n);
+	for (u, edges) in sorted.edges.iter().enumerate() {
+		let mut successors = edges.iter().map(|edge| edge.to).collect::<Vec<_>>();
+		successors.sort();
+		successors.dedup();
+		// Any other path to a successor goes through an earlier one
+		let mut covered = bitset(n);
+		for v in successors {
+			if !contains(&covered, v) {
+				reduction.add_directed_edge(order[u], order[v]);
+				union_with(&mut covered, &reachable[v]);
+			}
+		}
+	}
+	Some(reduction)
+}
+
+/// Path with the most edges in a DAG (its vertexes), `None` if the graph has a cycle. O(n + m)
+pub fn longest_path<G: GraphView>(graph: &G) -> Option<Vec<usize>> {
+	longest_path_by(graph, |_| 1).map(|(_, path)| path)
+}
+
+/// Path with the largest total weight in a DAG and the weight (a single vertex is a path of weight 0),
+/// `None` if the graph has a cycle. O(n + m)
+pub fn longest_weighted_path<G>(graph: &G) -> Option<(i64, Vec<usize>)>
+	where G: GraphView, G::Weight: Into<i64>
+{
+	longest_path_by(graph, |weight| weight.into())
+}
+
+fn longest_path_by<G, F>(graph: &G, weight: F) -> Option<(i64, Vec<usize>)>
+	where G: GraphView, F: Fn(G::Weight) -> i64
+{
+	let (order, _) = topological_positions(graph)?;
+	let n = graph.vertexes();
+	// Heaviest path starting at the vertex and the next vertex on it
+	let mut best = vec![0_i64; n];
+	let mut next = vec![None; n];
+	for &v in order.iter().rev() {
+		for edge in graph.neighbours(v) {
+			let candidate = weight(edge.weight) + best[edge.to];
+			if candidate > best[v] || next[v].is_none() && candidate == best[v] {
+				best[v] = candidate;
+				next[v] = Some(edge.to);
+			}
+		}
+	}
+	let Some(start) = (0..n).max_by_key(|&v| (best[v], std::cmp::Reverse(v))) else {
+		return Some((0, Vec::new()));
+	};
+	let mut path = vec![start];
+	while let Some(v) = next[*path.last().unwrap()] {
+		path.push(v);
+	}
+	Some((best[start], path))
+}
+
+/// Number of paths from `source` to every vertex of a DAG modulo `modulus` (parallel edges
+/// make different paths, `source` has the empty one). `None` if the graph has a cycle. O(n + m)
+pub fn count_paths<G: GraphView>(graph: &G, source: usize, modulus: u64) -> Option<Vec<u64>> {
+	let (order, position) = topological_positions(graph)?;
+	let mut count = vec![0; graph.vertexes()];
+	count[source] = 1 % modulus;
+	for &v in &order[position[source]..] {
+		if count[v] == 0 {
+			continue;
+		}
+		for edge in graph.neighbours(v) {
+			count[edge.to] = (count[edge.to] + count[v]) % modulus;
+		}
+	}
+	Some(count)
+}
+
+/// Number of paths between every pair of vertexes of a DAG modulo `modulus`, `counts[u][v]`
+/// for paths from `u` to `v`. `None` if the graph has a cycle. O(n (n + m))
+pub fn count_paths_between_all<G: GraphView>(graph: &G, modulus: u64) -> Option<Vec<Vec<u64>>> {
+	(0..graph.vertexes()).map(|source| count_paths(graph, source, modulus)).collect()
+}
+
+/// Dominator tree of a directed graph from `root`: `d` dominates `v` if every path from the root
+/// to `v` goes through `d`. Built by the Lengauer–Tarjan algorithm in O(m log n)
+#[derive(Debug, Clone)]
+pub struct DominatorTree {
+	pub root: usize,
+	/// Immediate dominator, `None` for the root and the vertexes unreachable from it
+	pub idom: Vec<Option<usize>>,
+	/// Entry and exit times in the dominator tree for the ancestor queries
+	t_in: Vec<usize>,
+	t_out: Vec<usize>,
+}
+
+/// State of Lengauer–Tarjan, vertexes are numbered in the DFS preorder
+struct LengauerTarjan {
+	/// Vertex with the number
+	vertex: Vec<usize>,
+	/// DFS number of the vertex, `usize::MAX` if it isn't reachable
+	number: Vec<usize>,
+	/// The rest are indexed by DFS numbers
+	parent: Vec<usize>,
+	semi: Vec<usize>,
+	/// Forest of the processed vertexes with path compression
+	ancestor: Vec<Option<usize>>,
+	/// Vertex with the smallest semidominator on the compressed path
+	label: Vec<usize>,
+}
+
+impl LengauerTarjan {
+	fn dfs(&mut self, graph: &CsrGraph<()>, v: usize, parent: usize) {
+		self.number[v] = self.vertex.len();
+		self.vertex.push(v);
+		self.parent.push(parent);
+		for edge in graph.edges_of(v) {
+			if self.number[edge.to] == usize::MAX {
+				self.dfs(graph, edge.to, self.number[v]);
+			}
+		}
+	}
+
+	fn compress(&mut self, v: usize) {
+		let ancestor = self.ancestor[v].unwrap();
+		if self.ancestor[ancestor].is_some() {
+			self.compress(ancestor);
+			if self.semi[self.label[ancestor]] < self.semi[self.label[v]] {
+				self.label[v] = self.label[ancestor];
+			}
+			self.ancestor[v] = self.ancestor[ancestor];
+		}
+	}
+
+	fn eval(&mut self, v: usize) -> usize {
+		if self.ancestor[v].is_none() {
+			return v;
+		}
+		self.compress(v);
+		self.label[v]
+	}
+}
+
+impl DominatorTree {
+	pub fn new<G: GraphView>(graph: &G, root: usize) -> Self {
+		let n = graph.vertexes();
+		let forward = CsrGraph::from_arcs(n, &(0..n)
+			.flat_map(|v| graph.neighbours(v).map(move |edge| (v, edge.to, ())))
+			.collect::<Vec<_>>(), true);
+		let backward = CsrGraph::reversed(&forward);
+
+		let mut search = LengauerTarjan {
+			vertex: Vec::new(),
+			number: vec![usize::MAX; n],
+			parent: Vec::new(),
+			semi: Vec::new(),
+			ancestor: Vec::new(),
+			label: Vec::new(),
+		};
+		search.dfs(&forward, root, 0);
+		let reached = search.vertex.len();
+		search.semi = (0..reached).collect();
+		search.ancestor = vec![None; reached];
+		search.label = (0..reached).collect();
+
+		let mut idom = vec![0; reached];
+		let mut bucket = vec![Vec::new(); reached];
+		for w in (1..reached).rev() {
+			for edge in backward.edges_of(search.vertex[w]) {
+				let v = search.number[edge.to];
+				if v == usize::MAX {
+					continue;
+				}
+				let u = search.eval(v);
+				search.semi[w] = search.semi[w].min(search.semi[u]);
+			}
+			bucket[search.semi[w]].push(w);
+			let parent = search.parent[w];
+			search.ancestor[w] = Some(parent);
+			for v in std::mem::take(&mut bucket[parent]) {
+				let u = search.eval(v);
+				idom[v] = if search.semi[u] < search.semi[v] { u } else { parent };
+			}
+		}
+		for w in 1..reached {
+			if idom[w] != search.semi[w] {
+				idom[w] = idom[idom[w]];
+			}
+		}
+
+		let mut tree = DominatorTree { root, idom: vec![None; n], t_in: vec![usize::MAX; n], t_out: vec![usize::MAX; n] };
+		let mut children = vec![Vec::new(); n];
+		for (&v, &d) in search.vertex.iter().zip(&idom).skip(1) {
+			let d = search.vertex[d];
+			tree.idom[v] = Some(d);
+			children[d].push(v);
+		}
+		let mut time = 0;
+		tree.number(&children, root, &mut time);
+		tree
+	}
+
+	fn number(&mut self, children: &[Vec<usize>], v: usize, time: &mut usize) {
+		self.t_in[v] = *time;
+		*time += 1;
+		for &child in &children[v] {
+			self.number(children, child, time);
+		}
+		self.t_out[v] = *time;
+	}
+
+	pub fn is_reachable(&self, v: usize) -> bool {
+		self.t_in[v] != usize::MAX
+	}
+
+	/// Every path from the root to `v` goes through `d` (a vertex dominates itself),
+	/// false if `v` isn't reachable
+	pub fn dominates(&self, d: usize, v: usize) -> bool {
+		self.is_reachable(d) && self.is_reachable(v) && self.t_in[d] <= self.t_in[v] && self.t_out[v] <= self.t_out[d]
+	}
+
+	/// Dominators of `v` from `v` up to the root, empty if `v` isn't reachable
+	pub fn dominators(&self, v: usize) -> Vec<usize> {
+		if !self.is_reachable(v) {
+			return Vec::new();
+		}
+		let mut dominators = vec![v];
+		while let Some(d) = self.idom[*dominators.last().unwrap()] {
+			dominators.push(d);
+		}
+		dominators
+	}
+}
@@ -1,5 +1,5 @@
 use std::cmp::min;
-use crate::{Decomposition, Edge, Graph, VisitColor};
+use crate::{CsrGraph, Cycle, Decomposition, Edge, EdgeSubgraph, Graph, GraphView, VisitColor};
 
 #[derive(Debug, Clone)]
 pub struct DFSSpace {
@@ -15,7 +15,7 @@ fn fill<T: Clone>(v: &mut Vec<T>, value: &T) {
 }
 
 impl DFSSpace {
-	pub fn new(graph: &Graph) -> Self {
+	pub fn new<G: GraphView>(graph: &G) -> Self {
 		let n = graph.vertexes();
 		DFSSpace {
 			time: 0,
@@ -40,13 +40,13 @@ impl DFSSpace {
 		}
 	}
 
-	pub fn dfs_preorder_with<F>(&mut self, graph: &Graph, v: usize, run_for_vertex: &mut F)
-		where F: FnMut(usize)
+	pub fn dfs_preorder_with<G, F>(&mut self, graph: &G, v: usize, run_for_vertex: &mut F)
+		where G: GraphView, F: FnMut(usize)
 	{
 		self.visit_colors[v] = VisitColor::Gray;
 		run_for_vertex(v);
 
-		for edge in &graph.edges[v] {
+		for edge in graph.neighbours(v) {
 			let to = edge.to;
 			if self.visit_colors[to] == VisitColor::White {
 				self.dfs_preorder_with(graph, to, run_for_vertex);
@@ -56,7 +56,7 @@ impl DFSSpace {
 	}
 
 
-	pub fn find_connected_components(&mut self, graph: &Graph) -> Decomposition {
+	pub fn find_connected_components<G: GraphView>(&mut self, graph: &G) -> Decomposition {
 		let mut components = Vec::new();
 		for vertex in 0..graph.vertexes() {
 			if self.visit_colors[vertex] == VisitColor::White {
@@ -70,7 +70,7 @@ impl DFSSpace {
 	}
 
 
-	pub fn topological_sort(&mut self, graph: &Graph) -> (Vec<usize>, bool) {
+	pub fn topological_sort<G: GraphView>(&mut self, graph: &G) -> (Vec<usize>, bool) {
 		let mut order = Vec::new();
 		let mut has_cycle = false;
 		for v in 0..graph.vertexes() {
@@ -82,12 +82,12 @@ impl DFSSpace {
 		(order, !has_cycle)
 	}
 
-	fn topsort_dfs(&mut self, graph: &Graph, v: usize, order: &mut Vec<usize>) -> bool {
+	fn topsort_dfs<G: GraphView>(&mut self, graph: &G, v: usize, order: &mut Vec<usize>) -> bool {
 		let mut has_cycle = false;
 		self.visit_colors[v] = VisitColor::Gray;
 		self.t_in[v] = self.time;
 		self.time += 1;
-		for edge in &graph.edges[v] {
+		for edge in graph.neighbours(v) {
 			let to = edge.to;
 			if self.visit_colors[to] == VisitColor::White {
 				has_cycle |= self.topsort_dfs(graph, to, order);
@@ -103,11 +103,56 @@ impl DFSSpace {
 		has_cycle
 	}
 
-	pub fn test_acyclic(&mut self, graph: &Graph) -> bool {
+	pub fn test_acyclic<G: GraphView>(&mut self, graph: &G) -> bool {
 		self.topological_sort(graph).1
 	}
 
-	pub fn find_bridges(&mut self, graph: &Graph) -> Vec<usize> { // List of edge indexes of bridges
+	/// Some cycle closed by a back edge to a Gray vertex, `None` if there is none. In an undirected graph
+	/// the edge the vertex was entered by doesn't count, but a parallel one does
+	pub fn find_cycle<G: GraphView>(&mut self, graph: &G, directed: bool) -> Option<Cycle> {
+		let mut path = Vec::new();
+		for v in 0..graph.vertexes() {
+			if self.visit_colors[v] == VisitColor::White {
+				if let Some(cycle) = self.cycle_dfs(graph, v, None, directed, &mut path) {
+					return Some(cycle);
+				}
+			}
+		}
+		None
+	}
+
+	/// `path` holds the Gray vertexes above `v` with the edges leaving them down the tree
+	fn cycle_dfs<G: GraphView>(&mut self, graph: &G, v: usize, entered_by: Option<usize>, directed: bool,
+	                           path: &mut Vec<(usize, usize)>) -> Option<Cycle> {
+		self.visit_colors[v] = VisitColor::Gray;
+		for edge in graph.neighbours(v) {
+			if !directed && entered_by == Some(edge.edge_index) {
+				continue;
+			}
+			match self.visit_colors[edge.to] {
+				VisitColor::White => {
+					path.push((v, edge.edge_index));
+					if let Some(cycle) = self.cycle_dfs(graph, edge.to, Some(edge.edge_index), directed, path) {
+						return Some(cycle);
+					}
+					path.pop();
+				}
+				VisitColor::Gray => {
+					let start = path.iter().position(|&(u, _)| u == edge.to).unwrap_or(path.len());
+					let (mut vertexes, mut edges): (Vec<_>, Vec<_>) = path[start..].iter().copied().unzip();
+					vertexes.push(v);
+					edges.push(edge.edge_index);
+					return Some(Cycle { vertexes, edges });
+				}
+				VisitColor::Black => {}
+			}
+		}
+		self.visit_colors[v] = VisitColor::Black;
+		None
+	}
+
+	/// Edge indexes of the bridges. An edge with a parallel one is never a bridge
+	pub fn find_bridges<G: GraphView>(&mut self, graph: &G) -> Vec<usize> { // List of edge indexes of bridges
 		let mut bridges = Vec::new();
 		let mut highest_reachable = vec![0; graph.vertexes()];
 		for v in 0..graph.vertexes() {
@@ -118,22 +163,23 @@ impl DFSSpace {
 		bridges
 	}
 
-	fn bridge_dfs(&mut self, node: usize, graph: &Graph, bridges: &mut Vec<usize>, highest_reachable: &mut Vec<usize>, edge_to_parent: Option<Edge>) {
+	fn bridge_dfs<G: GraphView>(&mut self, node: usize, graph: &G, bridges: &mut Vec<usize>, highest_reachable: &mut Vec<usize>, edge_to_parent: Option<Edge>) {
 		self.visit_colors[node] = VisitColor::Gray;
 		self.t_in[node] = self.time;
 		highest_reachable[node] = self.time;
 		self.time += 1;
 
-		for edge in &graph.edges[node] {
+		for edge in graph.neighbours(node) {
 			let to = edge.to;
 			if self.visit_colors[to] == VisitColor::White {
 				self.bridge_dfs(to, graph, bridges, highest_reachable, Some(Edge::new(node, edge.edge_index)));
 				highest_reachable[node] = min(highest_reachable[node], highest_reachable[to]);
 			} else if self.visit_colors[to] == VisitColor::Gray {
 				// upper edge from node itself (handle parent separately)
+				// Only THE edge to the parent is skipped: a parallel one makes the edge not a bridge
 				match edge_to_parent {
 					Some(parent_edge) => {
-						if parent_edge.to != to {
+						if parent_edge.edge_index != edge.edge_index {
 							highest_reachable[node] = min(highest_reachable[node], self.t_in[to]);
 						}
 					},
@@ -155,13 +201,20 @@ impl DFSSpace {
 	}
 
 	/// Returns both the list of indexes of vertexes that are cutting points
-	/// and the partition of the graph into VERTEX-biconnected components
-	pub fn find_cutting_points_with_components(&mut self, graph: &Graph) -> (Vec<usize>, Decomposition) {
+	/// and the partition of the EDGES into biconnected components (blocks), a loop is a component of its own.
+	/// Edge indexes have to be compact for the partition. `BlockCutTree` gives the vertex sets of the blocks
+	pub fn find_cutting_points_with_components<G: GraphView>(&mut self, graph: &G) -> (Vec<usize>, Decomposition) {
+		let (cutting_points, blocks) = self.find_cutting_points_and_blocks(graph);
+		(cutting_points, Decomposition::from_component_list(blocks))
+	}
+
+	/// Same with the blocks as lists of edge indexes, which may have gaps (as after `remove_edges`)
+	pub(crate) fn find_cutting_points_and_blocks<G: GraphView>(&mut self, graph: &G) -> (Vec<usize>, Vec<Vec<usize>>) {
 		let mut cutting_points = Vec::new();
 		let mut highest_reachable = vec![0; graph.vertexes()];
 		let mut components = Vec::new();
 		let mut edge_stack = Vec::new();
-		let mut edge_visited = vec![false; graph.edges()];
+		let mut edge_visited = vec![false; graph.edge_index_bound()];
 		// We could add to stack only edges to White and Grey vertexes (except THE vertex to parent)
 		// but here we can have parallel edges, so we need to check if the edge is visited
 		for v in 0..graph.vertexes() {
@@ -172,10 +225,10 @@ impl DFSSpace {
 		cutting_points.sort();
 		cutting_points.dedup();
 
-		(cutting_points, Decomposition::from_component_list(components))
+		(cutting_points, components)
 	}
 
-	fn cutting_point_dfs(&mut self, graph: &Graph,
+	fn cutting_point_dfs<G: GraphView>(&mut self, graph: &G,
 	                     node: usize, edge_to_parent: Option<Edge>,
 	                     highest_reachable: &mut Vec<usize>,
 	                     cutting_points: &mut Vec<usize>,
@@ -189,16 +242,24 @@ impl DFSSpace {
 		highest_reachable[node] = self.time;
 		self.time += 1;
 
-		for &edge in &graph.edges[node] {
+		for edge in graph.neighbours(node) {
 			let to = edge.to;
 			// Not just continue if to is a parent (cause we can have parallel edges…)
 			// But if this is THE edge from which we came from parent
 			if edge_to_parent.is_some() && edge_to_parent.unwrap().edge_index == edge.edge_index {
 				continue;
 			}
+			// A loop is a component of its own and doesn't affect anything else
+			if to == node {
+				if !edge_visited[edge.edge_index] {
+					edge_visited[edge.edge_index] = true;
+					components.push(vec![edge.edge_index]);
+				}
+				continue;
+			}
 			if !edge_visited[edge.edge_index] {
 				edge_visited[edge.edge_index] = true;
-				edge_stack.push(edge);
+				edge_stack.push(Edge::new(to, edge.edge_index));
 			}
 			if self.visit_colors[to] == VisitColor::White {
 				self.cutting_point_dfs(graph, to, Some(Edge::new(node, edge.edge_index)),
@@ -240,10 +301,10 @@ impl DFSSpace {
 	// — Decomposition of VERTEXES of the original graph into strong connectivity components
 	//   such that i'th component of the decomposition is the set of vertexes
 	//   that belong to the i'th component of the new graph
-	pub fn condensation(&mut self, graph: &Graph) -> (Graph, Decomposition) {
+	pub fn condensation<G: GraphView>(&mut self, graph: &G) -> (Graph, Decomposition) {
 		let quasi_topsort = self.topological_sort(graph).0;
 		self.clear();
-		let reversed_graph = graph.reversed();
+		let reversed_graph = CsrGraph::reversed(graph);
 		let mut components = Vec::new();
 		for &root in &quasi_topsort {
 			if self.visit_colors[root] == VisitColor::White {
@@ -254,28 +315,91 @@ impl DFSSpace {
 			}
 		}
 
-		let mut decomposition = Decomposition::from_component_list(components);
+		let decomposition = Decomposition::from_component_list(components);
+		(condensation_graph(graph, &decomposition), decomposition)
+	}
 
-		let mut condensation_graph = Graph::new(decomposition.component_list.len());
+	/// Same as `condensation`, but in a single pass without the reversed graph (Tarjan's algorithm)
+	pub fn tarjan_condensation<G: GraphView>(&mut self, graph: &G) -> (Graph, Decomposition) {
+		let mut lowlink = vec![0; graph.vertexes()];
+		let mut on_stack = vec![false; graph.vertexes()];
+		let mut stack = Vec::new();
+		let mut components = Vec::new();
 		for v in 0..graph.vertexes() {
-			for &edge in &graph.edges[v] {
-				let from = decomposition.component_of(v);
-				let to = decomposition.component_of(edge.to);
-				if from != to {
-					condensation_graph.add_directed_edge(from, to);
+			if self.visit_colors[v] == VisitColor::White {
+				self.tarjan_dfs(graph, v, &mut lowlink, &mut on_stack, &mut stack, &mut components);
+			}
+		}
+		// Components are completed in the reverse topological order
+		components.reverse();
+
+		let decomposition = Decomposition::from_component_list(components);
+		(condensation_graph(graph, &decomposition), decomposition)
+	}
+
+	fn tarjan_dfs<G: GraphView>(&mut self, graph: &G, node: usize,
+	              lowlink: &mut Vec<usize>,
+	              on_stack: &mut Vec<bool>,
+	              stack: &mut Vec<usize>,
+	              components: &mut Vec<Vec<usize>>
+	)
+	{
+		self.visit_colors[node] = VisitColor::Gray;
+		self.t_in[node] = self.time;
+		lowlink[node] = self.time;
+		self.time += 1;
+		stack.push(node);
+		on_stack[node] = true;
+
+		for edge in graph.neighbours(node) {
+			let to = edge.to;
+			if self.visit_colors[to] == VisitColor::White {
+				self.tarjan_dfs(graph, to, lowlink, on_stack, stack, components);
+				lowlink[node] = min(lowlink[node], lowlink[to]);
+			} else if on_stack[to] {
+				// `to` is in the component being built (not in an already completed one)
+				lowlink[node] = min(lowlink[node], self.t_in[to]);
+			}
+		}
+
+		// `node` is the first visited vertex of its component
+		if lowlink[node] == self.t_in[node] {
+			let mut component = Vec::new();
+			loop {
+				let v = stack.pop().unwrap();
+				on_stack[v] = false;
+				component.push(v);
+				if v == node {
+					break;
 				}
 			}
+			components.push(component);
 		}
 
-		(condensation_graph.deduplicated(false), decomposition)
+		self.visit_colors[node] = VisitColor::Black;
 	}
 }
 
-pub fn find_edge_biconnected_components(mut graph: Graph) -> Decomposition {
-	let mut dfs_space = DFSSpace::new(&graph);
-	let bridges = dfs_space.find_bridges(&mut graph);
-
-	graph.remove_edges(&bridges);
+/// Graph of the components with the edges between them (without duplicates)
+fn condensation_graph<G: GraphView>(graph: &G, components: &Decomposition) -> Graph {
+	let mut condensation_graph = Graph::new(components.component_list.len());
+	for v in 0..graph.vertexes() {
+		for edge in graph.neighbours(v) {
+			let from = components.component_of(v);
+			let to = components.component_of(edge.to);
+			if from != to {
+				condensation_graph.add_directed_edge(from, to);
+			}
+		}
+	}
+	condensation_graph.deduplicated(false)
+}
 
-	DFSSpace::new(&graph).find_connected_components(&graph)
+pub fn find_edge_biconnected_components<G: GraphView>(graph: &G) -> Decomposition {
+	let mut is_bridge = vec![false; graph.edge_index_bound()];
+	for bridge in DFSSpace::new(graph).find_bridges(graph) {
+		is_bridge[bridge] = true;
+	}
+	let without_bridges = EdgeSubgraph::new(graph, |edge| !is_bridge[edge]);
+	DFSSpace::new(&without_bridges).find_connected_components(&without_bridges)
 }
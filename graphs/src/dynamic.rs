@@ -0,0 +1,344 @@
+use std::fmt::Debug;
+use crate::WeightedGraph;
+
+/// Handle of a vertex: stays valid until the vertex is removed or the graph is compacted.
+/// A slot reused for a new vertex gets a new generation, so stale handles are detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VertexHandle {
+	pub index: usize,
+	pub generation: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeHandle {
+	pub index: usize,
+	pub generation: u32,
+}
+
+#[derive(Debug, Clone)]
+struct VertexSlot {
+	generation: u32,
+	alive: bool,
+	/// Half-edges `2 e + side` starting here (for undirected graphs: all incident ones)
+	outgoing: Vec<usize>,
+	/// Half-edges `2 e + 1` ending here (directed graphs only)
+	incoming: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct EdgeSlot<T> {
+	generation: u32,
+	/// `(from, to, weight)`
+	edge: Option<(usize, usize, T)>,
+	/// Position of both half-edges in the lists of their vertexes
+	positions: [usize; 2],
+}
+
+/// Old index → new index (`None` for removed ones) after a compaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexMapping {
+	pub vertexes: Vec<Option<usize>>,
+	pub edges: Vec<Option<usize>>,
+}
+
+impl IndexMapping {
+	/// Handle after the compaction (a stale handle stays stale)
+	pub fn vertex(&self, handle: VertexHandle) -> Option<VertexHandle> {
+		let index = self.vertexes.get(handle.index).cloned().flatten()?;
+		Some(VertexHandle { index, generation: handle.generation })
+	}
+
+	pub fn edge(&self, handle: EdgeHandle) -> Option<EdgeHandle> {
+		let index = self.edges.get(handle.index).cloned().flatten()?;
+		Some(EdgeHandle { index, generation: handle.generation })
+	}
+}
+
+/// Graph with O(1) insertion and deletion of edges (amortized), O(degree) deletion of vertexes.
+/// Freed slots are reused, so indexes have gaps until `compact` is called
+#[derive(Debug, Clone)]
+pub struct DynamicGraph<T> {
+	pub directed: bool,
+	vertex_slots: Vec<VertexSlot>,
+	edge_slots: Vec<EdgeSlot<T>>,
+	free_vertexes: Vec<usize>,
+	free_edges: Vec<usize>,
+	vertex_count: usize,
+	edge_count: usize,
+}
+
+impl<T: Clone + Copy + Debug> DynamicGraph<T> {
+	pub fn new(directed: bool) -> Self {
+		DynamicGraph {
+			directed,
+			vertex_slots: Vec::new(),
+			edge_slots: Vec::new(),
+			free_vertexes: Vec::new(),
+			free_edges: Vec::new(),
+			vertex_count: 0,
+			edge_count: 0,
+		}
+	}
+
+	/// Vertex and edge handles get the indexes of the graph (with generation 0)
+	pub fn from_graph(graph: &WeightedGraph<T>, directed: bool) -> Self {
+		let mut dynamic = Self::new(directed);
+		for _ in 0..graph.vertexes() {
+			dynamic.add_vertex();
+		}
+		let mut ends = vec![None; graph.edges()];
+		for (from, edges) in graph.edges.iter().enumerate() {
+			for edge in edges {
+				ends[edge.edge_index].get_or_insert((from, edge.to, edge.weight));
+			}
+		}
+		for (from, to, weight) in ends.into_iter().map(|e| e.expect("Edge indexes should be compact")) {
+			dynamic.add_edge(dynamic.vertex_handle(from), dynamic.vertex_handle(to), weight);
+		}
+		dynamic
+	}
+
+	pub fn vertexes(&self) -> usize {
+		self.vertex_count
+	}
+
+	pub fn edges(&self) -> usize {
+		self.edge_count
+	}
+
+	/// Upper bound of vertex indexes in use
+	pub fn vertex_capacity(&self) -> usize {
+		self.vertex_slots.len()
+	}
+
+	pub fn edge_capacity(&self) -> usize {
+		self.edge_slots.len()
+	}
+
+	fn vertex_handle(&self, index: usize) -> VertexHandle {
+		VertexHandle { index, generation: self.vertex_slots[index].generation }
+	}
+
+	fn edge_handle(&self, index: usize) -> EdgeHandle {
+		EdgeHandle { index, generation: self.edge_slots[index].generation }
+	}
+
+	pub fn contains_vertex(&self, vertex: VertexHandle) -> bool {
+		self.vertex_slots.get(vertex.index).is_some_and(|slot| slot.alive && slot.generation == vertex.generation)
+	}
+
+	pub fn contains_edge(&self, edge: EdgeHandle) -> bool {
+		self.edge_slots.get(edge.index).is_some_and(|slot| slot.edge.is_some() && slot.generation == edge.generation)
+	}
+
+	pub fn add_vertex(&mut self) -> VertexHandle {
+		self.vertex_count += 1;
+		match self.free_vertexes.pop() {
+			Some(index) => {
+				self.vertex_slots[index].alive = true;
+				self.vertex_handle(index)
+			}
+			None => {
+				self.vertex_slots.push(VertexSlot { generation: 0, alive: true, outgoing: Vec::new(), incoming: Vec::new() });
+				self.vertex_handle(self.vertex_slots.len() - 1)
+			}
+		}
+	}
+
+	/// Removes the vertex with all its edges. Returns false for a stale handle
+	pub fn remove_vertex(&mut self, vertex: VertexHandle) -> bool {
+		if !self.contains_vertex(vertex) {
+			return false;
+		}
+		let slot = &self.vertex_slots[vertex.index];
+		let mut incident = slot.outgoing.iter().chain(&slot.incoming).map(|half| half / 2).collect::<Vec<_>>();
+		// A loop of an undirected graph is listed twice
+		incident.sort();
+		incident.dedup();
+		for edge in incident {
+			self.remove_edge(self.edge_handle(edge));
+		}
+		let slot = &mut self.vertex_slots[vertex.index];
+		slot.alive = false;
+		slot.generation += 1;
+		self.free_vertexes.push(vertex.index);
+		self.vertex_count -= 1;
+		true
+	}
+
+	/// List where half-edge `2 e + side` is stored
+	fn half_edge_list(&mut self, vertex: usize, side: usize) -> &mut Vec<usize> {
+		if side == 1 && self.directed {
+			&mut self.vertex_slots[vertex].incoming
+		} else {
+			&mut self.vertex_slots[vertex].outgoing
+		}
+	}
+
+	pub fn add_edge(&mut self, from: VertexHandle, to: VertexHandle, weight: T) -> EdgeHandle {
+		assert!(self.contains_vertex(from) && self.contains_vertex(to), "Edge between removed vertexes");
+		let index = match self.free_edges.pop() {
+			Some(index) => index,
+			None => {
+				self.edge_slots.push(EdgeSlot { generation: 0, edge: None, positions: [0; 2] });
+				self.edge_slots.len() - 1
+			}
+		};
+		self.edge_slots[index].edge = Some((from.index, to.index, weight));
+		for (side, vertex) in [(0, from.index), (1, to.index)] {
+			let list = self.half_edge_list(vertex, side);
+			list.push(2 * index + side);
+			let position = list.len() - 1;
+			self.edge_slots[index].positions[side] = position;
+		}
+		self.edge_count += 1;
+		self.edge_handle(index)
+	}
+
+	/// Returns the weight of the removed edge, `None` for a stale handle
+	pub fn remove_edge(&mut self, edge: EdgeHandle) -> Option<T> {
+		if !self.contains_edge(edge) {
+			return None;
+		}
+		let (from, to, weight) = self.edge_slots[edge.index].edge.unwrap();
+		for (side, vertex) in [(0, from), (1, to)] {
+			let position = self.edge_slots[edge.index].positions[side];
+			let list = self.half_edge_list(vertex, side);
+			list.swap_remove(position);
+			// The last half-edge took the place of the removed one
+			if let Some(&moved) = list.get(position) {
+				self.edge_slots[moved / 2].positions[moved & 1] = position;
+			}
+		}
+		let slot = &mut self.edge_slots[edge.index];
+		slot.edge = None;
+		slot.generation += 1;
+		self.free_edges.push(edge.index);
+		self.edge_count -= 1;
+		Some(weight)
+	}
+
+	pub fn edge_ends(&self, edge: EdgeHandle) -> Option<(VertexHandle, VertexHandle)> {
+		if !self.contains_edge(edge) {
+			return None;
+		}
+		let (from, to, _) = self.edge_slots[edge.index].edge.unwrap();
+		Some((self.vertex_handle(from), self.vertex_handle(to)))
+	}
+
+	pub fn weight(&self, edge: EdgeHandle) -> Option<T> {
+		if !self.contains_edge(edge) {
+			return None;
+		}
+		self.edge_slots[edge.index].edge.map(|(_, _, weight)| weight)
+	}
+
+	pub fn set_weight(&mut self, edge: EdgeHandle, weight: T) -> bool {
+		if !self.contains_edge(edge) {
+			return false;
+		}
+		if let Some(edge) = &mut self.edge_slots[edge.index].edge {
+			edge.2 = weight;
+		}
+		true
+	}
+
+	/// `(neighbour, edge, weight)` of the edges going out of the vertex
+	/// (all incident ones for an undirected graph, a loop twice)
+	pub fn neighbours(&self, vertex: VertexHandle) -> impl Iterator<Item = (VertexHandle, EdgeHandle, T)> + '_ {
+		assert!(self.contains_vertex(vertex), "Stale vertex handle");
+		self.vertex_slots[vertex.index].outgoing.iter().map(move |&half| {
+			let (from, to, weight) = self.edge_slots[half / 2].edge.unwrap();
+			let other = if half & 1 == 0 { to } else { from };
+			(self.vertex_handle(other), self.edge_handle(half / 2), weight)
+		})
+	}
+
+	/// Number of edges going out of the vertex (a loop of an undirected graph counts twice)
+	pub fn degree(&self, vertex: VertexHandle) -> usize {
+		assert!(self.contains_vertex(vertex), "Stale vertex handle");
+		self.vertex_slots[vertex.index].outgoing.len()
+	}
+
+	pub fn in_degree(&self, vertex: VertexHandle) -> usize {
+		assert!(self.contains_vertex(vertex), "Stale vertex handle");
+		if self.directed {
+			self.vertex_slots[vertex.index].incoming.len()
+		} else {
+			self.vertex_slots[vertex.index].outgoing.len()
+		}
+	}
+
+	pub fn vertex_handles(&self) -> impl Iterator<Item = VertexHandle> + '_ {
+		(0..self.vertex_slots.len())
+			.filter(move |&i| self.vertex_slots[i].alive)
+			.map(move |i| self.vertex_handle(i))
+	}
+
+	pub fn edge_handles(&self) -> impl Iterator<Item = EdgeHandle> + '_ {
+		(0..self.edge_slots.len())
+			.filter(move |&i| self.edge_slots[i].edge.is_some())
+			.map(move |i| self.edge_handle(i))
+	}
+
+	/// Compact numbering of what's left: live slots keep their relative order
+	fn compact_mapping(&self) -> IndexMapping {
+		let renumber = |alive: Vec<bool>| {
+			let mut next = 0;
+			alive.into_iter()
+				.map(|alive| alive.then(|| {
+					next += 1;
+					next - 1
+				}))
+				.collect()
+		};
+		IndexMapping {
+			vertexes: renumber(self.vertex_slots.iter().map(|slot| slot.alive).collect()),
+			edges: renumber(self.edge_slots.iter().map(|slot| slot.edge.is_some()).collect()),
+		}
+	}
+
+	/// Snapshot as a `WeightedGraph` with compact indexes (the graph itself doesn't change)
+	pub fn to_graph(&self) -> (WeightedGraph<T>, IndexMapping) {
+		let mapping = self.compact_mapping();
+		let mut graph = WeightedGraph::new(self.vertex_count);
+		for (index, slot) in self.edge_slots.iter().enumerate() {
+			if let Some((from, to, weight)) = slot.edge {
+				let (edge_index, from, to) = (mapping.edges[index].unwrap(), mapping.vertexes[from].unwrap(), mapping.vertexes[to].unwrap());
+				graph.add_weighted_indexed_directed_edge(from, to, edge_index, weight);
+				if !self.directed {
+					graph.add_weighted_indexed_directed_edge(to, from, edge_index, weight);
+				}
+			}
+		}
+		graph.total_edges = self.edge_count;
+		(graph, mapping)
+	}
+
+	/// Renumbers vertexes and edges to `0..vertexes()` and `0..edges()`, dropping the free lists.
+	/// Old handles have to be translated with the returned mapping
+	pub fn compact(&mut self) -> IndexMapping {
+		let mapping = self.compact_mapping();
+		let half_edge = |half: &usize| 2 * mapping.edges[half / 2].unwrap() + (half & 1);
+		self.vertex_slots = self.vertex_slots.iter()
+			.filter(|slot| slot.alive)
+			.map(|slot| VertexSlot {
+				generation: slot.generation,
+				alive: true,
+				// Order of the lists is kept, so positions of half-edges don't change
+				outgoing: slot.outgoing.iter().map(half_edge).collect(),
+				incoming: slot.incoming.iter().map(half_edge).collect(),
+			})
+			.collect();
+		self.edge_slots = self.edge_slots.iter()
+			.filter_map(|slot| slot.edge.map(|(from, to, weight)| EdgeSlot {
+				generation: slot.generation,
+				edge: Some((mapping.vertexes[from].unwrap(), mapping.vertexes[to].unwrap(), weight)),
+				positions: slot.positions,
+			}))
+			.collect();
+		self.free_vertexes.clear();
+		self.free_edges.clear();
+		mapping
+	}
+}
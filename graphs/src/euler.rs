@@ -0,0 +1,159 @@
+use crate::{CsrGraph, Graph, GraphView};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EulerTour {
+	/// Visited vertexes, one more than edges (the first and the last coincide for a circuit)
+	pub vertexes: Vec<usize>,
+	/// Edge indexes in the order of traversal
+	pub edges: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EulerError {
+	/// Vertexes that violate the degree condition:
+	/// odd degree for undirected graphs, in-degree ≠ out-degree for directed ones
+	UnbalancedVertexes(Vec<usize>),
+	/// Edges don't lie in a single connected component
+	Disconnected,
+}
+
+/// Degree balance of every vertex: `out - in` for directed graphs, degree parity (0 or 1) for undirected ones.
+/// A loop counts twice in an undirected graph, as it's stored twice in the adjacency list
+fn balances<G: GraphView>(graph: &G, directed: bool) -> Vec<i64> {
+	let mut balance = vec![0_i64; graph.vertexes()];
+	for v in 0..graph.vertexes() {
+		if directed {
+			balance[v] += graph.degree(v) as i64;
+			for edge in graph.neighbours(v) {
+				balance[edge.to] -= 1;
+			}
+		} else {
+			balance[v] = graph.degree(v) as i64 % 2;
+		}
+	}
+	balance
+}
+
+/// Hierholzer's algorithm from `start`. Uses every edge (by index) at most once,
+/// so parallel edges and loops are handled naturally
+fn hierholzer<G: GraphView>(graph: &G, start: usize) -> EulerTour {
+	let index_bound = (0..graph.vertexes())
+		.flat_map(|v| graph.neighbours(v).map(|e| e.edge_index + 1))
+		.max()
+		.unwrap_or_default();
+	let mut used = vec![false; index_bound];
+	// Materialised once, so that resuming at `next_edge` doesn't rescan a filtered view
+	let adjacency = CsrGraph::from_view(graph);
+	let mut next_edge = vec![0; graph.vertexes()];
+
+	let mut vertexes = Vec::new();
+	let mut edges = Vec::new();
+	let mut stack = vec![(start, None)];
+	while let Some(&(v, entered_by)) = stack.last() {
+		let next = adjacency.edges_of(v)[next_edge[v]..].iter()
+			.find(|edge| {
+				next_edge[v] += 1;
+				!used[edge.edge_index]
+			})
+			.copied();
+		match next {
+			Some(edge) => {
+				used[edge.edge_index] = true;
+				stack.push((edge.to, Some(edge.edge_index)));
+			}
+			None => {
+				stack.pop();
+				vertexes.push(v);
+				edges.extend(entered_by);
+			}
+		}
+	}
+	vertexes.reverse();
+	edges.reverse();
+	EulerTour { vertexes, edges }
+}
+
+fn distinct_edges<G: GraphView>(graph: &G) -> usize {
+	let mut indexes = (0..graph.vertexes())
+		.flat_map(|v| graph.neighbours(v).map(|e| e.edge_index))
+		.collect::<Vec<_>>();
+	indexes.sort();
+	indexes.dedup();
+	indexes.len()
+}
+
+fn run_from<G: GraphView>(graph: &G, start: Option<usize>) -> Result<EulerTour, EulerError> {
+	let start = match start.or_else(|| (0..graph.vertexes()).find(|&v| graph.neighbours(v).next().is_some())) {
+		Some(start) => start,
+		// No edges at all: trivial tour
+		None => return Ok(EulerTour { vertexes: (0..graph.vertexes().min(1)).collect(), edges: Vec::new() }),
+	};
+	let tour = hierholzer(graph, start);
+	if tour.edges.len() != distinct_edges(graph) {
+		return Err(EulerError::Disconnected);
+	}
+	Ok(tour)
+}
+
+/// Eulerian circuit (closed walk through every edge exactly once)
+pub fn euler_circuit<G: GraphView>(graph: &G, directed: bool) -> Result<EulerTour, EulerError> {
+	let unbalanced = balances(graph, directed).iter()
+		.enumerate()
+		.filter(|(_, &b)| b != 0)
+		.map(|(v, _)| v)
+		.collect::<Vec<_>>();
+	if !unbalanced.is_empty() {
+		return Err(EulerError::UnbalancedVertexes(unbalanced));
+	}
+	run_from(graph, None)
+}
+
+/// Eulerian path (walk through every edge exactly once, possibly closed).
+/// For directed graphs it starts at the vertex with `out - in = 1` if there is one,
+/// for undirected ones — at a vertex of odd degree
+pub fn euler_path<G: GraphView>(graph: &G, directed: bool) -> Result<EulerTour, EulerError> {
+	let balance = balances(graph, directed);
+	let unbalanced = balance.iter()
+		.enumerate()
+		.filter(|(_, &b)| b != 0)
+		.map(|(v, _)| v)
+		.collect::<Vec<_>>();
+
+	let start = if directed {
+		let starts = unbalanced.iter().filter(|&&v| balance[v] == 1).count();
+		let ends = unbalanced.iter().filter(|&&v| balance[v] == -1).count();
+		if unbalanced.len() != starts + ends || starts > 1 || starts != ends {
+			return Err(EulerError::UnbalancedVertexes(unbalanced));
+		}
+		unbalanced.iter().find(|&&v| balance[v] == 1).cloned()
+	} else {
+		if unbalanced.len() > 2 {
+			return Err(EulerError::UnbalancedVertexes(unbalanced));
+		}
+		unbalanced.first().cloned()
+	};
+	run_from(graph, start)
+}
+
+/// De Bruijn sequence B(alphabet, order): a cyclic sequence of length `alphabet^order`
+/// where every word of length `order` appears exactly once.
+/// Built as an Eulerian circuit of the graph on words of length `order - 1`
+pub fn de_bruijn_sequence(alphabet: usize, order: usize) -> Vec<usize> {
+	if order == 0 || alphabet == 0 {
+		return Vec::new();
+	}
+	let vertexes = alphabet.pow(order as u32 - 1);
+	let mut graph = Graph::new(vertexes);
+	// Edge `v * alphabet + c` appends letter `c` to the word `v`
+	for v in 0..vertexes {
+		for c in 0..alphabet {
+			graph.add_directed_edge(v, (v * alphabet + c) % vertexes);
+		}
+	}
+	euler_circuit(&graph, true)
+		.expect("De Bruijn graph is balanced and strongly connected")
+		.edges
+		.into_iter()
+		.map(|edge_index| edge_index % alphabet)
+		.collect()
+}
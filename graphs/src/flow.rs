@@ -0,0 +1,422 @@
+use std::cmp::{min, Reverse};
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt::Debug;
+use crate::WeightedGraph;
+
+pub const INFINITE_CAPACITY: i64 = i64::MAX / 4;
+const INFINITE_COST: i64 = i64::MAX / 4;
+
+/// Residual network stored as a `WeightedGraph<i64>` where the weight of an arc is its cost per unit of flow.
+/// The k-th added arc has edge index `2k`, its reverse (residual) arc has edge index `2k + 1`,
+/// so the pair of an arc is always `edge_index ^ 1`
+#[derive(Debug, Clone)]
+pub struct FlowNetwork {
+	pub graph: WeightedGraph<i64>,
+	pub capacity: Vec<i64>,
+	pub flow: Vec<i64>,
+	pub cost: Vec<i64>,
+	/// `(from, to)` for every arc (including reverse ones) by its edge index
+	pub arc_ends: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlowResult {
+	pub flow: i64,
+	pub cost: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowError {
+	/// The residual network has a cycle of negative cost, so there is no cheapest flow
+	NegativeCycle,
+	/// A path of arcs with `INFINITE_CAPACITY` joins the source to the sink and no limit was given
+	Unbounded,
+	/// The cost doesn't fit into `i64`
+	CostOverflow,
+}
+
+impl FlowNetwork {
+	pub fn new(n: usize) -> Self {
+		FlowNetwork {
+			graph: WeightedGraph::new(n),
+			capacity: Vec::new(),
+			flow: Vec::new(),
+			cost: Vec::new(),
+			arc_ends: Vec::new(),
+		}
+	}
+
+	/// Builds the network from a graph with `(capacity, cost)` weights.
+	/// Every adjacency entry becomes an arc, so an undirected edge gives an arc in each direction
+	pub fn from_capacity_cost_graph(graph: &WeightedGraph<(i64, i64)>) -> Self {
+		let mut network = Self::new(graph.vertexes());
+		for (from, edges) in graph.edges.iter().enumerate() {
+			for edge in edges {
+				let (capacity, cost) = edge.weight;
+				network.add_arc(from, edge.to, capacity, cost);
+			}
+		}
+		network
+	}
+
+	/// Same as `from_capacity_cost_graph`, but with zero costs
+	pub fn from_capacity_graph(graph: &WeightedGraph<i64>) -> Self {
+		let mut network = Self::new(graph.vertexes());
+		for (from, edges) in graph.edges.iter().enumerate() {
+			for edge in edges {
+				network.add_arc(from, edge.to, edge.weight, 0);
+			}
+		}
+		network
+	}
+
+	pub fn vertexes(&self) -> usize {
+		self.graph.vertexes()
+	}
+
+	/// Returns the index of the arc (the edge index of its reverse is `index ^ 1`)
+	pub fn add_arc(&mut self, from: usize, to: usize, capacity: i64, cost: i64) -> usize {
+		let index = self.capacity.len();
+		self.graph.add_weighted_indexed_directed_edge(from, to, index, cost);
+		self.graph.add_weighted_indexed_directed_edge(to, from, index + 1, -cost);
+		self.graph.total_edges += 2;
+		self.capacity.extend([capacity, 0]);
+		self.flow.extend([0, 0]);
+		self.cost.extend([cost, -cost]);
+		self.arc_ends.extend([(from, to), (to, from)]);
+		index
+	}
+
+	pub fn residual_capacity(&self, arc: usize) -> i64 {
+		self.capacity[arc] - self.flow[arc]
+	}
+
+	fn push(&mut self, arc: usize, amount: i64) {
+		self.flow[arc] += amount;
+		self.flow[arc ^ 1] -= amount;
+	}
+
+	/// Resets the flow on all arcs to zero
+	pub fn clear_flow(&mut self) {
+		self.flow.iter_mut().for_each(|f| *f = 0);
+	}
+
+	/// Total cost of the current flow
+	pub fn total_cost(&self) -> i64 {
+		(0..self.flow.len())
+			.step_by(2)
+			.map(|arc| self.cost[arc] * self.flow[arc])
+			.sum()
+	}
+
+	/// Potentials such that all residual arcs have non-negative reduced cost.
+	/// Bellman–Ford (queue-based) from a virtual source connected to every vertex, so negative costs are allowed.
+	/// Returns `None` if the residual network has a negative cycle
+	fn initial_potentials(&self) -> Option<Vec<i64>> {
+		let n = self.vertexes();
+		let mut potential = vec![0_i64; n];
+		let mut in_queue = vec![true; n];
+		let mut relaxations = vec![0_usize; n];
+		let mut queue = (0..n).collect::<VecDeque<_>>();
+		while let Some(v) = queue.pop_front() {
+			in_queue[v] = false;
+			for edge in &self.graph.edges[v] {
+				if self.residual_capacity(edge.edge_index) > 0 && potential[v] + edge.weight < potential[edge.to] {
+					potential[edge.to] = potential[v] + edge.weight;
+					relaxations[edge.to] += 1;
+					if relaxations[edge.to] > n {
+						return None;
+					}
+					if !in_queue[edge.to] {
+						in_queue[edge.to] = true;
+						queue.push_back(edge.to);
+					}
+				}
+			}
+		}
+		Some(potential)
+	}
+
+	/// Dijkstra on reduced costs. Returns distances and the arc used to enter every vertex
+	fn shortest_paths(&self, source: usize, potential: &[i64]) -> (Vec<i64>, Vec<Option<usize>>) {
+		let n = self.vertexes();
+		let mut distance = vec![INFINITE_COST; n];
+		let mut parent_arc = vec![None; n];
+		let mut heap = BinaryHeap::new();
+		distance[source] = 0;
+		heap.push(Reverse((0, source)));
+		while let Some(Reverse((d, v))) = heap.pop() {
+			if d > distance[v] {
+				continue;
+			}
+			for edge in &self.graph.edges[v] {
+				if self.residual_capacity(edge.edge_index) <= 0 {
+					continue;
+				}
+				let reduced = edge.weight + potential[v] - potential[edge.to];
+				debug_assert!(reduced >= 0, "Potentials should make reduced costs non-negative");
+				if d + reduced < distance[edge.to] {
+					distance[edge.to] = d + reduced;
+					parent_arc[edge.to] = Some(edge.edge_index);
+					heap.push(Reverse((distance[edge.to], edge.to)));
+				}
+			}
+		}
+		(distance, parent_arc)
+	}
+
+	/// Successive shortest paths with Johnson potentials: pushes at most `flow_limit` units
+	/// of flow from `source` to `sink` with minimal cost (in addition to the current flow).
+	/// Costs may be negative as long as there is no negative cycle. Arcs with at least `INFINITE_CAPACITY`
+	/// are unbounded, a path of them with a limit that large is an error (the flow pushed before it stays)
+	pub fn min_cost_flow(&mut self, source: usize, sink: usize, flow_limit: i64) -> Result<FlowResult, FlowError> {
+		let mut potential = self.initial_potentials().ok_or(FlowError::NegativeCycle)?;
+		let mut result = FlowResult { flow: 0, cost: 0 };
+
+		while result.flow < flow_limit {
+			let (distance, parent_arc) = self.shortest_paths(source, &potential);
+			if distance[sink] == INFINITE_COST {
+				break;
+			}
+			for v in 0..self.vertexes() {
+				if distance[v] != INFINITE_COST {
+					potential[v] += distance[v];
+				}
+			}
+
+			// Bottleneck of the path
+			let mut amount = flow_limit - result.flow;
+			let mut unbounded = true;
+			let mut v = sink;
+			while let Some(arc) = parent_arc[v] {
+				amount = min(amount, self.residual_capacity(arc));
+				unbounded &= self.capacity[arc] >= INFINITE_CAPACITY;
+				v = self.arc_ends[arc].0;
+			}
+			if unbounded && flow_limit >= INFINITE_CAPACITY {
+				return Err(FlowError::Unbounded);
+			}
+
+			let mut v = sink;
+			while let Some(arc) = parent_arc[v] {
+				self.push(arc, amount);
+				result.cost = amount.checked_mul(self.cost[arc])
+					.and_then(|cost| result.cost.checked_add(cost))
+					.ok_or(FlowError::CostOverflow)?;
+				v = self.arc_ends[arc].0;
+			}
+			result.flow += amount;
+		}
+
+		Ok(result)
+	}
+
+	pub fn min_cost_max_flow(&mut self, source: usize, sink: usize) -> Result<FlowResult, FlowError> {
+		self.min_cost_flow(source, sink, INFINITE_CAPACITY)
+	}
+
+	/// BFS distances from `source` over the arcs with residual capacity, `usize::MAX` if unreachable
+	fn levels(&self, source: usize) -> Vec<usize> {
+		let mut level = vec![usize::MAX; self.vertexes()];
+		level[source] = 0;
+		let mut queue = VecDeque::from([source]);
+		while let Some(v) = queue.pop_front() {
+			for edge in &self.graph.edges[v] {
+				if self.residual_capacity(edge.edge_index) > 0 && level[edge.to] == usize::MAX {
+					level[edge.to] = level[v] + 1;
+					queue.push_back(edge.to);
+				}
+			}
+		}
+		level
+	}
+
+	/// Blocking flow step: pushes up to `limit` along the level graph, `next_edge` skips dead arcs
+	fn augment(&mut self, v: usize, sink: usize, limit: i64, level: &[usize], next_edge: &mut [usize]) -> i64 {
+		if v == sink {
+			return limit;
+		}
+		while next_edge[v] < self.graph.edges[v].len() {
+			let edge = self.graph.edges[v][next_edge[v]];
+			let residual = self.residual_capacity(edge.edge_index);
+			if residual > 0 && level[edge.to] == level[v] + 1 {
+				let pushed = self.augment(edge.to, sink, min(limit, residual), level, next_edge);
+				if pushed > 0 {
+					self.push(edge.edge_index, pushed);
+					return pushed;
+				}
+			}
+			next_edge[v] += 1;
+		}
+		0
+	}
+
+	/// Dinic's algorithm ignoring the costs: pushes as much flow as possible from `source` to `sink`
+	/// (in addition to the current flow) and returns the amount. O(n² m)
+	pub fn max_flow(&mut self, source: usize, sink: usize) -> i64 {
+		let mut total = 0;
+		loop {
+			let level = self.levels(source);
+			if level[sink] == usize::MAX {
+				return total;
+			}
+			let mut next_edge = vec![0; self.vertexes()];
+			loop {
+				let pushed = self.augment(source, sink, INFINITE_CAPACITY, &level, &mut next_edge);
+				if pushed == 0 {
+					break;
+				}
+				total += pushed;
+			}
+		}
+	}
+
+	/// Vertexes reachable from `source` over the arcs with residual capacity.
+	/// After a maximum flow it's the source side of a minimum cut
+	pub fn reachable_in_residual(&self, source: usize) -> Vec<bool> {
+		self.levels(source).into_iter().map(|level| level != usize::MAX).collect()
+	}
+
+	/// Arcs of the original network (not reverse ones) with positive flow: `(arc index, flow)`
+	pub fn arcs_with_flow(&self) -> Vec<(usize, i64)> {
+		(0..self.flow.len())
+			.step_by(2)
+			.filter(|&arc| self.flow[arc] > 0)
+			.map(|arc| (arc, self.flow[arc]))
+			.collect()
+	}
+}
+
+
+/// Dense assignment problem: `costs[i][j]` is the cost of giving job `j` to worker `i`, `None` if it's forbidden.
+/// There may be more jobs than workers (each worker gets a distinct job)
+#[derive(Debug, Clone)]
+pub struct AssignmentProblem {
+	pub costs: Vec<Vec<Option<i64>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+	pub total_cost: i64,
+	/// Job assigned to each worker
+	pub job_of: Vec<usize>,
+}
+
+impl AssignmentProblem {
+	/// Every pair is allowed
+	pub fn from_weight_matrix(matrix: &[Vec<i64>]) -> Self {
+		Self::from_partial_matrix(&matrix.iter().map(|row| row.iter().copied().map(Some).collect()).collect::<Vec<_>>())
+	}
+
+	pub fn from_partial_matrix(matrix: &[Vec<Option<i64>>]) -> Self {
+		debug_assert!(matrix.iter().all(|row| row.len() == matrix[0].len()), "Matrix should be rectangular");
+		AssignmentProblem { costs: matrix.to_vec() }
+	}
+
+	/// Bipartite `WeightedGraph` with the first `workers` vertexes being workers and the rest being jobs.
+	/// Missing edges are forbidden, of parallel edges the cheapest one is taken
+	pub fn from_bipartite_graph<T: Copy + Debug + Into<i64>>(graph: &WeightedGraph<T>, workers: usize) -> Self {
+		let jobs = graph.vertexes() - workers;
+		let mut costs = vec![vec![None; jobs]; workers];
+		for (worker, row) in costs.iter_mut().enumerate() {
+			for edge in graph.edges[worker].iter().filter(|edge| edge.to >= workers) {
+				let cost = edge.weight.into();
+				let entry = &mut row[edge.to - workers];
+				*entry = Some(entry.map_or(cost, |current| min(current, cost)));
+			}
+		}
+		AssignmentProblem { costs }
+	}
+
+	/// Hungarian algorithm (Kuhn–Munkres with potentials), O(n² m).
+	/// `None` if the allowed pairs don't let every worker get a job
+	pub fn solve(&self) -> Option<Assignment> {
+		let n = self.costs.len();
+		if n == 0 {
+			return Some(Assignment { total_cost: 0, job_of: Vec::new() });
+		}
+		let m = self.costs[0].len();
+		if n > m {
+			return None;
+		}
+
+		// 1-indexed as in the classical formulation: row/column 0 is fictive.
+		// `min_v` stays `INFINITE_COST` for the jobs no allowed pair has reached yet
+		let mut u = vec![0_i64; n + 1];
+		let mut v = vec![0_i64; m + 1];
+		let mut worker_of = vec![0_usize; m + 1];
+		let mut way = vec![0_usize; m + 1];
+
+		for i in 1..=n {
+			worker_of[0] = i;
+			let mut j0 = 0;
+			let mut min_v = vec![INFINITE_COST; m + 1];
+			let mut used = vec![false; m + 1];
+			loop {
+				used[j0] = true;
+				let i0 = worker_of[j0];
+				let mut delta = INFINITE_COST;
+				let mut j1 = 0;
+				for j in 1..=m {
+					if !used[j] {
+						if let Some(cost) = self.costs[i0 - 1][j - 1] {
+							let current = cost - u[i0] - v[j];
+							if current < min_v[j] {
+								min_v[j] = current;
+								way[j] = j0;
+							}
+						}
+						if min_v[j] < delta {
+							delta = min_v[j];
+							j1 = j;
+						}
+					}
+				}
+				if j1 == 0 {
+					// No allowed pair leads to a free job
+					return None;
+				}
+				for j in 0..=m {
+					if used[j] {
+						u[worker_of[j]] += delta;
+						v[j] -= delta;
+					} else if min_v[j] != INFINITE_COST {
+						min_v[j] -= delta;
+					}
+				}
+				j0 = j1;
+				if worker_of[j0] == 0 {
+					break;
+				}
+			}
+			// Flip the alternating path
+			loop {
+				let j1 = way[j0];
+				worker_of[j0] = worker_of[j1];
+				j0 = j1;
+				if j0 == 0 {
+					break;
+				}
+			}
+		}
+
+		let mut job_of = vec![0; n];
+		for j in 1..=m {
+			if worker_of[j] != 0 {
+				job_of[worker_of[j] - 1] = j - 1;
+			}
+		}
+		let total_cost = job_of.iter().enumerate().map(|(i, &j)| self.costs[i][j].unwrap()).sum();
+		Some(Assignment { total_cost, job_of })
+	}
+
+	/// Same as `solve`, but maximizes the total weight
+	pub fn solve_maximizing(&self) -> Option<Assignment> {
+		let negated = AssignmentProblem {
+			costs: self.costs.iter().map(|row| row.iter().map(|c| c.map(|c| -c)).collect()).collect(),
+		};
+		let mut assignment = negated.solve()?;
+		assignment.total_cost = -assignment.total_cost;
+		Some(assignment)
+	}
+}
@@ -0,0 +1,355 @@
+use std::collections::HashSet;
+use std::fmt::Debug;
+use crate::{minmax, tree_from_prufer_code, Graph, GraphView, WeightedGraph};
+
+/// Small seedable generator (SplitMix64), so that generated graphs are reproducible
+#[derive(Debug, Clone)]
+pub struct Rng {
+	state: u64,
+}
+
+impl Rng {
+	pub fn new(seed: u64) -> Self {
+		Rng { state: seed }
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.state;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+
+	/// Uniform in `0..n` (Lemire's method without bias)
+	pub fn below(&mut self, n: usize) -> usize {
+		assert!(n > 0, "Empty range");
+		let n = n as u64;
+		let threshold = n.wrapping_neg() % n;
+		loop {
+			let product = self.next_u64() as u128 * n as u128;
+			if product as u64 >= threshold {
+				return (product >> 64) as usize;
+			}
+		}
+	}
+
+	/// Uniform in `from..=to`
+	pub fn range(&mut self, from: i64, to: i64) -> i64 {
+		assert!(from <= to, "Empty range");
+		let width = to.wrapping_sub(from) as u64;
+		if width == u64::MAX {
+			return self.next_u64() as i64;
+		}
+		from.wrapping_add(self.below_u64(width + 1) as i64)
+	}
+
+	fn below_u64(&mut self, n: u64) -> u64 {
+		let threshold = n.wrapping_neg() % n;
+		loop {
+			let product = self.next_u64() as u128 * n as u128;
+			if product as u64 >= threshold {
+				return (product >> 64) as u64;
+			}
+		}
+	}
+
+	/// Uniform in `[0, 1)`
+	pub fn next_f64(&mut self) -> f64 {
+		(self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+	}
+
+	pub fn chance(&mut self, p: f64) -> bool {
+		self.next_f64() < p
+	}
+
+	/// Fisher–Yates
+	pub fn shuffle<T>(&mut self, items: &mut [T]) {
+		for i in (1..items.len()).rev() {
+			items.swap(i, self.below(i + 1));
+		}
+	}
+
+	pub fn permutation(&mut self, n: usize) -> Vec<usize> {
+		let mut permutation = (0..n).collect::<Vec<_>>();
+		self.shuffle(&mut permutation);
+		permutation
+	}
+}
+
+fn add_edge(graph: &mut Graph, from: usize, to: usize, directed: bool) {
+	if directed {
+		graph.add_directed_edge(from, to);
+	} else {
+		graph.add_undirected_edge(from, to);
+	}
+}
+
+/// Same graph with a weight for every edge (both halves of an undirected edge get the same one)
+pub fn with_weights<T, F>(graph: &Graph, mut weight: F) -> WeightedGraph<T>
+	where T: Copy + Debug, F: FnMut(usize) -> T
+{
+	let weights = (0..graph.edge_index_bound()).map(&mut weight).collect::<Vec<_>>();
+	let mut weighted = WeightedGraph::new(graph.vertexes());
+	for (from, edges) in graph.edges.iter().enumerate() {
+		for edge in edges {
+			weighted.add_weighted_indexed_directed_edge(from, edge.to, edge.edge_index, weights[edge.edge_index]);
+		}
+	}
+	weighted.total_edges = graph.edges();
+	weighted
+}
+
+/// Weights uniform in `from..=to`
+pub fn with_random_weights(graph: &Graph, from: i64, to: i64, rng: &mut Rng) -> WeightedGraph<i64> {
+	with_weights(graph, |_| rng.range(from, to))
+}
+
+
+pub fn complete_graph(n: usize) -> Graph {
+	let mut graph = Graph::new(n);
+	for i in 0..n {
+		for j in i + 1..n {
+			graph.add_undirected_edge(i, j);
+		}
+	}
+	graph
+}
+
+/// Parts are `0..left` and `left..left + right`
+pub fn complete_bipartite_graph(left: usize, right: usize) -> Graph {
+	let mut graph = Graph::new(left + right);
+	for i in 0..left {
+		for j in left..left + right {
+			graph.add_undirected_edge(i, j);
+		}
+	}
+	graph
+}
+
+pub fn path_graph(n: usize) -> Graph {
+	let mut graph = Graph::new(n);
+	for i in 1..n {
+		graph.add_undirected_edge(i - 1, i);
+	}
+	graph
+}
+
+/// For `n` = 1 and `n` = 2 it's a loop and a pair of parallel edges
+pub fn cycle_graph(n: usize) -> Graph {
+	let mut graph = path_graph(n);
+	if n > 0 {
+		graph.add_undirected_edge(n - 1, 0);
+	}
+	graph
+}
+
+/// Center `0` connected to `1..=leaves`
+pub fn star_graph(leaves: usize) -> Graph {
+	let mut graph = Graph::new(leaves + 1);
+	for i in 1..=leaves {
+		graph.add_undirected_edge(0, i);
+	}
+	graph
+}
+
+/// Hub `0` and a cycle on `1..=rim`
+pub fn wheel_graph(rim: usize) -> Graph {
+	let mut graph = star_graph(rim);
+	for i in 1..=rim {
+		graph.add_undirected_edge(i, i % rim + 1);
+	}
+	graph
+}
+
+/// Vertex `(r, c)` is `r * cols + c`
+pub fn grid_graph(rows: usize, cols: usize) -> Graph {
+	let mut graph = Graph::new(rows * cols);
+	for r in 0..rows {
+		for c in 0..cols {
+			if c + 1 < cols {
+				graph.add_undirected_edge(r * cols + c, r * cols + c + 1);
+			}
+			if r + 1 < rows {
+				graph.add_undirected_edge(r * cols + c, (r + 1) * cols + c);
+			}
+		}
+	}
+	graph
+}
+
+/// Grid with wrap-around edges. A dimension of size 1 or 2 gives loops or parallel edges
+pub fn torus_graph(rows: usize, cols: usize) -> Graph {
+	let mut graph = Graph::new(rows * cols);
+	for r in 0..rows {
+		for c in 0..cols {
+			graph.add_undirected_edge(r * cols + c, r * cols + (c + 1) % cols);
+			graph.add_undirected_edge(r * cols + c, (r + 1) % rows * cols + c);
+		}
+	}
+	graph
+}
+
+/// Vertexes are bit masks, edges connect masks differing in one bit
+pub fn hypercube_graph(dimension: usize) -> Graph {
+	let mut graph = Graph::new(1 << dimension);
+	for mask in 0..1_usize << dimension {
+		for bit in 0..dimension {
+			if mask & (1 << bit) == 0 {
+				graph.add_undirected_edge(mask, mask | (1 << bit));
+			}
+		}
+	}
+	graph
+}
+
+/// GP(n, k): outer cycle `0..n`, spokes `i — n + i`, inner star polygon `n + i — n + (i + k) mod n`.
+/// GP(5, 2) is the Petersen graph, GP(n, 1) is a prism, GP(6, 2) the Dürer graph,
+/// GP(8, 3) the Möbius–Kantor graph, GP(10, 2) the dodecahedron, GP(10, 3) the Desargues graph
+pub fn generalized_petersen_graph(n: usize, k: usize) -> Graph {
+	assert!(n >= 3 && k >= 1 && 2 * k < n, "GP(n, k) requires n >= 3 and 1 <= k < n / 2");
+	let mut graph = Graph::new(2 * n);
+	for i in 0..n {
+		graph.add_undirected_edge(i, (i + 1) % n);
+		graph.add_undirected_edge(i, n + i);
+		graph.add_undirected_edge(n + i, n + (i + k) % n);
+	}
+	graph
+}
+
+pub fn petersen_graph() -> Graph {
+	generalized_petersen_graph(5, 2)
+}
+
+/// Herschel graph: the smallest non-Hamiltonian polyhedral graph (11 vertexes, 18 edges, bipartite 5 + 6)
+pub fn herschel_graph() -> Graph {
+	let edges = [
+		(0, 2), (0, 3), (0, 4), (0, 5), (1, 2), (1, 3), (1, 6), (1, 7), (2, 8), (3, 9),
+		(4, 8), (4, 10), (5, 9), (5, 10), (6, 8), (6, 10), (7, 9), (7, 10),
+	];
+	let mut graph = Graph::new(11);
+	for (from, to) in edges {
+		graph.add_undirected_edge(from, to);
+	}
+	graph
+}
+
+
+/// Erdős–Rényi G(n, p): every pair (ordered, if `directed`) of distinct vertexes is an edge with probability `p`
+pub fn gnp_random_graph(n: usize, p: f64, directed: bool, rng: &mut Rng) -> Graph {
+	let mut graph = Graph::new(n);
+	for i in 0..n {
+		for j in 0..n {
+			if i != j && (directed || i < j) && rng.chance(p) {
+				add_edge(&mut graph, i, j, directed);
+			}
+		}
+	}
+	graph
+}
+
+/// Erdős–Rényi G(n, m): `m` distinct edges chosen uniformly (no loops or parallel edges)
+pub fn gnm_random_graph(n: usize, m: usize, directed: bool, rng: &mut Rng) -> Graph {
+	let pairs = if directed { n * n.saturating_sub(1) } else { n * n.saturating_sub(1) / 2 };
+	assert!(m <= pairs, "Too many edges for a simple graph");
+	// Floyd's sampling of `m` distinct pair indexes
+	let mut chosen = HashSet::with_capacity(m);
+	for upper in pairs - m..pairs {
+		let candidate = rng.below(upper + 1);
+		if !chosen.insert(candidate) {
+			chosen.insert(upper);
+		}
+	}
+	let mut chosen = chosen.into_iter().collect::<Vec<_>>();
+	chosen.sort();
+	rng.shuffle(&mut chosen);
+
+	let mut graph = Graph::new(n);
+	for index in chosen {
+		let (from, to) = if directed {
+			let (from, rest) = (index / (n - 1), index % (n - 1));
+			(from, if rest >= from { rest + 1 } else { rest })
+		} else {
+			unordered_pair(index)
+		};
+		add_edge(&mut graph, from, to, directed);
+	}
+	graph
+}
+
+/// Inverse of `(i, j) -> j (j - 1) / 2 + i` for `i < j`: pairs in the order of their larger vertex
+pub fn unordered_pair(index: usize) -> (usize, usize) {
+	let mut j = ((1.0 + (1.0 + 8.0 * index as f64).sqrt()) / 2.0) as usize;
+	while j * (j - 1) / 2 > index {
+		j -= 1;
+	}
+	while (j + 1) * j / 2 <= index {
+		j += 1;
+	}
+	(index - j * (j - 1) / 2, j)
+}
+
+/// Sequence of `n - 2` values in `0..n`: decodes into a uniformly random labelled tree
+pub fn random_prufer_code(n: usize, rng: &mut Rng) -> Vec<usize> {
+	(0..n.saturating_sub(2)).map(|_| rng.below(n)).collect()
+}
+
+/// Uniformly random labelled tree on `n` vertexes
+pub fn random_tree(n: usize, rng: &mut Rng) -> Graph {
+	if n < 2 {
+		return Graph::new(n);
+	}
+	tree_from_prufer_code(&random_prufer_code(n, rng)).unwrap()
+}
+
+/// Random simple `degree`-regular graph: points of the configuration model are paired one by one
+/// avoiding loops and parallel edges (Steger–Wormald), restarting when stuck
+pub fn random_regular_graph(n: usize, degree: usize, rng: &mut Rng) -> Graph {
+	assert!((n * degree) & 1 == 0, "n * degree should be even");
+	assert!(degree < n || n * degree == 0, "Degree should be less than n");
+	'attempt: loop {
+		let mut points = (0..n).flat_map(|v| vec![v; degree]).collect::<Vec<_>>();
+		let mut edges = HashSet::new();
+		while !points.is_empty() {
+			let mut tries = 0;
+			loop {
+				let i = rng.below(points.len());
+				let j = rng.below(points.len());
+				let (u, v) = (points[i], points[j]);
+				if u != v && !edges.contains(&minmax(u, v)) {
+					edges.insert(minmax(u, v));
+					let (i, j) = minmax(i, j);
+					points.swap_remove(j);
+					points.swap_remove(i);
+					break;
+				}
+				tries += 1;
+				if tries > 100 * points.len() {
+					continue 'attempt;
+				}
+			}
+		}
+		let mut edges = edges.into_iter().collect::<Vec<_>>();
+		edges.sort();
+		let mut graph = Graph::new(n);
+		for (from, to) in edges {
+			graph.add_undirected_edge(from, to);
+		}
+		return graph;
+	}
+}
+
+/// Random DAG: vertexes are put in a random order and every pair going forward in it
+/// is an edge with probability `p`. Returns the graph and the order (a topological sort)
+pub fn random_dag(n: usize, p: f64, rng: &mut Rng) -> (Graph, Vec<usize>) {
+	let order = rng.permutation(n);
+	let mut graph = Graph::new(n);
+	for (i, &from) in order.iter().enumerate() {
+		for &to in &order[i + 1..] {
+			if rng.chance(p) {
+				graph.add_directed_edge(from, to);
+			}
+		}
+	}
+	(graph, order)
+}
@@ -29,6 +29,9 @@ impl Edge {
 pub struct WeightedGraph<T> {
 	pub edges: Vec<Vec<WeightedEdge<T>>>,
 	pub total_edges: usize,
+	/// Largest edge index plus one, kept by `add_weighted_indexed_directed_edge`.
+	/// Equal to `total_edges` unless edges were removed with `remove_edges`
+	pub edge_index_bound: usize,
 }
 pub type Graph = WeightedGraph<()>;
 
@@ -37,6 +40,7 @@ impl<T: Clone + Copy + Debug> WeightedGraph<T> {
 		Self {
 			edges: vec![Vec::new(); n],
 			total_edges: 0,
+			edge_index_bound: 0,
 		}
 	}
 
@@ -60,6 +64,7 @@ impl<T: Clone + Copy + Debug> WeightedGraph<T> {
 
 	pub fn add_weighted_indexed_directed_edge(&mut self, from: usize, to: usize, edge_index: usize, weight: T) {
 		self.edges[from].push(WeightedEdge { to, edge_index, weight });
+		self.edge_index_bound = self.edge_index_bound.max(edge_index + 1);
 	}
 
 	pub fn add_weighted_undirected_edge(&mut self, from: usize, to: usize, weight: T) {
@@ -83,17 +88,31 @@ impl<T: Clone + Copy + Debug> WeightedGraph<T> {
 		self.total_edges
 	}
 
+	/// `(from, to)` of every edge by its index (requires compact enumeration).
+	/// For an undirected edge it's the first occurrence in the adjacency lists, so `from <= to`
+	pub fn edge_ends(&self) -> Vec<(usize, usize)> {
+		let mut ends = vec![None; self.total_edges];
+		for (from, edges) in self.edges.iter().enumerate() {
+			for edge in edges {
+				if ends[edge.edge_index].is_none() {
+					ends[edge.edge_index] = Some((from, edge.to));
+				}
+			}
+		}
+		ends.into_iter().map(|e| e.expect("Edge indexes should be compact")).collect()
+	}
+
+	/// Edge indexes are kept as they are (so they are no longer compact), see `without_edges`
 	pub fn remove_edges(&mut self, edges: &[usize]) {
-		let mut removed_edges = vec![false; self.total_edges];
+		let mut removed_edges = vec![false; self.edge_index_bound.max(self.total_edges)];
 		for edge in edges {
 			removed_edges[*edge] = true;
 		}
 		for edges in &mut self.edges {
-			// self.total_edges -= edges.len();
 			edges.retain(|edge| !removed_edges[edge.edge_index]);
-			// self.total_edges += edges.len();
 		}
-		self.total_edges -= edges.len();
+		// An edge may be listed several times, but it's removed once
+		self.total_edges -= removed_edges.iter().filter(|&&removed| removed).count();
 	}
 
 	/// Vertexes have the same indexes as in the original graph
@@ -109,6 +128,105 @@ impl<T: Clone + Copy + Debug> WeightedGraph<T> {
 		}
 		reversed
 	}
+
+	/// Subgraph induced by `vertexes`: vertex `vertexes[i]` becomes `i`,
+	/// edges are renumbered compactly (an undirected edge keeps a single index)
+	pub fn induced_subgraph(&self, vertexes: &[usize]) -> Self {
+		let mut new_index = vec![None; self.vertexes()];
+		for (i, &v) in vertexes.iter().enumerate() {
+			new_index[v] = Some(i);
+		}
+		let mut edge_map = HashMap::new();
+		let mut subgraph = Self::new(vertexes.len());
+		for (i, &v) in vertexes.iter().enumerate() {
+			for edge in &self.edges[v] {
+				if let Some(to) = new_index[edge.to] {
+					let next_index = edge_map.len();
+					let edge_index = *edge_map.entry(edge.edge_index).or_insert(next_index);
+					subgraph.add_weighted_indexed_directed_edge(i, to, edge_index, edge.weight);
+				}
+			}
+		}
+		subgraph.total_edges = edge_map.len();
+		subgraph
+	}
+
+	/// Subgraph formed by the given edges and their ends.
+	/// Returns it together with the original index of each of its vertexes
+	pub fn edge_subgraph(&self, edges: &[usize]) -> (Self, Vec<usize>) {
+		let mut selected = HashMap::new();
+		for (i, &e) in edges.iter().enumerate() {
+			selected.insert(e, i);
+		}
+		let mut new_index = vec![None; self.vertexes()];
+		let mut vertexes = Vec::new();
+		let mut arcs = Vec::new();
+		for (from, adjacent) in self.edges.iter().enumerate() {
+			for edge in adjacent {
+				if let Some(&edge_index) = selected.get(&edge.edge_index) {
+					for v in [from, edge.to] {
+						if new_index[v].is_none() {
+							new_index[v] = Some(vertexes.len());
+							vertexes.push(v);
+						}
+					}
+					arcs.push((new_index[from].unwrap(), new_index[edge.to].unwrap(), edge_index, edge.weight));
+				}
+			}
+		}
+		let mut subgraph = Self::new(vertexes.len());
+		for (from, to, edge_index, weight) in arcs {
+			subgraph.add_weighted_indexed_directed_edge(from, to, edge_index, weight);
+		}
+		subgraph.total_edges = edges.len();
+		(subgraph, vertexes)
+	}
+
+	/// Graph from `(from, to, edge_index, weight)` arcs with edge indexes renumbered compactly
+	/// (keeping their relative order and the sharing of an index by both halves of an undirected edge)
+	fn from_arcs_renumbered(n: usize, arcs: Vec<(usize, usize, usize, T)>) -> Self {
+		let mut indexes = arcs.iter().map(|arc| arc.2).collect::<Vec<_>>();
+		indexes.sort();
+		indexes.dedup();
+		let mut graph = Self::new(n);
+		for (from, to, edge_index, weight) in arcs {
+			graph.add_weighted_indexed_directed_edge(from, to, indexes.binary_search(&edge_index).unwrap(), weight);
+		}
+		graph.total_edges = indexes.len();
+		graph
+	}
+
+	/// Same as `remove_edges`, but keeps edge indexes compact (the relative order is preserved)
+	pub fn without_edges(&self, edges: &[usize]) -> Self {
+		let removed = edges.iter().cloned().collect::<HashSet<_>>();
+		let arcs = self.edges.iter()
+			.enumerate()
+			.flat_map(|(from, edges)| edges.iter().map(move |e| (from, e.to, e.edge_index, e.weight)))
+			.filter(|arc| !removed.contains(&arc.2))
+			.collect();
+		Self::from_arcs_renumbered(self.vertexes(), arcs)
+	}
+
+	/// Merges the ends of edge `edge_index` into one vertex keeping all the other edges
+	/// (so edges parallel to it become loops), unlike `pull_edge` which also deduplicates.
+	/// The larger end disappears, vertexes after it are shifted down; edges are renumbered compactly
+	pub fn contracted(&self, edge_index: usize) -> Self {
+		let (l, r) = sort_pair(self.edges.iter()
+			.enumerate()
+			.find_map(|(from, edges)| edges.iter()
+				.find(|edge| edge.edge_index == edge_index)
+				.map(|edge| (from, edge.to)))
+			.unwrap());
+		let new_vertex_index = |v: usize| if v < r { v } else if v == r { l } else { v - 1 };
+
+		let arcs = self.edges.iter()
+			.enumerate()
+			.flat_map(|(from, edges)| edges.iter().map(move |e| (from, e.to, e.edge_index, e.weight)))
+			.filter(|arc| arc.2 != edge_index)
+			.map(|(from, to, index, weight)| (new_vertex_index(from), new_vertex_index(to), index, weight))
+			.collect();
+		Self::from_arcs_renumbered(self.vertexes() - 1, arcs)
+	}
 }
 
 impl<T: Copy + Default + PartialEq + Debug> WeightedGraph<T> {
@@ -165,6 +283,26 @@ impl WeightedGraph<()> {
 		graph
 	}
 
+	/// Simple undirected graph joining the distinct vertexes that aren't adjacent here
+	/// (edges are taken as undirected, loops and parallel edges are ignored)
+	pub fn complement(&self) -> Self {
+		let n = self.vertexes();
+		let mut adjacent = vec![vec![false; n]; n];
+		for (v, edges) in self.edges.iter().enumerate() {
+			for edge in edges {
+				adjacent[v][edge.to] = true;
+				adjacent[edge.to][v] = true;
+			}
+		}
+		let mut complement = Self::new(n);
+		for (u, row) in adjacent.iter().enumerate() {
+			for (v, _) in row.iter().enumerate().skip(u + 1).filter(|(_, &joined)| !joined) {
+				complement.add_undirected_edge(u, v);
+			}
+		}
+		complement
+	}
+
 
 	/// Deduplicate edges
 	/// Edges are also renumerated (such that if `consider_inverse_edges_equal` is true,
@@ -0,0 +1,376 @@
+use std::collections::VecDeque;
+use crate::{bipartition, DFSSpace, GraphView};
+
+/// Largest graph handled by the bitmask DP
+pub const HAMILTONIAN_DP_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HamiltonianMethod {
+	/// At most two vertexes
+	Trivial,
+	/// Constructive rotation algorithm, guaranteed to succeed under Ore's (and so Dirac's) condition
+	DiracOre,
+	BitmaskDp,
+	Backtracking,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HamiltonianPath {
+	pub vertexes: Vec<usize>,
+	/// Edge indexes between consecutive vertexes (for a cycle, the last one closes it)
+	pub edges: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NonExistenceProof {
+	Disconnected,
+	/// Vertex that can't have two (for a cycle) or one (for a path) neighbours on the route
+	LowDegree(usize),
+	/// A cycle can't pass through a cutting point
+	CutVertex(usize),
+	/// More than two vertexes of degree 1 can't all be path ends
+	TooManyLeaves(Vec<usize>),
+	/// Sides of a bipartite graph differ too much: the route alternates between them
+	BipartiteImbalance { smaller: usize, larger: usize },
+	/// The search was exhaustive
+	Exhaustive(HamiltonianMethod),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HamiltonianResult {
+	Found { path: HamiltonianPath, method: HamiltonianMethod },
+	NotFound(NonExistenceProof),
+}
+
+impl HamiltonianResult {
+	pub fn path(&self) -> Option<&HamiltonianPath> {
+		match self {
+			HamiltonianResult::Found { path, .. } => Some(path),
+			HamiltonianResult::NotFound(_) => None,
+		}
+	}
+}
+
+/// Simple-graph view of an undirected multigraph: loops are dropped and parallel edges merged
+struct SimpleGraph {
+	n: usize,
+	adjacent: Vec<Vec<bool>>,
+	neighbours: Vec<Vec<usize>>,
+}
+
+impl SimpleGraph {
+	fn new<G: GraphView>(graph: &G) -> Self {
+		let n = graph.vertexes();
+		let mut adjacent = vec![vec![false; n]; n];
+		let ends = (0..n).flat_map(|v| graph.neighbours(v).map(move |edge| (v, edge.to)));
+		for (v, to) in ends.filter(|&(v, to)| v != to) {
+			adjacent[v][to] = true;
+			adjacent[to][v] = true;
+		}
+		let neighbours = adjacent.iter()
+			.map(|row| (0..n).filter(|&u| row[u]).collect())
+			.collect();
+		SimpleGraph { n, adjacent, neighbours }
+	}
+
+	fn degree(&self, v: usize) -> usize {
+		self.neighbours[v].len()
+	}
+}
+
+fn edge_between<G: GraphView>(graph: &G, u: usize, v: usize, except: Option<usize>) -> Option<usize> {
+	graph.neighbours(u)
+		.find(|e| e.to == v && Some(e.edge_index) != except)
+		.map(|e| e.edge_index)
+}
+
+fn attach_edges<G: GraphView>(graph: &G, vertexes: Vec<usize>, cycle: bool) -> HamiltonianPath {
+	let mut edges = vertexes.windows(2)
+		.map(|w| edge_between(graph, w[0], w[1], None).unwrap())
+		.collect::<Vec<_>>();
+	if cycle && vertexes.len() > 1 {
+		// For two vertexes the closing edge has to be a different parallel edge
+		let closing = edge_between(graph, *vertexes.last().unwrap(), vertexes[0], edges.first().cloned());
+		edges.push(closing.unwrap());
+	}
+	HamiltonianPath { vertexes, edges }
+}
+
+/// Rotation algorithm on a cyclic order of vertexes: whenever neighbours in the order aren't adjacent,
+/// reverses a segment to fix that. Terminates with a cycle if Ore's condition holds
+fn ore_rotation(graph: &SimpleGraph) -> Option<Vec<usize>> {
+	let n = graph.n;
+	let mut order = (0..n).collect::<VecDeque<_>>();
+	for _ in 0..n * (n - 1) {
+		if !graph.adjacent[order[0]][order[1]] {
+			let i = (2..n - 1).find(|&i| graph.adjacent[order[0]][order[i]] && graph.adjacent[order[1]][order[i + 1]])?;
+			let mut segment = order.range(1..i + 1).cloned().collect::<Vec<_>>();
+			segment.reverse();
+			for (j, v) in segment.into_iter().enumerate() {
+				order[1 + j] = v;
+			}
+		}
+		order.rotate_left(1);
+	}
+	let order = Vec::from(order);
+	(0..n).all(|i| graph.adjacent[order[i]][order[(i + 1) % n]]).then_some(order)
+}
+
+fn satisfies_ore(graph: &SimpleGraph, extra_degree: usize, n: usize) -> bool {
+	(0..graph.n).all(|u| (u + 1..graph.n)
+		.all(|v| graph.adjacent[u][v] || graph.degree(u) + graph.degree(v) + 2 * extra_degree >= n))
+}
+
+/// Bitmask DP over subsets: `reach[mask]` is the set of possible last vertexes
+/// of a path visiting exactly `mask` (starting at 0 for cycles, anywhere for paths)
+fn bitmask_dp(graph: &SimpleGraph, cycle: bool) -> Option<Vec<usize>> {
+	let n = graph.n;
+	let adjacent = graph.neighbours.iter()
+		.map(|ns| ns.iter().fold(0_u32, |mask, &u| mask | (1 << u)))
+		.collect::<Vec<_>>();
+	let full = (1_usize << n) - 1;
+	let mut reach = vec![0_u32; full + 1];
+	if cycle {
+		reach[1] = 1;
+	} else {
+		for v in 0..n {
+			reach[1 << v] = 1 << v;
+		}
+	}
+	for mask in 1..=full {
+		let mut ends = reach[mask];
+		while ends != 0 {
+			let v = ends.trailing_zeros() as usize;
+			ends &= ends - 1;
+			let mut next = adjacent[v] & !(mask as u32);
+			while next != 0 {
+				let u = next.trailing_zeros() as usize;
+				next &= next - 1;
+				reach[mask | (1 << u)] |= 1 << u;
+			}
+		}
+	}
+
+	let mut finals = reach[full];
+	if cycle {
+		finals &= adjacent[0];
+	}
+	if finals == 0 {
+		return None;
+	}
+	let mut last = finals.trailing_zeros() as usize;
+	let mut mask = full;
+	let mut path = vec![last];
+	while mask.count_ones() > 1 {
+		mask ^= 1 << last;
+		last = (reach[mask] & adjacent[last]).trailing_zeros() as usize;
+		path.push(last);
+	}
+	path.reverse();
+	Some(path)
+}
+
+struct Backtracking<'a> {
+	graph: &'a SimpleGraph,
+	cycle: bool,
+	visited: Vec<bool>,
+	path: Vec<usize>,
+}
+
+impl<'a> Backtracking<'a> {
+	/// Every unvisited vertex still needs enough free neighbours,
+	/// and all of them must be reachable from the end of the path through unvisited vertexes
+	fn feasible(&self) -> bool {
+		let end = *self.path.last().unwrap();
+		let start = self.path[0];
+		let mut dead_ends = 0;
+		for v in 0..self.graph.n {
+			if self.visited[v] {
+				continue;
+			}
+			let free = self.graph.neighbours[v].iter()
+				.filter(|&&u| !self.visited[u] || u == end || (self.cycle && u == start))
+				.count();
+			if free == 0 || (self.cycle && free < 2) {
+				return false;
+			}
+			if free == 1 {
+				// Only the final vertex of a path may be a dead end
+				dead_ends += 1;
+				if dead_ends > 1 {
+					return false;
+				}
+			}
+		}
+
+		let mut seen = vec![false; self.graph.n];
+		let mut queue = VecDeque::from([end]);
+		seen[end] = true;
+		let mut reached = 0;
+		while let Some(v) = queue.pop_front() {
+			for &u in &self.graph.neighbours[v] {
+				if !self.visited[u] && !seen[u] {
+					seen[u] = true;
+					reached += 1;
+					queue.push_back(u);
+				}
+			}
+		}
+		reached == self.graph.n - self.path.len()
+	}
+
+	fn search(&mut self) -> bool {
+		let end = *self.path.last().unwrap();
+		if self.path.len() == self.graph.n {
+			return !self.cycle || self.graph.adjacent[end][self.path[0]];
+		}
+		if !self.feasible() {
+			return false;
+		}
+		// Try the most constrained vertexes first
+		let mut candidates = self.graph.neighbours[end].iter()
+			.cloned()
+			.filter(|&u| !self.visited[u])
+			.collect::<Vec<_>>();
+		candidates.sort_by_key(|&u| self.graph.neighbours[u].iter().filter(|&&w| !self.visited[w]).count());
+		for u in candidates {
+			self.visited[u] = true;
+			self.path.push(u);
+			if self.search() {
+				return true;
+			}
+			self.path.pop();
+			self.visited[u] = false;
+		}
+		false
+	}
+}
+
+fn backtracking(graph: &SimpleGraph, cycle: bool) -> Option<Vec<usize>> {
+	// A cycle passes through every vertex, so it's enough to start at the one of minimal degree
+	let starts = if cycle {
+		vec![(0..graph.n).min_by_key(|&v| graph.degree(v)).unwrap()]
+	} else {
+		let mut starts = (0..graph.n).collect::<Vec<_>>();
+		starts.sort_by_key(|&v| graph.degree(v));
+		starts
+	};
+	for start in starts {
+		let mut state = Backtracking {
+			graph,
+			cycle,
+			visited: vec![false; graph.n],
+			path: vec![start],
+		};
+		state.visited[start] = true;
+		if state.search() {
+			return Some(state.path);
+		}
+	}
+	None
+}
+
+/// Cheap certificates that there is no Hamiltonian cycle (path)
+fn quick_refutation<G: GraphView>(graph: &G, simple: &SimpleGraph, cycle: bool) -> Option<NonExistenceProof> {
+	let n = simple.n;
+	let required_degree = if cycle { 2 } else { 1 };
+	if let Some(v) = (0..n).find(|&v| simple.degree(v) < required_degree) {
+		return Some(NonExistenceProof::LowDegree(v));
+	}
+	let components = DFSSpace::new(graph).find_connected_components(graph);
+	if components.component_list.len() > 1 {
+		return Some(NonExistenceProof::Disconnected);
+	}
+	if cycle {
+		let (cutting_points, _) = DFSSpace::new(graph).find_cutting_points_and_blocks(graph);
+		if let Some(&v) = cutting_points.first() {
+			return Some(NonExistenceProof::CutVertex(v));
+		}
+	} else {
+		let leaves = (0..n).filter(|&v| simple.degree(v) == 1).collect::<Vec<_>>();
+		if leaves.len() > 2 {
+			return Some(NonExistenceProof::TooManyLeaves(leaves));
+		}
+	}
+	if let Some(sides) = bipartition(graph) {
+		let mut sizes = sides.component_list.iter().map(|side| side.len()).collect::<Vec<_>>();
+		sizes.sort();
+		let allowed_difference = if cycle { 0 } else { 1 };
+		if sizes[1] - sizes[0] > allowed_difference {
+			return Some(NonExistenceProof::BipartiteImbalance { smaller: sizes[0], larger: sizes[1] });
+		}
+	}
+	None
+}
+
+fn hamiltonian<G: GraphView>(graph: &G, cycle: bool) -> HamiltonianResult {
+	let simple = SimpleGraph::new(graph);
+	let n = simple.n;
+	let found = |vertexes: Vec<usize>, method| HamiltonianResult::Found { path: attach_edges(graph, vertexes, cycle), method };
+
+	if n <= 1 {
+		return found((0..n).collect(), HamiltonianMethod::Trivial);
+	}
+	if cycle && n == 2 {
+		let edges = graph.neighbours(0).filter(|e| e.to == 1).count();
+		return if edges >= 2 {
+			found(vec![0, 1], HamiltonianMethod::Trivial)
+		} else {
+			HamiltonianResult::NotFound(NonExistenceProof::LowDegree(0))
+		};
+	}
+
+	if let Some(proof) = quick_refutation(graph, &simple, cycle) {
+		return HamiltonianResult::NotFound(proof);
+	}
+
+	// For a path: a universal vertex added to the graph turns the path into a cycle
+	let extra_degree = if cycle { 0 } else { 1 };
+	if satisfies_ore(&simple, extra_degree, n + extra_degree) {
+		let order = if cycle {
+			ore_rotation(&simple)
+		} else {
+			let mut extended = simple.adjacent.clone();
+			extended.iter_mut().for_each(|row| row.push(true));
+			extended.push(vec![true; n + 1]);
+			extended[n][n] = false;
+			let extended = SimpleGraph {
+				n: n + 1,
+				neighbours: extended.iter().enumerate()
+					.map(|(v, row)| (0..=n).filter(|&u| u != v && row[u]).collect())
+					.collect(),
+				adjacent: extended,
+			};
+			ore_rotation(&extended).map(|mut order| {
+				let position = order.iter().position(|&v| v == n).unwrap();
+				order.rotate_left(position);
+				order.remove(0);
+				order
+			})
+		};
+		if let Some(order) = order {
+			return found(order, HamiltonianMethod::DiracOre);
+		}
+	}
+
+	let (method, vertexes) = if n <= HAMILTONIAN_DP_LIMIT {
+		(HamiltonianMethod::BitmaskDp, bitmask_dp(&simple, cycle))
+	} else {
+		(HamiltonianMethod::Backtracking, backtracking(&simple, cycle))
+	};
+	match vertexes {
+		Some(vertexes) => found(vertexes, method),
+		None => HamiltonianResult::NotFound(NonExistenceProof::Exhaustive(method)),
+	}
+}
+
+/// Hamiltonian cycle of an undirected graph: tries the Dirac/Ore construction,
+/// cheap non-existence certificates, then bitmask DP (n ≤ 20) or backtracking
+pub fn hamiltonian_cycle<G: GraphView>(graph: &G) -> HamiltonianResult {
+	hamiltonian(graph, true)
+}
+
+/// Hamiltonian path of an undirected graph, same strategy as `hamiltonian_cycle`
+pub fn hamiltonian_path<G: GraphView>(graph: &G) -> HamiltonianResult {
+	hamiltonian(graph, false)
+}
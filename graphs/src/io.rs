@@ -0,0 +1,981 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter, Write as _};
+use std::io::{self, Read};
+use std::str::FromStr;
+use crate::{DFSSpace, Decomposition, Graph, WeightedGraph};
+
+#[derive(Debug)]
+pub enum GraphFormatError {
+	Io(io::Error),
+	/// Input ended while `expected` was being read
+	UnexpectedEnd { expected: &'static str },
+	InvalidToken { line: usize, token: String, expected: &'static str },
+	VertexOutOfRange { line: usize, vertex: usize, vertexes: usize },
+	Malformed { line: usize, message: String },
+}
+
+impl Display for GraphFormatError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			GraphFormatError::Io(error) => write!(f, "I/O error: {}", error),
+			GraphFormatError::UnexpectedEnd { expected } => write!(f, "Unexpected end of input, expected {}", expected),
+			GraphFormatError::InvalidToken { line, token, expected } =>
+				write!(f, "Line {}: expected {}, found `{}`", line, expected, token),
+			GraphFormatError::VertexOutOfRange { line, vertex, vertexes } =>
+				write!(f, "Line {}: vertex {} is out of range for {} vertexes", line, vertex, vertexes),
+			GraphFormatError::Malformed { line, message } => write!(f, "Line {}: {}", line, message),
+		}
+	}
+}
+
+impl std::error::Error for GraphFormatError {}
+
+impl From<io::Error> for GraphFormatError {
+	fn from(error: io::Error) -> Self {
+		GraphFormatError::Io(error)
+	}
+}
+
+pub type GraphFormatResult<T> = Result<T, GraphFormatError>;
+
+/// Reads the whole input to feed it into one of the `parse_*` functions
+pub fn read_input<R: Read>(mut reader: R) -> GraphFormatResult<String> {
+	let mut text = String::new();
+	reader.read_to_string(&mut text)?;
+	Ok(text)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indexing {
+	ZeroBased,
+	OneBased,
+}
+
+impl Indexing {
+	fn offset(self) -> usize {
+		match self {
+			Indexing::ZeroBased => 0,
+			Indexing::OneBased => 1,
+		}
+	}
+}
+
+/// Largest number of vertexes an edge list or a DIMACS header may declare: the graph is allocated
+/// before any edge is read and isolated vertexes take no input, so the count isn't bounded by the text
+pub const MAX_PARSED_VERTEXES: usize = 1 << 24;
+
+/// Largest total multiplicity of an adjacency matrix: a single entry can stand for any number of edges
+pub const MAX_PARSED_EDGES: usize = 1 << 24;
+
+/// Whitespace-separated tokens with their line numbers (1-based)
+struct Tokens<'a> {
+	tokens: Vec<(usize, &'a str)>,
+	position: usize,
+}
+
+impl<'a> Tokens<'a> {
+	fn new(text: &'a str) -> Self {
+		let tokens = text.lines()
+			.enumerate()
+			.flat_map(|(i, line)| line.split_whitespace().map(move |token| (i + 1, token)))
+			.collect();
+		Tokens { tokens, position: 0 }
+	}
+
+	fn next_token(&mut self, expected: &'static str) -> GraphFormatResult<(usize, &'a str)> {
+		let token = self.tokens.get(self.position).cloned().ok_or(GraphFormatError::UnexpectedEnd { expected })?;
+		self.position += 1;
+		Ok(token)
+	}
+
+	fn next<T: FromStr>(&mut self, expected: &'static str) -> GraphFormatResult<(usize, T)> {
+		let (line, token) = self.next_token(expected)?;
+		token.parse::<T>()
+			.map(|value| (line, value))
+			.map_err(|_| GraphFormatError::InvalidToken { line, token: token.to_string(), expected })
+	}
+
+	fn next_vertex(&mut self, vertexes: usize, indexing: Indexing) -> GraphFormatResult<(usize, usize)> {
+		let (line, vertex) = self.next::<usize>("vertex")?;
+		if vertex < indexing.offset() || vertex - indexing.offset() >= vertexes {
+			return Err(GraphFormatError::VertexOutOfRange { line, vertex, vertexes });
+		}
+		Ok((line, vertex - indexing.offset()))
+	}
+
+	/// Line of the next token (0 at the end)
+	fn line(&self) -> usize {
+		self.tokens.get(self.position).map(|t| t.0).unwrap_or_default()
+	}
+
+	fn remaining(&self) -> usize {
+		self.tokens.len() - self.position
+	}
+
+	/// Vertex count from a header of a format where isolated vertexes take no input
+	fn vertex_count(&mut self) -> GraphFormatResult<(usize, usize)> {
+		let (line, n) = self.next::<usize>("number of vertexes")?;
+		if n > MAX_PARSED_VERTEXES {
+			return Err(GraphFormatError::Malformed {
+				line,
+				message: format!("{} vertexes is more than the limit of {}", n, MAX_PARSED_VERTEXES),
+			});
+		}
+		Ok((line, n))
+	}
+
+	fn expect_end(&self) -> GraphFormatResult<()> {
+		match self.tokens.get(self.position) {
+			Some(&(line, token)) => Err(GraphFormatError::Malformed { line, message: format!("unexpected trailing token `{}`", token) }),
+			None => Ok(()),
+		}
+	}
+}
+
+fn add_edge<T: Copy + Debug>(graph: &mut WeightedGraph<T>, from: usize, to: usize, weight: T, directed: bool) {
+	if directed {
+		graph.add_weighted_directed_edge(from, to, weight);
+	} else {
+		graph.add_weighted_undirected_edge(from, to, weight);
+	}
+}
+
+/// Weighted edges by index: `(from, to, weight)`
+fn weighted_edge_ends<T: Copy + Debug>(graph: &WeightedGraph<T>) -> Vec<(usize, usize, T)> {
+	let mut ends = vec![None; graph.edges()];
+	for (from, edges) in graph.edges.iter().enumerate() {
+		for edge in edges {
+			if ends[edge.edge_index].is_none() {
+				ends[edge.edge_index] = Some((from, edge.to, edge.weight));
+			}
+		}
+	}
+	ends.into_iter().map(|e| e.expect("Edge indexes should be compact")).collect()
+}
+
+
+/// Edge list: `n m` followed by `m` lines `u v`
+pub fn parse_edge_list(text: &str, indexing: Indexing, directed: bool) -> GraphFormatResult<Graph> {
+	let mut tokens = Tokens::new(text);
+	let (_, n) = tokens.vertex_count()?;
+	let (_, m) = tokens.next::<usize>("number of edges")?;
+	let mut graph = Graph::new(n);
+	for _ in 0..m {
+		let (_, from) = tokens.next_vertex(n, indexing)?;
+		let (_, to) = tokens.next_vertex(n, indexing)?;
+		add_edge(&mut graph, from, to, (), directed);
+	}
+	tokens.expect_end()?;
+	Ok(graph)
+}
+
+/// Weighted edge list: `n m` followed by `m` lines `u v w`
+pub fn parse_weighted_edge_list<T>(text: &str, indexing: Indexing, directed: bool) -> GraphFormatResult<WeightedGraph<T>>
+	where T: FromStr + Copy + Debug
+{
+	let mut tokens = Tokens::new(text);
+	let (_, n) = tokens.vertex_count()?;
+	let (_, m) = tokens.next::<usize>("number of edges")?;
+	let mut graph = WeightedGraph::new(n);
+	for _ in 0..m {
+		let (_, from) = tokens.next_vertex(n, indexing)?;
+		let (_, to) = tokens.next_vertex(n, indexing)?;
+		let (_, weight) = tokens.next::<T>("edge weight")?;
+		add_edge(&mut graph, from, to, weight, directed);
+	}
+	tokens.expect_end()?;
+	Ok(graph)
+}
+
+pub fn format_edge_list(graph: &Graph, indexing: Indexing) -> String {
+	let mut text = format!("{} {}\n", graph.vertexes(), graph.edges());
+	for (from, to) in graph.edge_ends() {
+		writeln!(text, "{} {}", from + indexing.offset(), to + indexing.offset()).unwrap();
+	}
+	text
+}
+
+pub fn format_weighted_edge_list<T: Copy + Debug + Display>(graph: &WeightedGraph<T>, indexing: Indexing) -> String {
+	let mut text = format!("{} {}\n", graph.vertexes(), graph.edges());
+	for (from, to, weight) in weighted_edge_ends(graph) {
+		writeln!(text, "{} {} {}", from + indexing.offset(), to + indexing.offset(), weight).unwrap();
+	}
+	text
+}
+
+
+/// How the rows of an adjacency matrix are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatrixLayout {
+	/// Whitespace-separated entries like `0 1 10`
+	Spaced,
+	/// Every row is a single string of 0s and 1s like `0110`
+	Packed,
+}
+
+fn matrix_row(tokens: &mut Tokens, length: usize, layout: MatrixLayout) -> GraphFormatResult<Vec<usize>> {
+	match layout {
+		MatrixLayout::Spaced => (0..length)
+			.map(|_| tokens.next::<usize>("matrix entry").map(|(_, entry)| entry))
+			.collect(),
+		MatrixLayout::Packed => {
+			let (line, token) = tokens.next_token("packed matrix row")?;
+			if token.len() != length || !token.chars().all(|c| c == '0' || c == '1') {
+				return Err(GraphFormatError::InvalidToken {
+					line,
+					token: token.to_string(),
+					expected: "packed matrix row of 0s and 1s",
+				});
+			}
+			Ok(token.chars().map(|c| if c == '1' { 1 } else { 0 }).collect())
+		}
+	}
+}
+
+/// Number of vertexes of a matrix, checked against the tokens left before anything is allocated.
+/// `tokens_needed(n)` is `None` on overflow
+fn matrix_size(tokens: &mut Tokens, tokens_needed: impl Fn(usize) -> Option<usize>) -> GraphFormatResult<usize> {
+	let (line, n) = tokens.next::<usize>("number of vertexes")?;
+	if tokens_needed(n).is_none_or(|needed| needed > tokens.remaining()) {
+		return Err(GraphFormatError::Malformed {
+			line,
+			message: format!("matrix of {} vertexes is longer than the input", n),
+		});
+	}
+	Ok(n)
+}
+
+/// `multiplicity` plus the entries of a row read at `line`, at most `MAX_PARSED_EDGES`
+fn add_multiplicities(multiplicity: usize, entries: &[usize], line: usize) -> GraphFormatResult<usize> {
+	entries.iter()
+		.try_fold(multiplicity, |sum, &entry| sum.checked_add(entry))
+		.filter(|&sum| sum <= MAX_PARSED_EDGES)
+		.ok_or_else(|| GraphFormatError::Malformed {
+			line,
+			message: format!("matrix has more than the limit of {} edges", MAX_PARSED_EDGES),
+		})
+}
+
+/// Full adjacency matrix: `n` followed by `n` rows, entries are edge multiplicities (0 or 1 when packed).
+/// For an undirected graph the matrix has to be symmetric, a diagonal entry is the number of loops
+pub fn parse_adjacency_matrix(text: &str, layout: MatrixLayout, directed: bool) -> GraphFormatResult<Graph> {
+	let mut tokens = Tokens::new(text);
+	let n = matrix_size(&mut tokens, |n| match layout {
+		MatrixLayout::Spaced => n.checked_mul(n),
+		MatrixLayout::Packed => Some(n),
+	})?;
+	let mut matrix = Vec::with_capacity(n);
+	let mut lines = Vec::with_capacity(n);
+	let mut multiplicity = 0;
+	for i in 0..n {
+		lines.push(tokens.line());
+		let row = matrix_row(&mut tokens, n, layout)?;
+		// An undirected edge is read from the upper triangle only
+		multiplicity = add_multiplicities(multiplicity, &row[if directed { 0 } else { i }..], lines[i])?;
+		matrix.push(row);
+	}
+	tokens.expect_end()?;
+
+	let mut graph = Graph::new(n);
+	for (i, row) in matrix.iter().enumerate() {
+		for (j, &entry) in row.iter().enumerate() {
+			if directed {
+				(0..entry).for_each(|_| graph.add_directed_edge(i, j));
+			} else if entry != matrix[j][i] {
+				return Err(GraphFormatError::Malformed {
+					line: lines[i],
+					message: format!("matrix of an undirected graph is not symmetric at ({}, {})", i, j),
+				});
+			} else if i <= j {
+				(0..entry).for_each(|_| graph.add_undirected_edge(i, j));
+			}
+		}
+	}
+	Ok(graph)
+}
+
+/// Lower triangle of a symmetric 0/1 matrix without the diagonal:
+/// `n` followed by rows of length `1, 2, …, n - 1`
+pub fn parse_triangular_adjacency_matrix(text: &str, layout: MatrixLayout) -> GraphFormatResult<Graph> {
+	let mut tokens = Tokens::new(text);
+	let n = matrix_size(&mut tokens, |n| match layout {
+		MatrixLayout::Spaced => n.checked_mul(n.saturating_sub(1)).map(|entries| entries / 2),
+		MatrixLayout::Packed => Some(n.saturating_sub(1)),
+	})?;
+	let mut matrix = Vec::with_capacity(n.saturating_sub(1));
+	let mut multiplicity = 0;
+	for i in 1..n {
+		let line = tokens.line();
+		let row = matrix_row(&mut tokens, i, layout)?;
+		multiplicity = add_multiplicities(multiplicity, &row, line)?;
+		matrix.push(row);
+	}
+	tokens.expect_end()?;
+
+	let mut graph = Graph::new(n);
+	for (i, row) in matrix.iter().enumerate() {
+		for (j, &entry) in row.iter().enumerate() {
+			(0..entry).for_each(|_| graph.add_undirected_edge(i + 1, j));
+		}
+	}
+	Ok(graph)
+}
+
+fn multiplicity_matrix(graph: &Graph, directed: bool) -> Vec<Vec<usize>> {
+	let n = graph.vertexes();
+	let mut matrix = vec![vec![0; n]; n];
+	for (from, to) in graph.edge_ends() {
+		matrix[from][to] += 1;
+		if !directed && from != to {
+			matrix[to][from] += 1;
+		}
+	}
+	matrix
+}
+
+pub fn format_adjacency_matrix(graph: &Graph, directed: bool) -> String {
+	let mut text = format!("{}\n", graph.vertexes());
+	for row in multiplicity_matrix(graph, directed) {
+		writeln!(text, "{}", row.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ")).unwrap();
+	}
+	text
+}
+
+/// Packed lower triangle (parallel edges are written as a single one, loops are dropped)
+pub fn format_triangular_adjacency_matrix(graph: &Graph) -> String {
+	let matrix = multiplicity_matrix(graph, false);
+	let mut text = format!("{}\n", graph.vertexes());
+	for (i, row) in matrix.iter().enumerate().skip(1) {
+		writeln!(text, "{}", row[..i].iter().map(|&x| if x > 0 { '1' } else { '0' }).collect::<String>()).unwrap();
+	}
+	text
+}
+
+
+/// DIMACS graph colouring / clique format: `p edge n m` and `e u v` lines (1-based, undirected)
+pub fn parse_dimacs(text: &str) -> GraphFormatResult<Graph> {
+	let mut graph = None;
+	for (i, line) in text.lines().enumerate() {
+		let line_number = i + 1;
+		let mut tokens = Tokens::new(line);
+		tokens.tokens.iter_mut().for_each(|t| t.0 = line_number);
+		match tokens.tokens.first().map(|t| t.1) {
+			None | Some("c") => continue,
+			Some("p") => {
+				tokens.next_token("problem line")?;
+				tokens.next_token("problem name")?;
+				let (_, n) = tokens.vertex_count()?;
+				graph = Some(Graph::new(n));
+			}
+			Some("e") => {
+				tokens.next_token("edge line")?;
+				let graph = graph.as_mut().ok_or(GraphFormatError::Malformed {
+					line: line_number,
+					message: "edge before the problem line".to_string(),
+				})?;
+				let (_, from) = tokens.next_vertex(graph.vertexes(), Indexing::OneBased)?;
+				let (_, to) = tokens.next_vertex(graph.vertexes(), Indexing::OneBased)?;
+				graph.add_undirected_edge(from, to);
+			}
+			Some(other) => return Err(GraphFormatError::InvalidToken {
+				line: line_number,
+				token: other.to_string(),
+				expected: "DIMACS line type (c, p or e)",
+			}),
+		}
+	}
+	graph.ok_or(GraphFormatError::UnexpectedEnd { expected: "problem line" })
+}
+
+/// DIMACS network: `p <problem> n m`, arcs `a u v w` (1-based, directed)
+/// and optional `n id s` / `n id t` lines for the source and the sink (as in max-flow instances)
+#[derive(Debug, Clone)]
+pub struct DimacsNetwork<T> {
+	pub problem: String,
+	pub graph: WeightedGraph<T>,
+	pub source: Option<usize>,
+	pub sink: Option<usize>,
+}
+
+pub fn parse_dimacs_network<T>(text: &str) -> GraphFormatResult<DimacsNetwork<T>>
+	where T: FromStr + Copy + Debug
+{
+	let mut network: Option<DimacsNetwork<T>> = None;
+	for (i, line) in text.lines().enumerate() {
+		let line_number = i + 1;
+		let mut tokens = Tokens::new(line);
+		tokens.tokens.iter_mut().for_each(|t| t.0 = line_number);
+		let kind = match tokens.tokens.first().map(|t| t.1) {
+			None | Some("c") => continue,
+			Some(kind) => kind,
+		};
+		tokens.next_token("line type")?;
+		if kind == "p" {
+			let (_, problem) = tokens.next::<String>("problem name")?;
+			let (_, n) = tokens.vertex_count()?;
+			network = Some(DimacsNetwork { problem, graph: WeightedGraph::new(n), source: None, sink: None });
+			continue;
+		}
+		let network = network.as_mut().ok_or(GraphFormatError::Malformed {
+			line: line_number,
+			message: "description before the problem line".to_string(),
+		})?;
+		let n = network.graph.vertexes();
+		match kind {
+			"a" => {
+				let (_, from) = tokens.next_vertex(n, Indexing::OneBased)?;
+				let (_, to) = tokens.next_vertex(n, Indexing::OneBased)?;
+				let (_, weight) = tokens.next::<T>("arc weight")?;
+				network.graph.add_weighted_directed_edge(from, to, weight);
+			}
+			"n" => {
+				let (_, vertex) = tokens.next_vertex(n, Indexing::OneBased)?;
+				match tokens.next_token("node designation")? {
+					(_, "s") => network.source = Some(vertex),
+					(_, "t") => network.sink = Some(vertex),
+					(line, token) => return Err(GraphFormatError::InvalidToken {
+						line,
+						token: token.to_string(),
+						expected: "node designation (s or t)",
+					}),
+				}
+			}
+			other => return Err(GraphFormatError::InvalidToken {
+				line: line_number,
+				token: other.to_string(),
+				expected: "DIMACS line type (c, p, n or a)",
+			}),
+		}
+	}
+	network.ok_or(GraphFormatError::UnexpectedEnd { expected: "problem line" })
+}
+
+pub fn format_dimacs(graph: &Graph) -> String {
+	let mut text = format!("p edge {} {}\n", graph.vertexes(), graph.edges());
+	for (from, to) in graph.edge_ends() {
+		writeln!(text, "e {} {}", from + 1, to + 1).unwrap();
+	}
+	text
+}
+
+pub fn format_dimacs_network<T: Copy + Debug + Display>(network: &DimacsNetwork<T>) -> String {
+	let mut text = format!("p {} {} {}\n", network.problem, network.graph.vertexes(), network.graph.edges());
+	if let Some(source) = network.source {
+		writeln!(text, "n {} s", source + 1).unwrap();
+	}
+	if let Some(sink) = network.sink {
+		writeln!(text, "n {} t", sink + 1).unwrap();
+	}
+	for (from, to, weight) in weighted_edge_ends(&network.graph) {
+		writeln!(text, "a {} {} {}", from + 1, to + 1, weight).unwrap();
+	}
+	text
+}
+
+
+/// Graph read from DOT or GraphML: vertexes are numbered in the order of their first appearance
+#[derive(Debug, Clone)]
+pub struct NamedGraph {
+	pub graph: Graph,
+	pub directed: bool,
+	pub names: Vec<String>,
+	/// Attributes of every edge by its index
+	pub edge_attributes: Vec<Vec<(String, String)>>,
+}
+
+impl NamedGraph {
+	fn new(directed: bool) -> Self {
+		NamedGraph { graph: Graph::new(0), directed, names: Vec::new(), edge_attributes: Vec::new() }
+	}
+
+	fn vertex(&mut self, name: &str, index: &mut HashMap<String, usize>) -> usize {
+		if let Some(&v) = index.get(name) {
+			return v;
+		}
+		let v = self.names.len();
+		index.insert(name.to_string(), v);
+		self.names.push(name.to_string());
+		self.graph.edges.push(Vec::new());
+		v
+	}
+
+	fn add_edge(&mut self, from: usize, to: usize, attributes: Vec<(String, String)>) {
+		add_edge(&mut self.graph, from, to, (), self.directed);
+		self.edge_attributes.push(attributes);
+	}
+
+	/// Value of the attribute of an edge, parsed (e.g. a `weight`)
+	pub fn edge_attribute<T: FromStr>(&self, edge_index: usize, name: &str) -> Option<T> {
+		self.edge_attributes[edge_index].iter()
+			.find(|(key, _)| key == name)
+			.and_then(|(_, value)| value.parse().ok())
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DotToken {
+	Id(String),
+	Symbol(&'static str),
+}
+
+fn dot_tokens(text: &str) -> GraphFormatResult<Vec<(usize, DotToken)>> {
+	let chars = text.chars().collect::<Vec<_>>();
+	let mut tokens = Vec::new();
+	let mut line = 1;
+	let mut i = 0;
+	while i < chars.len() {
+		let c = chars[i];
+		let next = chars.get(i + 1).cloned();
+		if c == '\n' {
+			line += 1;
+			i += 1;
+		} else if c.is_whitespace() {
+			i += 1;
+		} else if c == '#' || (c == '/' && next == Some('/')) {
+			while i < chars.len() && chars[i] != '\n' {
+				i += 1;
+			}
+		} else if c == '/' && next == Some('*') {
+			let start_line = line;
+			i += 2;
+			while !(chars.get(i) == Some(&'*') && chars.get(i + 1) == Some(&'/')) {
+				match chars.get(i) {
+					None => return Err(GraphFormatError::Malformed { line: start_line, message: "unterminated comment".to_string() }),
+					Some('\n') => line += 1,
+					Some(_) => {}
+				}
+				i += 1;
+			}
+			i += 2;
+		} else if c == '"' {
+			let start_line = line;
+			let mut value = String::new();
+			i += 1;
+			loop {
+				match chars.get(i) {
+					None => return Err(GraphFormatError::Malformed { line: start_line, message: "unterminated string".to_string() }),
+					Some('"') => break,
+					Some('\\') if chars.get(i + 1) == Some(&'"') => {
+						value.push('"');
+						i += 1;
+					}
+					Some(&ch) => {
+						if ch == '\n' {
+							line += 1;
+						}
+						value.push(ch);
+					}
+				}
+				i += 1;
+			}
+			i += 1;
+			tokens.push((start_line, DotToken::Id(value)));
+		} else if c == '-' && (next == Some('-') || next == Some('>')) {
+			tokens.push((line, DotToken::Symbol(if next == Some('-') { "--" } else { "->" })));
+			i += 2;
+		} else if let Some(symbol) = ["{", "}", "[", "]", ";", ",", "="].into_iter().find(|s| s.starts_with(c)) {
+			tokens.push((line, DotToken::Symbol(symbol)));
+			i += 1;
+		} else if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' {
+			let start = i;
+			while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.'
+				|| (chars[i] == '-' && i == start)) {
+				i += 1;
+			}
+			tokens.push((line, DotToken::Id(chars[start..i].iter().collect())));
+		} else {
+			return Err(GraphFormatError::Malformed { line, message: format!("unexpected character `{}`", c) });
+		}
+	}
+	Ok(tokens)
+}
+
+/// Subset of Graphviz DOT: `graph`/`digraph`, edge chains `a -- b -- c [attrs]`, node statements.
+/// Subgraphs are flattened, graph/node/edge default attributes are ignored
+pub fn parse_dot(text: &str) -> GraphFormatResult<NamedGraph> {
+	let tokens = dot_tokens(text)?;
+	let last_line = tokens.last().map(|t| t.0).unwrap_or(1);
+	let mut position = 0;
+	let peek = |position: usize| tokens.get(position).map(|t| &t.1);
+	let unexpected = |position: usize, expected: &'static str| match tokens.get(position) {
+		Some((line, token)) => GraphFormatError::InvalidToken {
+			line: *line,
+			token: match token { DotToken::Id(id) => id.clone(), DotToken::Symbol(s) => s.to_string() },
+			expected,
+		},
+		None => GraphFormatError::UnexpectedEnd { expected },
+	};
+
+	if peek(position) == Some(&DotToken::Id("strict".to_string())) {
+		position += 1;
+	}
+	let directed = match peek(position) {
+		Some(DotToken::Id(kind)) if kind == "graph" => false,
+		Some(DotToken::Id(kind)) if kind == "digraph" => true,
+		_ => return Err(unexpected(position, "`graph` or `digraph`")),
+	};
+	position += 1;
+	if let Some(DotToken::Id(_)) = peek(position) {
+		position += 1;
+	}
+	if peek(position) != Some(&DotToken::Symbol("{")) {
+		return Err(unexpected(position, "`{`"));
+	}
+	position += 1;
+
+	let mut result = NamedGraph::new(directed);
+	let mut index = HashMap::new();
+	let mut depth = 1;
+	while depth > 0 {
+		match peek(position) {
+			None => return Err(GraphFormatError::UnexpectedEnd { expected: "`}`" }),
+			Some(DotToken::Symbol("}")) => {
+				depth -= 1;
+				position += 1;
+			}
+			Some(DotToken::Symbol("{")) => {
+				depth += 1;
+				position += 1;
+			}
+			Some(DotToken::Symbol(";")) => position += 1,
+			Some(DotToken::Id(keyword)) if keyword == "subgraph" => {
+				position += 1;
+				if let Some(DotToken::Id(_)) = peek(position) {
+					position += 1;
+				}
+			}
+			Some(DotToken::Id(keyword)) if ["graph", "node", "edge"].contains(&keyword.as_str())
+				&& peek(position + 1) == Some(&DotToken::Symbol("[")) => {
+				position += 1;
+				parse_dot_attributes(&tokens, &mut position, &unexpected)?;
+			}
+			Some(DotToken::Id(first)) => {
+				let mut chain = vec![first.clone()];
+				position += 1;
+				if peek(position) == Some(&DotToken::Symbol("=")) {
+					// Graph attribute like `rankdir = LR`
+					position += 2;
+					continue;
+				}
+				while let Some(DotToken::Symbol(operator)) = peek(position) {
+					if *operator != "--" && *operator != "->" {
+						break;
+					}
+					if (*operator == "->") != directed {
+						return Err(unexpected(position, if directed { "`->`" } else { "`--`" }));
+					}
+					position += 1;
+					match peek(position) {
+						Some(DotToken::Id(id)) => chain.push(id.clone()),
+						_ => return Err(unexpected(position, "node id")),
+					}
+					position += 1;
+				}
+				let attributes = if peek(position) == Some(&DotToken::Symbol("[")) {
+					parse_dot_attributes(&tokens, &mut position, &unexpected)?
+				} else {
+					Vec::new()
+				};
+				let vertexes = chain.iter().map(|name| result.vertex(name, &mut index)).collect::<Vec<_>>();
+				for pair in vertexes.windows(2) {
+					result.add_edge(pair[0], pair[1], attributes.clone());
+				}
+			}
+			Some(_) => return Err(unexpected(position, "statement")),
+		}
+	}
+	if position != tokens.len() {
+		return Err(GraphFormatError::Malformed { line: last_line, message: "content after the closing `}`".to_string() });
+	}
+	Ok(result)
+}
+
+fn parse_dot_attributes<F>(tokens: &[(usize, DotToken)], position: &mut usize, unexpected: &F) -> GraphFormatResult<Vec<(String, String)>>
+	where F: Fn(usize, &'static str) -> GraphFormatError
+{
+	let mut attributes = Vec::new();
+	// Skip `[`
+	*position += 1;
+	loop {
+		match tokens.get(*position).map(|t| &t.1) {
+			Some(DotToken::Symbol("]")) => {
+				*position += 1;
+				return Ok(attributes);
+			}
+			Some(DotToken::Symbol(",")) | Some(DotToken::Symbol(";")) => *position += 1,
+			Some(DotToken::Id(key)) => {
+				if tokens.get(*position + 1).map(|t| &t.1) != Some(&DotToken::Symbol("=")) {
+					return Err(unexpected(*position + 1, "`=`"));
+				}
+				match tokens.get(*position + 2).map(|t| &t.1) {
+					Some(DotToken::Id(value)) => attributes.push((key.clone(), value.clone())),
+					_ => return Err(unexpected(*position + 2, "attribute value")),
+				}
+				*position += 3;
+			}
+			_ => return Err(unexpected(*position, "attribute or `]`")),
+		}
+	}
+}
+
+const PALETTE: [&str; 8] = ["red", "blue", "forestgreen", "orange", "purple", "brown", "deeppink", "cyan4"];
+
+/// What to emphasize in the DOT output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DotStyle<'a> {
+	pub directed: bool,
+	/// Colours vertexes by their component
+	pub vertex_decomposition: Option<&'a Decomposition>,
+	/// Colours edges by their component (e.g. biconnected components)
+	pub edge_decomposition: Option<&'a Decomposition>,
+	/// Edges drawn bold and red (e.g. bridges)
+	pub highlighted_edges: &'a [usize],
+	/// Edge indexes of a path: drawn bold with its vertexes
+	pub path: &'a [usize],
+}
+
+fn format_dot_impl<T: Copy + Debug, F>(graph: &WeightedGraph<T>, style: &DotStyle, label: F) -> String
+	where F: Fn(&T) -> Option<String>
+{
+	let ends = weighted_edge_ends(graph);
+	let mut on_path = vec![false; graph.vertexes()];
+	for &e in style.path {
+		on_path[ends[e].0] = true;
+		on_path[ends[e].1] = true;
+	}
+
+	let mut text = format!("{} G {{\n", if style.directed { "digraph" } else { "graph" });
+	for v in 0..graph.vertexes() {
+		let mut attributes = Vec::new();
+		if let Some(decomposition) = style.vertex_decomposition {
+			let colour = PALETTE[decomposition.component_of(v) % PALETTE.len()];
+			attributes.push(format!("style=filled, fillcolor={}", colour));
+		}
+		if on_path[v] {
+			attributes.push("penwidth=3".to_string());
+		}
+		if attributes.is_empty() {
+			writeln!(text, "\t{};", v).unwrap();
+		} else {
+			writeln!(text, "\t{} [{}];", v, attributes.join(", ")).unwrap();
+		}
+	}
+
+	let operator = if style.directed { "->" } else { "--" };
+	for (index, &(from, to, weight)) in ends.iter().enumerate() {
+		let mut attributes = Vec::new();
+		if let Some(label) = label(&weight) {
+			attributes.push(format!("label=\"{}\"", label));
+		}
+		if let Some(decomposition) = style.edge_decomposition {
+			attributes.push(format!("color={}", PALETTE[decomposition.component_of(index) % PALETTE.len()]));
+		}
+		if style.highlighted_edges.contains(&index) {
+			attributes.push("color=red, penwidth=3".to_string());
+		}
+		if style.path.contains(&index) {
+			attributes.push("penwidth=3, style=bold".to_string());
+		}
+		if attributes.is_empty() {
+			writeln!(text, "\t{} {} {};", from, operator, to).unwrap();
+		} else {
+			writeln!(text, "\t{} {} {} [{}];", from, operator, to, attributes.join(", ")).unwrap();
+		}
+	}
+	text.push_str("}\n");
+	text
+}
+
+pub fn format_dot(graph: &Graph, style: &DotStyle) -> String {
+	format_dot_impl(graph, style, |_| None)
+}
+
+/// Same as `format_dot`, edges are labelled with their weights
+pub fn format_weighted_dot<T: Copy + Debug + Display>(graph: &WeightedGraph<T>, style: &DotStyle) -> String {
+	format_dot_impl(graph, style, |weight| Some(weight.to_string()))
+}
+
+/// Undirected graph in DOT with its bridges highlighted
+pub fn format_dot_with_bridges(graph: &Graph) -> String {
+	let bridges = DFSSpace::new(graph).find_bridges(graph);
+	format_dot(graph, &DotStyle { highlighted_edges: &bridges, ..DotStyle::default() })
+}
+
+
+fn escape_xml(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn unescape_xml(text: &str) -> String {
+	text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&apos;", "'").replace("&amp;", "&")
+}
+
+fn format_graphml_impl<T: Copy + Debug, F>(graph: &WeightedGraph<T>, directed: bool, weight: F) -> String
+	where F: Fn(&T) -> Option<String>
+{
+	let ends = weighted_edge_ends(graph);
+	let weighted = ends.iter().any(|e| weight(&e.2).is_some());
+	let mut text = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+	text.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+	if weighted {
+		text.push_str("  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"string\"/>\n");
+	}
+	writeln!(text, "  <graph id=\"G\" edgedefault=\"{}\">", if directed { "directed" } else { "undirected" }).unwrap();
+	for v in 0..graph.vertexes() {
+		writeln!(text, "    <node id=\"n{}\"/>", v).unwrap();
+	}
+	for (index, (from, to, w)) in ends.iter().enumerate() {
+		match weight(w) {
+			Some(w) => writeln!(text, "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"><data key=\"weight\">{}</data></edge>",
+			                    index, from, to, escape_xml(&w)).unwrap(),
+			None => writeln!(text, "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"/>", index, from, to).unwrap(),
+		}
+	}
+	text.push_str("  </graph>\n</graphml>\n");
+	text
+}
+
+pub fn format_graphml(graph: &Graph, directed: bool) -> String {
+	format_graphml_impl(graph, directed, |_| None)
+}
+
+pub fn format_weighted_graphml<T: Copy + Debug + Display>(graph: &WeightedGraph<T>, directed: bool) -> String {
+	format_graphml_impl(graph, directed, |w| Some(w.to_string()))
+}
+
+/// XML tag: name, attributes, whether it's a closing tag and whether it's self-closing
+struct XmlTag {
+	line: usize,
+	name: String,
+	attributes: Vec<(String, String)>,
+	closing: bool,
+	self_closing: bool,
+	/// Text right after the tag (up to the next one)
+	text_after: String,
+}
+
+fn xml_tags(text: &str) -> GraphFormatResult<Vec<XmlTag>> {
+	let mut tags = Vec::new();
+	let mut rest = text;
+	let mut line = 1;
+	while let Some(start) = rest.find('<') {
+		line += rest[..start].matches('\n').count();
+		rest = &rest[start..];
+		let skip_until = |rest: &str, terminator: &str| rest.find(terminator).map(|i| i + terminator.len());
+		let skipped = if rest.starts_with("<!--") {
+			skip_until(rest, "-->")
+		} else if rest.starts_with("<?") {
+			skip_until(rest, "?>")
+		} else if rest.starts_with("<!") {
+			skip_until(rest, ">")
+		} else {
+			None
+		};
+		if rest.starts_with("<!") || rest.starts_with("<?") {
+			let end = skipped.ok_or(GraphFormatError::Malformed { line, message: "unterminated markup".to_string() })?;
+			line += rest[..end].matches('\n').count();
+			rest = &rest[end..];
+			continue;
+		}
+
+		let end = rest.find('>').ok_or(GraphFormatError::Malformed { line, message: "unterminated tag".to_string() })?;
+		let body = &rest[1..end];
+		let tag_line = line;
+		line += body.matches('\n').count();
+		rest = &rest[end + 1..];
+
+		let closing = body.starts_with('/');
+		let self_closing = body.ends_with('/');
+		let body = body.trim_start_matches('/').trim_end_matches('/').trim();
+		let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+		let name = body[..name_end].to_string();
+		let mut attributes = Vec::new();
+		let mut attribute_text = body[name_end..].trim();
+		while !attribute_text.is_empty() {
+			let malformed = || GraphFormatError::Malformed { line: tag_line, message: format!("malformed attributes of <{}>", name) };
+			let equals = attribute_text.find('=').ok_or_else(malformed)?;
+			let key = attribute_text[..equals].trim().to_string();
+			let value_text = attribute_text[equals + 1..].trim_start();
+			let quote = value_text.chars().next().filter(|&c| c == '"' || c == '\'').ok_or_else(malformed)?;
+			let value_end = value_text[1..].find(quote).ok_or_else(malformed)? + 1;
+			attributes.push((key, unescape_xml(&value_text[1..value_end])));
+			attribute_text = value_text[value_end + 1..].trim_start();
+		}
+
+		let text_end = rest.find('<').unwrap_or(rest.len());
+		tags.push(XmlTag { line: tag_line, name, attributes, closing, self_closing, text_after: unescape_xml(rest[..text_end].trim()) });
+	}
+	Ok(tags)
+}
+
+/// GraphML: nodes, edges (with the `directed` attribute overriding `edgedefault`) and edge `data`,
+/// whose keys are resolved to `attr.name` when it's declared. Only the first graph is read
+pub fn parse_graphml(text: &str) -> GraphFormatResult<NamedGraph> {
+	let tags = xml_tags(text)?;
+	let attribute = |tag: &XmlTag, name: &str| tag.attributes.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
+	let missing = |tag: &XmlTag, name: &str| GraphFormatError::Malformed {
+		line: tag.line,
+		message: format!("<{}> without `{}`", tag.name, name),
+	};
+
+	let mut key_names = HashMap::new();
+	let mut result: Option<NamedGraph> = None;
+	let mut index = HashMap::new();
+	// Edges are added after all nodes are known: (source, target, directed, attributes)
+	let mut edges = Vec::new();
+	let mut current_edge: Option<usize> = None;
+	for tag in &tags {
+		if tag.closing {
+			match tag.name.as_str() {
+				"edge" => current_edge = None,
+				"graph" => break,
+				_ => {}
+			}
+			continue;
+		}
+		match tag.name.as_str() {
+			"key" => {
+				if let (Some(id), Some(name)) = (attribute(tag, "id"), attribute(tag, "attr.name")) {
+					key_names.insert(id, name);
+				}
+			}
+			"graph" => {
+				let directed = attribute(tag, "edgedefault").map(|d| d == "directed").unwrap_or(false);
+				result = Some(NamedGraph::new(directed));
+			}
+			"node" => {
+				let graph = result.as_mut().ok_or(GraphFormatError::Malformed { line: tag.line, message: "<node> outside of <graph>".to_string() })?;
+				let id = attribute(tag, "id").ok_or_else(|| missing(tag, "id"))?;
+				graph.vertex(&id, &mut index);
+			}
+			"edge" => {
+				let graph = result.as_ref().ok_or(GraphFormatError::Malformed { line: tag.line, message: "<edge> outside of <graph>".to_string() })?;
+				let source = attribute(tag, "source").ok_or_else(|| missing(tag, "source"))?;
+				let target = attribute(tag, "target").ok_or_else(|| missing(tag, "target"))?;
+				let directed = attribute(tag, "directed").map(|d| d == "true").unwrap_or(graph.directed);
+				edges.push((tag.line, source, target, directed, Vec::new()));
+				if !tag.self_closing {
+					current_edge = Some(edges.len() - 1);
+				}
+			}
+			"data" => {
+				if let Some(edge) = current_edge {
+					let key = attribute(tag, "key").ok_or_else(|| missing(tag, "key"))?;
+					let name = key_names.get(&key).cloned().unwrap_or(key);
+					edges[edge].4.push((name, tag.text_after.clone()));
+				}
+			}
+			_ => {}
+		}
+	}
+
+	let mut result = result.ok_or(GraphFormatError::UnexpectedEnd { expected: "<graph>" })?;
+	for (line, source, target, directed, attributes) in edges {
+		let unknown = |name: &str| GraphFormatError::Malformed { line, message: format!("edge refers to unknown node `{}`", name) };
+		let from = *index.get(&source).ok_or_else(|| unknown(&source))?;
+		let to = *index.get(&target).ok_or_else(|| unknown(&target))?;
+		add_edge(&mut result.graph, from, to, (), directed);
+		result.edge_attributes.push(attributes);
+	}
+	Ok(result)
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use crate::{Decomposition, GraphView};
+
+/// Isomorphism invariant of an undirected multigraph: equal forms mean isomorphic graphs and vice versa
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalForm {
+	pub vertexes: usize,
+	/// `(u, v, multiplicity)` with `u ≤ v` in canonical labels, sorted.
+	/// A loop is counted twice, as it's stored twice in the adjacency list
+	pub edges: Vec<(usize, usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CanonicalLabelling {
+	/// Canonical label of every vertex
+	pub labelling: Vec<usize>,
+	pub form: CanonicalForm,
+	/// Generators of the automorphism group, `generator[v]` is the image of `v`
+	pub automorphisms: Vec<Vec<usize>>,
+	/// Order of the automorphism group (saturates at `u128::MAX`)
+	pub group_order: u128,
+}
+
+/// Merged adjacency lists: `(neighbour, number of arcs to it)`
+fn multiplicities<G: GraphView>(graph: &G) -> Vec<Vec<(usize, usize)>> {
+	(0..graph.vertexes())
+		.map(|v| {
+			let mut ends = graph.neighbours(v).map(|edge| edge.to).collect::<Vec<_>>();
+			ends.sort();
+			let mut merged: Vec<(usize, usize)> = Vec::new();
+			for to in ends {
+				match merged.last_mut() {
+					Some((last, count)) if *last == to => *count += 1,
+					_ => merged.push((to, 1)),
+				}
+			}
+			merged
+		})
+		.collect()
+}
+
+/// Colour refinement (1-WL) until the partition is equitable. A vertex gets the rank of
+/// (its colour, multiset of neighbour colours), so the result doesn't depend on the vertex numbers
+fn refine(neighbours: &[Vec<(usize, usize)>], colour: &mut [usize]) {
+	let n = colour.len();
+	let mut cells = cell_count(colour);
+	loop {
+		let signatures = (0..n)
+			.map(|v| {
+				let mut around = neighbours[v].iter().map(|&(u, count)| (colour[u], count)).collect::<Vec<_>>();
+				around.sort();
+				(colour[v], around)
+			})
+			.collect::<Vec<_>>();
+		let mut order = (0..n).collect::<Vec<_>>();
+		order.sort_by(|&a, &b| signatures[a].cmp(&signatures[b]));
+		let mut rank = 0;
+		for (i, &v) in order.iter().enumerate() {
+			if i > 0 && signatures[v] != signatures[order[i - 1]] {
+				rank += 1;
+			}
+			colour[v] = rank;
+		}
+		let refined = cell_count(colour);
+		if refined == cells {
+			return;
+		}
+		cells = refined;
+	}
+}
+
+fn cell_count(colour: &[usize]) -> usize {
+	let mut distinct = colour.to_vec();
+	distinct.sort();
+	distinct.dedup();
+	distinct.len()
+}
+
+/// `v` gets a colour of its own, placed right before the rest of its cell
+fn individualize(colour: &[usize], v: usize) -> Vec<usize> {
+	colour.iter()
+		.enumerate()
+		.map(|(u, &c)| 2 * c + (c == colour[v] && u != v) as usize)
+		.collect()
+}
+
+fn find(parent: &mut [usize], v: usize) -> usize {
+	if parent[v] != v {
+		parent[v] = find(parent, parent[v]);
+	}
+	parent[v]
+}
+
+/// Individualization–refinement search tree. Children of a node that are mapped onto each other
+/// by the automorphisms found so far (fixing the individualized vertexes) give the same leaves
+struct Search<'a> {
+	neighbours: &'a [Vec<(usize, usize)>],
+	first: Option<(CanonicalForm, Vec<usize>)>,
+	best: Option<(CanonicalForm, Vec<usize>)>,
+	automorphisms: Vec<Vec<usize>>,
+	/// Individualized vertexes and target cells on the way to the first leaf
+	first_path: Vec<(usize, Vec<usize>)>,
+}
+
+impl Search<'_> {
+	fn form(&self, labelling: &[usize]) -> CanonicalForm {
+		let mut edges = self.neighbours.iter()
+			.enumerate()
+			.flat_map(|(v, around)| around.iter().map(move |&(u, count)| (v, u, count)))
+			.filter(|&(v, u, _)| v <= u)
+			.map(|(v, u, count)| {
+				let (a, b) = (labelling[v], labelling[u]);
+				(a.min(b), a.max(b), count)
+			})
+			.collect::<Vec<_>>();
+		edges.sort();
+		CanonicalForm { vertexes: labelling.len(), edges }
+	}
+
+	/// `u ↦ v` where `v` has the same label in the reference leaf as `u` in this one
+	fn record_automorphism(&mut self, reference: &[usize], labelling: &[usize]) {
+		let mut vertex_of = vec![0; reference.len()];
+		for (v, &label) in reference.iter().enumerate() {
+			vertex_of[label] = v;
+		}
+		let automorphism = labelling.iter().map(|&label| vertex_of[label]).collect::<Vec<_>>();
+		if automorphism.iter().enumerate().any(|(v, &image)| v != image) {
+			self.automorphisms.push(automorphism);
+		}
+	}
+
+	fn leaf(&mut self, labelling: Vec<usize>) {
+		let form = self.form(&labelling);
+		let Some((first_form, first_labelling)) = &self.first else {
+			self.first = Some((form.clone(), labelling.clone()));
+			self.best = Some((form, labelling));
+			return;
+		};
+		let (best_form, best_labelling) = self.best.as_ref().unwrap();
+		if form == *first_form {
+			let reference = first_labelling.clone();
+			self.record_automorphism(&reference, &labelling);
+		} else if form == *best_form {
+			let reference = best_labelling.clone();
+			self.record_automorphism(&reference, &labelling);
+		} else if form < *best_form {
+			self.best = Some((form, labelling));
+		}
+	}
+
+	/// Union–find of the orbits of the automorphisms fixing every vertex of `fixed`
+	fn orbits(&self, fixed: &[usize]) -> Vec<usize> {
+		let mut parent = (0..self.neighbours.len()).collect::<Vec<_>>();
+		for automorphism in self.automorphisms.iter().filter(|a| fixed.iter().all(|&v| a[v] == v)) {
+			for (v, &image) in automorphism.iter().enumerate() {
+				let (a, b) = (find(&mut parent, v), find(&mut parent, image));
+				parent[a] = b;
+			}
+		}
+		parent
+	}
+
+	fn search(&mut self, mut colour: Vec<usize>, path: &mut Vec<usize>) {
+		refine(self.neighbours, &mut colour);
+		let n = colour.len();
+		let mut size = vec![0; n];
+		for &c in &colour {
+			size[c] += 1;
+		}
+		// The smallest non-trivial cell, the first one of them
+		let Some(target) = (0..n).filter(|&c| size[c] > 1).min_by_key(|&c| size[c]) else {
+			self.leaf(colour);
+			return;
+		};
+		let cell = (0..n).filter(|&v| colour[v] == target).collect::<Vec<_>>();
+		let on_first_path = self.first.is_none();
+
+		let mut explored: Vec<usize> = Vec::new();
+		for &v in &cell {
+			let mut orbits = self.orbits(path);
+			let root = find(&mut orbits, v);
+			if explored.iter().any(|&w| find(&mut orbits, w) == root) {
+				continue;
+			}
+			if on_first_path && explored.is_empty() {
+				self.first_path.push((v, cell.clone()));
+			}
+			path.push(v);
+			self.search(individualize(&colour, v), path);
+			path.pop();
+			explored.push(v);
+		}
+	}
+
+	/// Orbit–stabilizer along the first path: |Aut| is the product of the orbit sizes
+	/// of the individualized vertexes in the successive pointwise stabilizers
+	fn group_order(&self) -> u128 {
+		let mut order = 1_u128;
+		let mut fixed = Vec::new();
+		for (v, cell) in &self.first_path {
+			let mut orbits = self.orbits(&fixed);
+			let root = find(&mut orbits, *v);
+			let orbit = cell.iter().filter(|&&w| find(&mut orbits, w) == root).count();
+			order = order.saturating_mul(orbit as u128);
+			fixed.push(*v);
+		}
+		order
+	}
+}
+
+/// Canonical labelling of an undirected multigraph by colour refinement and individualization,
+/// with the automorphism group found along the way. Fast on most graphs, exponential in the worst case
+pub fn canonical_labelling<G: GraphView>(graph: &G) -> CanonicalLabelling {
+	let neighbours = multiplicities(graph);
+	let mut search = Search { neighbours: &neighbours, first: None, best: None, automorphisms: Vec::new(), first_path: Vec::new() };
+	search.search(vec![0; graph.vertexes()], &mut Vec::new());
+	let group_order = search.group_order();
+	let (form, labelling) = search.best.unwrap();
+	CanonicalLabelling { labelling, form, automorphisms: search.automorphisms, group_order }
+}
+
+pub fn canonical_form<G: GraphView>(graph: &G) -> CanonicalForm {
+	canonical_labelling(graph).form
+}
+
+/// Generators of the automorphism group
+pub fn automorphism_generators<G: GraphView>(graph: &G) -> Vec<Vec<usize>> {
+	canonical_labelling(graph).automorphisms
+}
+
+/// Isomorphism from `a` to `b`: `mapping[v]` is the vertex of `b` that corresponds to `v`
+pub fn are_isomorphic<A: GraphView, B: GraphView>(a: &A, b: &B) -> Option<Vec<usize>> {
+	if a.vertexes() != b.vertexes() {
+		return None;
+	}
+	let (a, b) = (canonical_labelling(a), canonical_labelling(b));
+	if a.form != b.form {
+		return None;
+	}
+	let mut vertex_of = vec![0; b.labelling.len()];
+	for (v, &label) in b.labelling.iter().enumerate() {
+		vertex_of[label] = v;
+	}
+	Some(a.labelling.iter().map(|&label| vertex_of[label]).collect())
+}
+
+/// Splits a family of graphs into isomorphism classes (in the order of the first appearance)
+pub fn isomorphism_classes<G: GraphView>(graphs: &[G]) -> Decomposition {
+	let mut class_of_form = HashMap::new();
+	let component_map = graphs.iter()
+		.map(|graph| {
+			let next = class_of_form.len();
+			*class_of_form.entry(canonical_form(graph)).or_insert(next)
+		})
+		.collect();
+	Decomposition::from_component_map(component_map)
+}
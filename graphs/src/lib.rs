@@ -1,7 +1,6 @@
 use {
 	std::{
 		io::{
-			self,
 			Read,
 			Write,
 			Stdin,
@@ -35,6 +34,87 @@ pub use self::graph::*;
 pub mod dfs;
 pub use self::dfs::*;
 
+pub mod flow;
+pub use self::flow::*;
+
+pub mod matching;
+pub use self::matching::*;
+
+pub mod euler;
+pub use self::euler::*;
+
+pub mod hamilton;
+pub use self::hamilton::*;
+
+pub mod tree;
+pub use self::tree::*;
+
+pub mod polynomial;
+pub use self::polynomial::*;
+
+pub mod chromatic;
+pub use self::chromatic::*;
+
+pub mod tutte;
+pub use self::tutte::*;
+
+pub mod io;
+pub use self::io::*;
+
+pub mod generators;
+pub use self::generators::*;
+
+pub mod two_sat;
+pub use self::two_sat::*;
+
+pub mod dynamic;
+pub use self::dynamic::*;
+
+pub mod view;
+pub use self::view::*;
+
+pub mod csr;
+pub use self::csr::*;
+
+pub mod shortest_paths;
+pub use self::shortest_paths::*;
+
+pub mod isomorphism;
+pub use self::isomorphism::*;
+
+pub mod block_cut_tree;
+pub use self::block_cut_tree::*;
+
+pub mod bridge_tree;
+pub use self::bridge_tree::*;
+
+pub mod planarity;
+pub use self::planarity::*;
+
+pub mod colouring;
+pub use self::colouring::*;
+
+pub mod cliques;
+pub use self::cliques::*;
+
+pub mod bigint;
+pub use self::bigint::*;
+
+pub mod spanning_trees;
+pub use self::spanning_trees::*;
+
+pub mod dag;
+pub use self::dag::*;
+
+pub mod topological_sort;
+pub use self::topological_sort::*;
+
+pub mod cycles;
+pub use self::cycles::*;
+
+pub mod connectivity;
+pub use self::connectivity::*;
+
 
 pub fn print_vec<T: Display>(vec: &Vec<T>) {
 	println!("{}", vec.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" "));
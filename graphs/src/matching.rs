@@ -0,0 +1,318 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use crate::{two_colouring, CsrGraph, Decomposition, GraphView};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matching {
+	/// Edge indexes of the matching edges
+	pub matched_edges: Vec<usize>,
+	/// For each vertex — the vertex it's matched with
+	pub mate: Vec<Option<usize>>,
+}
+
+impl Matching {
+	pub fn size(&self) -> usize {
+		self.matched_edges.len()
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BipartiteMatching {
+	pub matching: Matching,
+	/// König's minimum vertex cover (its size equals the size of the matching)
+	pub vertex_cover: Vec<usize>,
+	/// Complement of the vertex cover
+	pub independent_set: Vec<usize>,
+}
+
+/// Splits vertexes of an undirected graph into two sides (components 0 and 1 of the decomposition, the second
+/// one is empty without edges) such that every edge goes between the sides. Returns `None` if the graph
+/// is not bipartite, `two_colouring` gives an odd cycle instead
+pub fn bipartition<G: GraphView>(graph: &G) -> Option<Decomposition> {
+	let mut sides = two_colouring(graph).ok()?.component_list;
+	sides.resize(2, Vec::new());
+	Some(Decomposition::from_component_list(sides))
+}
+
+const UNREACHED: usize = usize::MAX;
+
+struct HopcroftKarp<T> {
+	/// Neighbour lists are materialised once, so that `next_edge` indexes them in O(1) even for filtered views
+	graph: CsrGraph<T>,
+	is_left: Vec<bool>,
+	/// Edge index of the matching edge for every matched vertex
+	mate_edge: Vec<Option<usize>>,
+	mate: Vec<Option<usize>>,
+	layer: Vec<usize>,
+	next_edge: Vec<usize>,
+}
+
+impl<T: Clone + Copy + Debug> HopcroftKarp<T> {
+	/// Layers free left vertexes and everything reachable by alternating paths.
+	/// Returns whether some free right vertex is reachable
+	fn build_layers(&mut self, left: &[usize]) -> bool {
+		self.layer.iter_mut().for_each(|l| *l = UNREACHED);
+		let mut queue = VecDeque::new();
+		for &u in left {
+			if self.mate[u].is_none() {
+				self.layer[u] = 0;
+				queue.push_back(u);
+			}
+		}
+		let mut found = false;
+		while let Some(u) = queue.pop_front() {
+			for edge in self.graph.edges_of(u) {
+				if !self.is_left[edge.to] {
+					match self.mate[edge.to] {
+						None => found = true,
+						Some(w) if self.layer[w] == UNREACHED => {
+							self.layer[w] = self.layer[u] + 1;
+							queue.push_back(w);
+						}
+						_ => {}
+					}
+				}
+			}
+		}
+		found
+	}
+
+	fn augment(&mut self, u: usize) -> bool {
+		while let Some(&edge) = self.graph.edges_of(u).get(self.next_edge[u]) {
+			self.next_edge[u] += 1;
+			if self.is_left[edge.to] {
+				continue;
+			}
+			let advance = match self.mate[edge.to] {
+				None => true,
+				Some(w) => self.layer[w] == self.layer[u] + 1 && self.augment(w),
+			};
+			if advance {
+				self.mate[u] = Some(edge.to);
+				self.mate[edge.to] = Some(u);
+				self.mate_edge[u] = Some(edge.edge_index);
+				self.mate_edge[edge.to] = Some(edge.edge_index);
+				return true;
+			}
+		}
+		self.layer[u] = UNREACHED;
+		false
+	}
+}
+
+/// Hopcroft–Karp maximum matching, O(E √V).
+/// `sides` should have two components: the left one (0) and the right one (1)
+pub fn hopcroft_karp<G: GraphView>(graph: &G, sides: &Decomposition) -> BipartiteMatching {
+	let n = graph.vertexes();
+	debug_assert_eq!(sides.elements, n, "Decomposition should cover all vertexes");
+	let left = sides.component_list.first().cloned().unwrap_or_default();
+	let mut is_left = vec![false; n];
+	for &v in &left {
+		is_left[v] = true;
+	}
+
+	let mut state = HopcroftKarp {
+		graph: CsrGraph::from_view(graph),
+		is_left,
+		mate_edge: vec![None; n],
+		mate: vec![None; n],
+		layer: vec![UNREACHED; n],
+		next_edge: vec![0; n],
+	};
+
+	while state.build_layers(&left) {
+		state.next_edge.iter_mut().for_each(|i| *i = 0);
+		for &u in &left {
+			if state.mate[u].is_none() {
+				state.augment(u);
+			}
+		}
+	}
+
+	// König: vertexes reachable from free left vertexes by alternating paths
+	let mut reachable = vec![false; n];
+	let mut queue = VecDeque::new();
+	for &u in &left {
+		if state.mate[u].is_none() {
+			reachable[u] = true;
+			queue.push_back(u);
+		}
+	}
+	while let Some(u) = queue.pop_front() {
+		for edge in graph.neighbours(u) {
+			if state.is_left[edge.to] || reachable[edge.to] || state.mate_edge[u] == Some(edge.edge_index) {
+				continue;
+			}
+			reachable[edge.to] = true;
+			if let Some(w) = state.mate[edge.to] {
+				if !reachable[w] {
+					reachable[w] = true;
+					queue.push_back(w);
+				}
+			}
+		}
+	}
+	let (vertex_cover, independent_set): (Vec<usize>, Vec<usize>) = (0..n)
+		.partition(|&v| state.is_left[v] != reachable[v]);
+
+	let mut matched_edges = left.iter()
+		.filter_map(|&u| state.mate_edge[u])
+		.collect::<Vec<_>>();
+	matched_edges.sort();
+
+	BipartiteMatching {
+		matching: Matching { matched_edges, mate: state.mate },
+		vertex_cover,
+		independent_set,
+	}
+}
+
+/// Maximum matching of a bipartite graph with the sides detected automatically.
+/// Returns `None` if the graph is not bipartite
+pub fn maximum_bipartite_matching<G: GraphView>(graph: &G) -> Option<BipartiteMatching> {
+	bipartition(graph).map(|sides| hopcroft_karp(graph, &sides))
+}
+
+
+struct Blossom<'a, G> {
+	graph: &'a G,
+	mate: Vec<Option<usize>>,
+	parent: Vec<Option<usize>>,
+	base: Vec<usize>,
+	used: Vec<bool>,
+	in_blossom: Vec<bool>,
+}
+
+impl<G: GraphView> Blossom<'_, G> {
+	fn lowest_common_ancestor(&self, mut a: usize, mut b: usize) -> usize {
+		let mut on_path = vec![false; self.graph.vertexes()];
+		loop {
+			a = self.base[a];
+			on_path[a] = true;
+			match self.mate[a] {
+				Some(m) => a = self.parent[m].unwrap(),
+				None => break,
+			}
+		}
+		loop {
+			b = self.base[b];
+			if on_path[b] {
+				return b;
+			}
+			b = self.parent[self.mate[b].unwrap()].unwrap();
+		}
+	}
+
+	fn mark_path(&mut self, mut v: usize, b: usize, mut child: usize) {
+		while self.base[v] != b {
+			let m = self.mate[v].unwrap();
+			self.in_blossom[self.base[v]] = true;
+			self.in_blossom[self.base[m]] = true;
+			self.parent[v] = Some(child);
+			child = m;
+			v = self.parent[m].unwrap();
+		}
+	}
+
+	/// Looks for an augmenting path from `root` and returns its free end
+	fn find_path(&mut self, root: usize) -> Option<usize> {
+		let graph = self.graph;
+		let n = graph.vertexes();
+		self.used.iter_mut().for_each(|u| *u = false);
+		self.parent.iter_mut().for_each(|p| *p = None);
+		for (i, b) in self.base.iter_mut().enumerate() {
+			*b = i;
+		}
+
+		self.used[root] = true;
+		let mut queue = VecDeque::from([root]);
+		while let Some(v) = queue.pop_front() {
+			for edge in graph.neighbours(v) {
+				let to = edge.to;
+				if self.base[v] == self.base[to] || self.mate[v] == Some(to) {
+					continue;
+				}
+				if to == root || self.mate[to].is_some_and(|m| self.parent[m].is_some()) {
+					// Odd cycle: contract the blossom
+					let current_base = self.lowest_common_ancestor(v, to);
+					self.in_blossom.iter_mut().for_each(|b| *b = false);
+					self.mark_path(v, current_base, to);
+					self.mark_path(to, current_base, v);
+					for i in 0..n {
+						if self.in_blossom[self.base[i]] {
+							self.base[i] = current_base;
+							if !self.used[i] {
+								self.used[i] = true;
+								queue.push_back(i);
+							}
+						}
+					}
+				} else if self.parent[to].is_none() {
+					self.parent[to] = Some(v);
+					match self.mate[to] {
+						None => return Some(to),
+						Some(m) => {
+							self.used[m] = true;
+							queue.push_back(m);
+						}
+					}
+				}
+			}
+		}
+		None
+	}
+}
+
+/// Maximum matching of an arbitrary undirected graph (Edmonds' blossom algorithm), O(V³)
+pub fn maximum_matching<G: GraphView>(graph: &G) -> Matching {
+	let n = graph.vertexes();
+	let mut state = Blossom {
+		graph,
+		mate: vec![None; n],
+		parent: vec![None; n],
+		base: (0..n).collect(),
+		used: vec![false; n],
+		in_blossom: vec![false; n],
+	};
+
+	// Greedy initial matching speeds things up considerably
+	for v in 0..n {
+		if state.mate[v].is_none() {
+			if let Some(edge) = graph.neighbours(v).find(|e| e.to != v && state.mate[e.to].is_none()) {
+				state.mate[v] = Some(edge.to);
+				state.mate[edge.to] = Some(v);
+			}
+		}
+	}
+
+	for root in 0..n {
+		if state.mate[root].is_some() {
+			continue;
+		}
+		if let Some(mut v) = state.find_path(root) {
+			// Flip the augmenting path
+			loop {
+				let pv = state.parent[v].unwrap();
+				let ppv = state.mate[pv];
+				state.mate[v] = Some(pv);
+				state.mate[pv] = Some(v);
+				match ppv {
+					Some(next) => v = next,
+					None => break,
+				}
+			}
+		}
+	}
+
+	let mut matched_edges = Vec::new();
+	for v in 0..n {
+		if let Some(u) = state.mate[v] {
+			if v < u {
+				matched_edges.push(graph.neighbours(v).find(|e| e.to == u).unwrap().edge_index);
+			}
+		}
+	}
+	matched_edges.sort();
+
+	Matching { matched_edges, mate: state.mate }
+}
@@ -0,0 +1,614 @@
+use std::collections::HashMap;
+use crate::{Edge, Graph, GraphView};
+
+/// Edges around every vertex in the clockwise order. Parallel edges go next to each other,
+/// a loop appears twice in a row
+#[derive(Debug, Clone)]
+pub struct PlanarEmbedding {
+	pub rotation: Vec<Vec<Edge>>,
+}
+
+/// Boundary walk of a face: `edges[i]` goes from `vertexes[i]` to `vertexes[i + 1]` (cyclically)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Face {
+	pub vertexes: Vec<usize>,
+	pub edges: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KuratowskiKind {
+	K5,
+	K33,
+}
+
+/// Subdivision of K₅ or K₃,₃ contained in the graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KuratowskiSubgraph {
+	pub kind: KuratowskiKind,
+	/// Sorted edge indexes
+	pub edges: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Planarity {
+	Planar(PlanarEmbedding),
+	NonPlanar(KuratowskiSubgraph),
+}
+
+impl Planarity {
+	pub fn embedding(&self) -> Option<&PlanarEmbedding> {
+		match self {
+			Planarity::Planar(embedding) => Some(embedding),
+			Planarity::NonPlanar(_) => None,
+		}
+	}
+}
+
+impl PlanarEmbedding {
+	/// Positions of the two darts of every edge: `(vertex, index in its rotation)`
+	fn darts(&self) -> Vec<Vec<(usize, usize)>> {
+		let edges = self.rotation.iter().flatten().map(|e| e.edge_index + 1).max().unwrap_or_default();
+		let mut darts = vec![Vec::with_capacity(2); edges];
+		for (v, around) in self.rotation.iter().enumerate() {
+			for (i, edge) in around.iter().enumerate() {
+				darts[edge.edge_index].push((v, i));
+			}
+		}
+		darts
+	}
+
+	/// Faces of every connected component with edges (an isolated vertex has no face walk).
+	/// A dart is followed by the next one clockwise after its reverse
+	pub fn faces(&self) -> Vec<Face> {
+		self.faces_with_darts().0
+	}
+
+	/// Faces and the face to the right of every dart
+	fn faces_with_darts(&self) -> (Vec<Face>, Vec<Vec<usize>>) {
+		let darts = self.darts();
+		let reverse = |v: usize, i: usize| {
+			let pair = &darts[self.rotation[v][i].edge_index];
+			if pair[0] == (v, i) { pair[1] } else { pair[0] }
+		};
+		let mut face_of = self.rotation.iter().map(|around| vec![usize::MAX; around.len()]).collect::<Vec<_>>();
+		let mut faces = Vec::new();
+		for start in 0..self.rotation.len() {
+			for start_index in 0..self.rotation[start].len() {
+				if face_of[start][start_index] != usize::MAX {
+					continue;
+				}
+				let mut face = Face { vertexes: Vec::new(), edges: Vec::new() };
+				let (mut v, mut i) = (start, start_index);
+				while face_of[v][i] == usize::MAX {
+					face_of[v][i] = faces.len();
+					face.vertexes.push(v);
+					face.edges.push(self.rotation[v][i].edge_index);
+					let (w, j) = reverse(v, i);
+					(v, i) = (w, (j + 1) % self.rotation[w].len());
+				}
+				faces.push(face);
+			}
+		}
+		(faces, face_of)
+	}
+
+	/// Dual graph on the faces: edge `i` of the dual crosses edge `i` of the graph
+	/// (a bridge gives a loop), so unused indexes of the graph stay unused in the dual.
+	/// Returns the faces as well, vertex `f` of the dual is `faces[f]`
+	pub fn dual(&self) -> (Graph, Vec<Face>) {
+		let (faces, face_of) = self.faces_with_darts();
+		let mut dual = Graph::new(faces.len());
+		for (edge_index, pair) in self.darts().into_iter().enumerate() {
+			if let [(u, i), (v, j)] = pair[..] {
+				let (f, g) = (face_of[u][i], face_of[v][j]);
+				dual.add_weighted_indexed_directed_edge(f, g, edge_index, ());
+				dual.add_weighted_indexed_directed_edge(g, f, edge_index, ());
+				dual.total_edges += 1;
+			}
+		}
+		(dual, faces)
+	}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Interval {
+	low: Option<usize>,
+	high: Option<usize>,
+}
+
+impl Interval {
+	fn is_empty(&self) -> bool {
+		self.low.is_none() && self.high.is_none()
+	}
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ConflictPair {
+	left: Interval,
+	right: Interval,
+}
+
+impl ConflictPair {
+	fn swap(&mut self) {
+		std::mem::swap(&mut self.left, &mut self.right);
+	}
+}
+
+/// Left–right planarity test (de Fraysseix–Rosenstiehl as described by Brandes) on a simple graph.
+/// Edges are numbered `0..from.len()` and oriented by the DFS
+struct LeftRight<'a> {
+	adjacency: &'a [Vec<(usize, usize)>],
+	from: Vec<usize>,
+	to: Vec<usize>,
+	oriented: Vec<bool>,
+	height: Vec<Option<usize>>,
+	parent_edge: Vec<Option<usize>>,
+	lowpt: Vec<usize>,
+	lowpt2: Vec<usize>,
+	nesting_depth: Vec<i64>,
+	/// Outgoing edges of the orientation, sorted by the nesting depth
+	ordered: Vec<Vec<usize>>,
+	roots: Vec<usize>,
+
+	reference: Vec<Option<usize>>,
+	side: Vec<i64>,
+	stack: Vec<ConflictPair>,
+	stack_bottom: Vec<usize>,
+	lowpt_edge: Vec<usize>,
+
+	/// `neighbour ↦ (clockwise next, counterclockwise next)`
+	rotation: Vec<HashMap<usize, (usize, usize)>>,
+	first: Vec<Option<usize>>,
+	left_ref: Vec<usize>,
+	right_ref: Vec<usize>,
+}
+
+impl<'a> LeftRight<'a> {
+	fn new(adjacency: &'a [Vec<(usize, usize)>], edges: usize) -> Self {
+		let n = adjacency.len();
+		LeftRight {
+			adjacency,
+			from: vec![0; edges],
+			to: vec![0; edges],
+			oriented: vec![false; edges],
+			height: vec![None; n],
+			parent_edge: vec![None; n],
+			lowpt: vec![0; edges],
+			lowpt2: vec![0; edges],
+			nesting_depth: vec![0; edges],
+			ordered: vec![Vec::new(); n],
+			roots: Vec::new(),
+			reference: vec![None; edges],
+			side: vec![1; edges],
+			stack: Vec::new(),
+			stack_bottom: vec![0; edges],
+			lowpt_edge: vec![0; edges],
+			rotation: vec![HashMap::new(); n],
+			first: vec![None; n],
+			left_ref: vec![0; n],
+			right_ref: vec![0; n],
+		}
+	}
+
+	/// Neighbours of every vertex in the clockwise order, `None` if the graph is not planar
+	fn run(mut self) -> Option<Vec<Vec<usize>>> {
+		let n = self.adjacency.len();
+		if n > 2 && self.from.len() > 3 * n - 6 {
+			return None;
+		}
+		for v in 0..n {
+			if self.height[v].is_none() {
+				self.height[v] = Some(0);
+				self.roots.push(v);
+				self.orient(v);
+			}
+		}
+		self.sort_by_nesting_depth();
+		for root in self.roots.clone() {
+			if !self.test(root) {
+				return None;
+			}
+		}
+
+		for e in 0..self.from.len() {
+			self.nesting_depth[e] *= self.sign(e);
+		}
+		self.sort_by_nesting_depth();
+		for v in 0..n {
+			let mut previous = None;
+			for i in 0..self.ordered[v].len() {
+				let w = self.to[self.ordered[v][i]];
+				self.add_clockwise(v, w, previous);
+				previous = Some(w);
+			}
+		}
+		for root in self.roots.clone() {
+			self.embed(root);
+		}
+
+		Some((0..n)
+			.map(|v| {
+				let mut around = Vec::with_capacity(self.rotation[v].len());
+				if let Some(first) = self.first[v] {
+					let mut w = first;
+					loop {
+						around.push(w);
+						w = self.rotation[v][&w].0;
+						if w == first {
+							break;
+						}
+					}
+				}
+				around
+			})
+			.collect())
+	}
+
+	fn sort_by_nesting_depth(&mut self) {
+		for v in 0..self.ordered.len() {
+			let mut ordered = std::mem::take(&mut self.ordered[v]);
+			ordered.sort_by_key(|&e| self.nesting_depth[e]);
+			self.ordered[v] = ordered;
+		}
+	}
+
+	/// DFS orientation with lowpoints and nesting depths
+	fn orient(&mut self, v: usize) {
+		let height_v = self.height[v].unwrap();
+		let parent = self.parent_edge[v];
+		let adjacency = self.adjacency;
+		for &(w, e) in &adjacency[v] {
+			if self.oriented[e] {
+				continue;
+			}
+			self.oriented[e] = true;
+			(self.from[e], self.to[e]) = (v, w);
+			self.ordered[v].push(e);
+			self.lowpt[e] = height_v;
+			self.lowpt2[e] = height_v;
+			match self.height[w] {
+				None => {
+					self.parent_edge[w] = Some(e);
+					self.height[w] = Some(height_v + 1);
+					self.orient(w);
+				}
+				Some(height_w) => self.lowpt[e] = height_w,
+			}
+
+			self.nesting_depth[e] = 2 * self.lowpt[e] as i64;
+			if self.lowpt2[e] < height_v {
+				// Chordal edge
+				self.nesting_depth[e] += 1;
+			}
+			if let Some(p) = parent {
+				if self.lowpt[e] < self.lowpt[p] {
+					self.lowpt2[p] = self.lowpt[p].min(self.lowpt2[e]);
+					self.lowpt[p] = self.lowpt[e];
+				} else if self.lowpt[e] > self.lowpt[p] {
+					self.lowpt2[p] = self.lowpt2[p].min(self.lowpt[e]);
+				} else {
+					self.lowpt2[p] = self.lowpt2[p].min(self.lowpt2[e]);
+				}
+			}
+		}
+	}
+
+	fn conflicting(&self, interval: Interval, edge: usize) -> bool {
+		interval.high.is_some_and(|high| self.lowpt[high] > self.lowpt[edge])
+	}
+
+	fn lowest(&self, pair: &ConflictPair) -> usize {
+		match (pair.left.low, pair.right.low) {
+			(None, Some(right)) => self.lowpt[right],
+			(Some(left), None) => self.lowpt[left],
+			(Some(left), Some(right)) => self.lowpt[left].min(self.lowpt[right]),
+			(None, None) => unreachable!("Conflict pairs on the stack aren't empty"),
+		}
+	}
+
+	fn test(&mut self, v: usize) -> bool {
+		let parent = self.parent_edge[v];
+		let height_v = self.height[v].unwrap();
+		for i in 0..self.ordered[v].len() {
+			let e = self.ordered[v][i];
+			let w = self.to[e];
+			self.stack_bottom[e] = self.stack.len();
+			if self.parent_edge[w] == Some(e) {
+				if !self.test(w) {
+					return false;
+				}
+			} else {
+				self.lowpt_edge[e] = e;
+				self.stack.push(ConflictPair { left: Interval::default(), right: Interval { low: Some(e), high: Some(e) } });
+			}
+			// Integrate the return edges of `e`
+			if self.lowpt[e] < height_v {
+				let parent = parent.unwrap();
+				if i == 0 {
+					self.lowpt_edge[parent] = self.lowpt_edge[e];
+				} else if !self.add_constraints(e, parent) {
+					return false;
+				}
+			}
+		}
+		if let Some(parent) = parent {
+			self.remove_back_edges(parent);
+		}
+		true
+	}
+
+	fn add_constraints(&mut self, e: usize, parent: usize) -> bool {
+		let mut pair = ConflictPair::default();
+		// Return edges of `e` go to the right
+		loop {
+			let mut q = self.stack.pop().unwrap();
+			if !q.left.is_empty() {
+				q.swap();
+			}
+			if !q.left.is_empty() {
+				return false;
+			}
+			let q_low = q.right.low.unwrap();
+			if self.lowpt[q_low] > self.lowpt[parent] {
+				match pair.right.low {
+					None => pair.right = q.right,
+					Some(low) => self.reference[low] = q.right.high,
+				}
+				pair.right.low = q.right.low;
+			} else {
+				self.reference[q_low] = Some(self.lowpt_edge[parent]);
+			}
+			if self.stack.len() <= self.stack_bottom[e] {
+				break;
+			}
+		}
+		// Conflicting return edges of the previous siblings go to the left
+		while let Some(&top) = self.stack.last() {
+			if !self.conflicting(top.left, e) && !self.conflicting(top.right, e) {
+				break;
+			}
+			let mut q = self.stack.pop().unwrap();
+			if self.conflicting(q.right, e) {
+				q.swap();
+			}
+			if self.conflicting(q.right, e) {
+				return false;
+			}
+			if let Some(low) = pair.right.low {
+				self.reference[low] = q.right.high;
+			}
+			if q.right.low.is_some() {
+				pair.right.low = q.right.low;
+			}
+			match pair.left.low {
+				None => pair.left = q.left,
+				Some(low) => self.reference[low] = q.left.high,
+			}
+			pair.left.low = q.left.low;
+		}
+		if !pair.left.is_empty() || !pair.right.is_empty() {
+			self.stack.push(pair);
+		}
+		true
+	}
+
+	fn remove_back_edges(&mut self, e: usize) {
+		let u = self.from[e];
+		let height_u = self.height[u].unwrap();
+		// Conflict pairs whose back edges all end at `u` are dropped
+		while let Some(top) = self.stack.last() {
+			if self.lowest(top) != height_u {
+				break;
+			}
+			let pair = self.stack.pop().unwrap();
+			if let Some(low) = pair.left.low {
+				self.side[low] = -1;
+			}
+		}
+		if let Some(mut pair) = self.stack.pop() {
+			// Trim the intervals of the next one
+			while let Some(high) = pair.left.high.filter(|&high| self.to[high] == u) {
+				pair.left.high = self.reference[high];
+			}
+			if let (None, Some(low)) = (pair.left.high, pair.left.low) {
+				self.reference[low] = pair.right.low;
+				self.side[low] = -1;
+				pair.left.low = None;
+			}
+			while let Some(high) = pair.right.high.filter(|&high| self.to[high] == u) {
+				pair.right.high = self.reference[high];
+			}
+			if let (None, Some(low)) = (pair.right.high, pair.right.low) {
+				self.reference[low] = pair.left.low;
+				self.side[low] = -1;
+				pair.right.low = None;
+			}
+			self.stack.push(pair);
+		}
+		// The side of `e` is the side of its highest return edge
+		if self.lowpt[e] < height_u {
+			let top = *self.stack.last().unwrap();
+			let (left_high, right_high) = (top.left.high, top.right.high);
+			self.reference[e] = match (left_high, right_high) {
+				(Some(left), Some(right)) if self.lowpt[left] > self.lowpt[right] => Some(left),
+				(Some(left), None) => Some(left),
+				_ => right_high,
+			};
+		}
+	}
+
+	fn sign(&mut self, e: usize) -> i64 {
+		if let Some(reference) = self.reference[e].take() {
+			self.side[e] *= self.sign(reference);
+		}
+		self.side[e]
+	}
+
+	/// Inserts `w` into the rotation of `v` clockwise after `reference` (which is `None` for the first neighbour)
+	fn add_clockwise(&mut self, v: usize, w: usize, reference: Option<usize>) {
+		match reference {
+			None => {
+				self.rotation[v].insert(w, (w, w));
+				self.first[v] = Some(w);
+			}
+			Some(reference) => {
+				let next = self.rotation[v][&reference].0;
+				self.rotation[v].insert(w, (next, reference));
+				self.rotation[v].get_mut(&reference).unwrap().0 = w;
+				self.rotation[v].get_mut(&next).unwrap().1 = w;
+			}
+		}
+	}
+
+	fn add_counterclockwise(&mut self, v: usize, w: usize, reference: usize) {
+		let previous = self.rotation[v][&reference].1;
+		self.add_clockwise(v, w, Some(previous));
+		if self.first[v] == Some(reference) {
+			self.first[v] = Some(w);
+		}
+	}
+
+	fn embed(&mut self, v: usize) {
+		for i in 0..self.ordered[v].len() {
+			let e = self.ordered[v][i];
+			let w = self.to[e];
+			if self.parent_edge[w] == Some(e) {
+				match self.first[w] {
+					Some(first) => self.add_counterclockwise(w, v, first),
+					None => self.add_clockwise(w, v, None),
+				}
+				self.left_ref[v] = w;
+				self.right_ref[v] = w;
+				self.embed(w);
+			} else if self.side[e] == 1 {
+				self.add_clockwise(w, v, Some(self.right_ref[w]));
+			} else {
+				self.add_counterclockwise(w, v, self.left_ref[w]);
+				self.left_ref[w] = v;
+			}
+		}
+	}
+}
+
+/// Underlying simple graph of the kept edges: adjacency with simple edge numbers
+/// and the representative original edge of every simple edge
+fn simple_graph(n: usize, ends: &[(usize, usize)], keep: impl Fn(usize) -> bool) -> (Vec<Vec<(usize, usize)>>, Vec<usize>) {
+	let mut adjacency = vec![Vec::new(); n];
+	let mut representative = Vec::new();
+	let mut seen = HashMap::new();
+	for (index, &(u, v)) in ends.iter().enumerate() {
+		if u == v || !keep(index) || seen.contains_key(&(u.min(v), u.max(v))) {
+			continue;
+		}
+		seen.insert((u.min(v), u.max(v)), representative.len());
+		adjacency[u].push((v, representative.len()));
+		adjacency[v].push((u, representative.len()));
+		representative.push(index);
+	}
+	(adjacency, representative)
+}
+
+fn is_simple_planar(adjacency: &[Vec<(usize, usize)>], edges: usize) -> bool {
+	LeftRight::new(adjacency, edges).run().is_some()
+}
+
+fn edge_ends<G: GraphView>(graph: &G) -> Vec<(usize, usize)> {
+	let mut ends = vec![(0, 0); graph.edge_index_bound()];
+	for v in 0..graph.vertexes() {
+		for edge in graph.neighbours(v) {
+			ends[edge.edge_index] = (v, edge.to);
+		}
+	}
+	ends
+}
+
+pub fn is_planar<G: GraphView>(graph: &G) -> bool {
+	let (adjacency, representative) = simple_graph(graph.vertexes(), &edge_ends(graph), |_| true);
+	is_simple_planar(&adjacency, representative.len())
+}
+
+/// Planarity test of an undirected multigraph in O(V + E) by the left–right criterion.
+/// Gives a rotation system for a planar graph. For a non-planar one the shortest non-planar prefix of the
+/// edges is found by binary search, then its edges are dropped one by one while the rest stays non-planar:
+/// what remains is a Kuratowski subgraph. O(V² + E log E) in total, as the prefix has at most 3V - 5 edges
+pub fn planarity<G: GraphView>(graph: &G) -> Planarity {
+	let n = graph.vertexes();
+	let ends = edge_ends(graph);
+	let (adjacency, representative) = simple_graph(n, &ends, |_| true);
+	match LeftRight::new(&adjacency, representative.len()).run() {
+		Some(rotation) => Planarity::Planar(expand_rotation(graph, &rotation)),
+		None => Planarity::NonPlanar(kuratowski_subgraph(n, &ends, &representative)),
+	}
+}
+
+/// Puts the parallel edges and loops of the multigraph into the rotation of its simple graph.
+/// Parallel edges are listed in the opposite orders at their two ends, so that they nest
+fn expand_rotation<G: GraphView>(graph: &G, rotation: &[Vec<usize>]) -> PlanarEmbedding {
+	let rotation = (0..graph.vertexes())
+		.map(|v| {
+			let mut between: HashMap<usize, Vec<usize>> = HashMap::new();
+			for edge in graph.neighbours(v) {
+				between.entry(edge.to).or_default().push(edge.edge_index);
+			}
+			let mut around = Vec::new();
+			if let Some(loops) = between.get_mut(&v) {
+				loops.sort();
+				loops.dedup();
+				around.extend(loops.iter().flat_map(|&e| [Edge::new(v, e), Edge::new(v, e)]));
+			}
+			for &w in &rotation[v] {
+				let mut parallel = between.remove(&w).unwrap_or_default();
+				parallel.sort();
+				if v > w {
+					parallel.reverse();
+				}
+				around.extend(parallel.into_iter().map(|e| Edge::new(w, e)));
+			}
+			around
+		})
+		.collect();
+	PlanarEmbedding { rotation }
+}
+
+/// Whether the simple graph of `edges` (distinct, without loops) is planar
+fn is_planar_subgraph(n: usize, ends: &[(usize, usize)], edges: &[usize]) -> bool {
+	let mut adjacency = vec![Vec::new(); n];
+	for (simple, &e) in edges.iter().enumerate() {
+		let (u, v) = ends[e];
+		adjacency[u].push((v, simple));
+		adjacency[v].push((u, simple));
+	}
+	is_simple_planar(&adjacency, edges.len())
+}
+
+fn kuratowski_subgraph(n: usize, ends: &[(usize, usize)], representative: &[usize]) -> KuratowskiSubgraph {
+	// Shortest non-planar prefix: everything shorter is planar, so it has at most 3n - 5 edges
+	let (mut planar, mut non_planar) = (0, representative.len());
+	while non_planar - planar > 1 {
+		let middle = (planar + non_planar) / 2;
+		if is_planar_subgraph(n, ends, &representative[..middle]) {
+			planar = middle;
+		} else {
+			non_planar = middle;
+		}
+	}
+	let mut edges = representative[..non_planar].to_vec();
+	// The last edge of the prefix is in every non-planar subgraph of it, the others are tried one by one
+	let mut i = 0;
+	while i + 1 < edges.len() {
+		let e = edges.remove(i);
+		if is_planar_subgraph(n, ends, &edges) {
+			edges.insert(i, e);
+			i += 1;
+		}
+	}
+	edges.sort();
+	let mut degree = vec![0; n];
+	for &e in &edges {
+		degree[ends[e].0] += 1;
+		degree[ends[e].1] += 1;
+	}
+	// Branch vertexes of K₅ have degree 4, those of K₃,₃ have degree 3
+	let kind = if degree.contains(&4) { KuratowskiKind::K5 } else { KuratowskiKind::K33 };
+	KuratowskiSubgraph { kind, edges }
+}
@@ -0,0 +1,277 @@
+use std::fmt::{Display, Formatter};
+
+/// Polynomial with integer coefficients, `coefficients[i]` is the coefficient of `x^i`.
+/// Trailing zeros are stripped, so the zero polynomial has no coefficients
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Polynomial {
+	pub coefficients: Vec<i64>,
+}
+
+impl Polynomial {
+	pub fn new(coefficients: Vec<i64>) -> Self {
+		let mut res = Self {
+			coefficients,
+		};
+		res.strip();
+		res
+	}
+
+	fn strip(&mut self) {
+		while self.coefficients.last() == Some(&0) {
+			self.coefficients.pop();
+		}
+	}
+
+	pub fn zero() -> Self {
+		Self::new(Vec::new())
+	}
+
+	pub fn one() -> Self {
+		Self::new(vec![1])
+	}
+
+	pub fn monomial(degree: usize, coefficient: i64) -> Self {
+		let mut coefficients = vec![0; degree + 1];
+		coefficients[degree] = coefficient;
+		Self::new(coefficients)
+	}
+
+	/// `x - root`
+	pub fn linear(root: i64) -> Self {
+		Self::new(vec![-root, 1])
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.coefficients.is_empty()
+	}
+
+	/// Degree of the polynomial (0 for the zero polynomial)
+	pub fn degree(&self) -> usize {
+		self.coefficients.len().saturating_sub(1)
+	}
+
+	pub fn evaluate(&self, x: i64) -> i64 {
+		self.coefficients.iter().rev().fold(0, |acc, &c| acc * x + c)
+	}
+
+	pub fn pow(&self, exponent: usize) -> Self {
+		(0..exponent).fold(Self::one(), |acc, _| acc * self.clone())
+	}
+
+	/// Exact division by a polynomial with leading coefficient ±1
+	pub fn div_exact(&self, divisor: &Self) -> Self {
+		let leading = *divisor.coefficients.last().expect("Division by zero polynomial");
+		assert!(leading == 1 || leading == -1, "Divisor should be monic up to sign");
+		if self.coefficients.len() < divisor.coefficients.len() {
+			assert!(self.is_zero(), "Division is not exact");
+			return Self::zero();
+		}
+		let mut remainder = self.coefficients.clone();
+		let shift = remainder.len() - divisor.coefficients.len();
+		let mut quotient = vec![0; shift + 1];
+		for i in (0..=shift).rev() {
+			let factor = remainder[i + divisor.coefficients.len() - 1] * leading;
+			quotient[i] = factor;
+			for (j, &d) in divisor.coefficients.iter().enumerate() {
+				remainder[i + j] -= factor * d;
+			}
+		}
+		debug_assert!(remainder.iter().all(|&r| r == 0), "Division is not exact");
+		Self::new(quotient)
+	}
+}
+
+impl Display for Polynomial {
+	/// Coefficients from the highest degree to the lowest
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
+		write!(f, "{}", self.coefficients.iter()
+			.rev()
+			.map(|c| c.to_string())
+			.collect::<Vec<_>>()
+			.join(" "))
+	}
+}
+
+/// Operations with polynomials (overloaded operators)
+impl std::ops::Add for Polynomial {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		let mut result = self;
+		let mut other = other;
+		if result.coefficients.len() < other.coefficients.len() {
+			std::mem::swap(&mut result, &mut other);
+		}
+		for i in 0..other.coefficients.len() {
+			result.coefficients[i] += other.coefficients[i];
+		}
+		result.strip();
+		result
+	}
+}
+
+impl std::ops::AddAssign for Polynomial {
+	fn add_assign(&mut self, other: Self) {
+		*self = self.clone() + other;
+	}
+}
+
+impl std::ops::Neg for Polynomial {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Self::new(self.coefficients.into_iter().map(|c| -c).collect())
+	}
+}
+
+impl std::ops::Sub for Polynomial {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		self + (-other)
+	}
+}
+
+impl std::ops::SubAssign for Polynomial {
+	fn sub_assign(&mut self, other: Self) {
+		*self = self.clone() - other;
+	}
+}
+
+impl std::ops::Mul for Polynomial {
+	type Output = Self;
+
+	fn mul(self, other: Self) -> Self {
+		if self.is_zero() || other.is_zero() {
+			return Self::zero();
+		}
+		let mut result = vec![0_i64; self.coefficients.len() + other.coefficients.len() - 1];
+		for (i, &a) in self.coefficients.iter().enumerate() {
+			for (j, &b) in other.coefficients.iter().enumerate() {
+				result[i + j] += a * b;
+			}
+		}
+		Self::new(result)
+	}
+}
+
+impl std::ops::MulAssign for Polynomial {
+	fn mul_assign(&mut self, other: Self) {
+		*self = self.clone() * other;
+	}
+}
+
+/// Polynomial in two variables, `coefficients[i][j]` is the coefficient of `x^i y^j`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BivariatePolynomial {
+	pub coefficients: Vec<Vec<i64>>,
+}
+
+impl BivariatePolynomial {
+	pub fn new(coefficients: Vec<Vec<i64>>) -> Self {
+		let mut res = Self {
+			coefficients,
+		};
+		res.strip();
+		res
+	}
+
+	fn strip(&mut self) {
+		for row in &mut self.coefficients {
+			while row.last() == Some(&0) {
+				row.pop();
+			}
+		}
+		while self.coefficients.last().is_some_and(|row| row.is_empty()) {
+			self.coefficients.pop();
+		}
+	}
+
+	pub fn zero() -> Self {
+		Self::new(Vec::new())
+	}
+
+	pub fn one() -> Self {
+		Self::monomial(0, 0, 1)
+	}
+
+	pub fn monomial(x_degree: usize, y_degree: usize, coefficient: i64) -> Self {
+		let mut coefficients = vec![Vec::new(); x_degree + 1];
+		coefficients[x_degree] = vec![0; y_degree + 1];
+		coefficients[x_degree][y_degree] = coefficient;
+		Self::new(coefficients)
+	}
+
+	pub fn coefficient(&self, x_degree: usize, y_degree: usize) -> i64 {
+		self.coefficients.get(x_degree).and_then(|row| row.get(y_degree)).cloned().unwrap_or(0)
+	}
+
+	pub fn evaluate(&self, x: i64, y: i64) -> i64 {
+		self.coefficients.iter()
+			.rev()
+			.fold(0, |acc, row| acc * x + row.iter().rev().fold(0, |acc, &c| acc * y + c))
+	}
+
+	/// Substitutes polynomials in one variable for `x` and `y`
+	pub fn substitute(&self, x: &Polynomial, y: &Polynomial) -> Polynomial {
+		self.coefficients.iter()
+			.rev()
+			.fold(Polynomial::zero(), |acc, row| {
+				let row_value = row.iter()
+					.rev()
+					.fold(Polynomial::zero(), |acc, &c| acc * y.clone() + Polynomial::monomial(0, c));
+				acc * x.clone() + row_value
+			})
+	}
+}
+
+impl std::ops::Add for BivariatePolynomial {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		let mut result = self.coefficients;
+		if result.len() < other.coefficients.len() {
+			result.resize(other.coefficients.len(), Vec::new());
+		}
+		for (i, row) in other.coefficients.into_iter().enumerate() {
+			if result[i].len() < row.len() {
+				result[i].resize(row.len(), 0);
+			}
+			for (j, c) in row.into_iter().enumerate() {
+				result[i][j] += c;
+			}
+		}
+		Self::new(result)
+	}
+}
+
+impl std::ops::Mul for BivariatePolynomial {
+	type Output = Self;
+
+	fn mul(self, other: Self) -> Self {
+		if self.coefficients.is_empty() || other.coefficients.is_empty() {
+			return Self::zero();
+		}
+		let mut result = vec![Vec::new(); self.coefficients.len() + other.coefficients.len() - 1];
+		for (i1, row1) in self.coefficients.iter().enumerate() {
+			for (i2, row2) in other.coefficients.iter().enumerate() {
+				if row1.is_empty() || row2.is_empty() {
+					continue;
+				}
+				let row = &mut result[i1 + i2];
+				if row.len() < row1.len() + row2.len() - 1 {
+					row.resize(row1.len() + row2.len() - 1, 0);
+				}
+				for (j1, &a) in row1.iter().enumerate() {
+					for (j2, &b) in row2.iter().enumerate() {
+						row[j1 + j2] += a * b;
+					}
+				}
+			}
+		}
+		Self::new(result)
+	}
+}
@@ -7,7 +7,7 @@ pub struct OutputWriter<W: Write> {
 }
 
 impl OutputWriter<Stdout> {
-	pub fn new() -> Self { Self::from_writer(io::stdout()) }
+	pub fn new() -> Self { Self::from_writer(std::io::stdout()) }
 }
 
 impl OutputWriter<File> {
@@ -76,7 +76,7 @@ pub struct InputReader<R: Read> {
 
 impl InputReader<Stdin> {
 	pub fn new() -> Self {
-		Self::from_reader(io::stdin())
+		Self::from_reader(std::io::stdin())
 	}
 }
 
@@ -0,0 +1,85 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use crate::GraphView;
+
+/// Shortest path tree from `source`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<D> {
+	pub source: usize,
+	/// `None` for unreachable vertexes
+	pub distance: Vec<Option<D>>,
+	/// `(parent, edge index)` of every reached vertex except the source
+	pub parent: Vec<Option<(usize, usize)>>,
+}
+
+impl<D> ShortestPaths<D> {
+	/// Edge indexes of the path from the source
+	pub fn edges_to(&self, target: usize) -> Option<Vec<usize>> {
+		self.distance[target].as_ref()?;
+		let mut edges = Vec::new();
+		let mut v = target;
+		while let Some((parent, edge_index)) = self.parent[v] {
+			edges.push(edge_index);
+			v = parent;
+		}
+		edges.reverse();
+		Some(edges)
+	}
+
+	/// Vertexes of the path from the source (both ends included)
+	pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+		self.distance[target].as_ref()?;
+		let mut path = vec![target];
+		let mut v = target;
+		while let Some((parent, _)) = self.parent[v] {
+			path.push(parent);
+			v = parent;
+		}
+		path.reverse();
+		Some(path)
+	}
+}
+
+/// Distances in edges
+pub fn bfs<G: GraphView>(graph: &G, source: usize) -> ShortestPaths<usize> {
+	let mut distance = vec![None; graph.vertexes()];
+	let mut parent = vec![None; graph.vertexes()];
+	let mut queue = VecDeque::from([source]);
+	distance[source] = Some(0);
+	while let Some(v) = queue.pop_front() {
+		let next = distance[v].unwrap() + 1;
+		for edge in graph.neighbours(v) {
+			if distance[edge.to].is_none() {
+				distance[edge.to] = Some(next);
+				parent[edge.to] = Some((v, edge.edge_index));
+				queue.push_back(edge.to);
+			}
+		}
+	}
+	ShortestPaths { source, distance, parent }
+}
+
+/// Dijkstra with a binary heap, weights should be non-negative
+pub fn dijkstra<G>(graph: &G, source: usize) -> ShortestPaths<i64>
+	where G: GraphView, G::Weight: Into<i64>
+{
+	let mut distance: Vec<Option<i64>> = vec![None; graph.vertexes()];
+	let mut parent = vec![None; graph.vertexes()];
+	let mut heap = BinaryHeap::from([Reverse((0, source))]);
+	distance[source] = Some(0);
+	while let Some(Reverse((d, v))) = heap.pop() {
+		if distance[v] != Some(d) {
+			continue;
+		}
+		for edge in graph.neighbours(v) {
+			let weight: i64 = edge.weight.into();
+			debug_assert!(weight >= 0, "Dijkstra requires non-negative weights");
+			if distance[edge.to].is_none_or(|old| d + weight < old) {
+				distance[edge.to] = Some(d + weight);
+				parent[edge.to] = Some((v, edge.edge_index));
+				heap.push(Reverse((d + weight, edge.to)));
+			}
+		}
+	}
+	ShortestPaths { source, distance, parent }
+}
@@ -0,0 +1,169 @@
+use crate::{BigUint, DFSSpace, Graph, Rng};
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+	(a as u128 * b as u128 % modulus as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+	let mut result = 1 % modulus;
+	while exponent > 0 {
+		if exponent & 1 == 1 {
+			result = mul_mod(result, base, modulus);
+		}
+		base = mul_mod(base, base, modulus);
+		exponent >>= 1;
+	}
+	result
+}
+
+/// Deterministic Miller–Rabin, these bases are enough for every 64-bit number
+fn is_prime(n: u64) -> bool {
+	const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+	if n < 2 {
+		return false;
+	}
+	if let Some(&p) = BASES.iter().find(|&&p| n.is_multiple_of(p)) {
+		return n == p;
+	}
+	let shift = (n - 1).trailing_zeros();
+	let odd = (n - 1) >> shift;
+	BASES.iter().all(|&base| {
+		let mut x = pow_mod(base, odd, n);
+		if x == 1 || x == n - 1 {
+			return true;
+		}
+		(1..shift).any(|_| {
+			x = mul_mod(x, x, n);
+			x == n - 1
+		})
+	})
+}
+
+/// Reduced Laplacian: the Laplacian without the row and the column of the last vertex, modulo `modulus`.
+/// Loops don't change it, parallel edges count separately
+fn reduced_laplacian(graph: &Graph, modulus: u64) -> Vec<Vec<u64>> {
+	let size = graph.vertexes() - 1;
+	let mut matrix = vec![vec![0; size]; size];
+	for (from, to) in graph.edge_ends().into_iter().filter(|(from, to)| from != to) {
+		for (a, b) in [(from, to), (to, from)] {
+			if a < size {
+				matrix[a][a] = (matrix[a][a] + 1) % modulus;
+				if b < size {
+					matrix[a][b] = (matrix[a][b] + modulus - 1) % modulus;
+				}
+			}
+		}
+	}
+	matrix
+}
+
+/// Gaussian elimination over the field of residues. O(n³)
+fn determinant_mod(mut matrix: Vec<Vec<u64>>, modulus: u64) -> u64 {
+	let n = matrix.len();
+	let mut determinant = 1 % modulus;
+	for column in 0..n {
+		let Some(pivot) = (column..n).find(|&row| matrix[row][column] != 0) else {
+			return 0;
+		};
+		if pivot != column {
+			matrix.swap(pivot, column);
+			determinant = (modulus - determinant) % modulus;
+		}
+		determinant = mul_mod(determinant, matrix[column][column], modulus);
+		let inverse = pow_mod(matrix[column][column], modulus - 2, modulus);
+		let (upper, lower) = matrix.split_at_mut(column + 1);
+		let pivot_row = &upper[column];
+		for row in lower {
+			let factor = mul_mod(row[column], inverse, modulus);
+			if factor == 0 {
+				continue;
+			}
+			for (x, &y) in row.iter_mut().zip(pivot_row).skip(column) {
+				*x = (*x + modulus - mul_mod(factor, y, modulus)) % modulus;
+			}
+		}
+	}
+	determinant
+}
+
+/// Number of spanning trees of an undirected multigraph modulo a prime, by the matrix-tree theorem
+/// (parallel edges give different trees, loops are never in a tree). O(n³)
+pub fn spanning_tree_count_mod(graph: &Graph, modulus: u64) -> u64 {
+	debug_assert!(is_prime(modulus), "Modulus should be prime");
+	if graph.vertexes() <= 1 {
+		return 1 % modulus;
+	}
+	determinant_mod(reduced_laplacian(graph, modulus), modulus)
+}
+
+/// Exact number of spanning trees of an undirected multigraph: the determinant modulo enough
+/// primes just below 2⁶² to exceed `∏ deg(v)` over all vertexes but one (every vertex but the root
+/// picks an edge towards the root), put together by the Chinese remainder theorem. O(n³ log τ)
+pub fn spanning_tree_count(graph: &Graph) -> BigUint {
+	let n = graph.vertexes();
+	if n <= 1 {
+		return BigUint::from(1);
+	}
+	let mut degree = vec![0_u64; n];
+	for (from, to) in graph.edge_ends().into_iter().filter(|(from, to)| from != to) {
+		degree[from] += 1;
+		degree[to] += 1;
+	}
+	let bits = degree[..n - 1].iter().map(|&d| (d.max(1) as f64).log2()).sum::<f64>();
+	let count = (bits / 61.0) as usize + 2;
+
+	let primes = (0..1_u64 << 62).rev().filter(|&p| is_prime(p)).take(count).collect::<Vec<_>>();
+	let residues = primes.iter().map(|&p| spanning_tree_count_mod(graph, p)).collect::<Vec<_>>();
+
+	// Garner: x = c₀ + c₁ p₀ + c₂ p₀ p₁ + …
+	let mut digits: Vec<u64> = Vec::with_capacity(count);
+	for (i, (&p, &residue)) in primes.iter().zip(&residues).enumerate() {
+		let (mut value, mut radix) = (0, 1);
+		for (&digit, &q) in digits.iter().zip(&primes[..i]) {
+			value = (value + mul_mod(digit % p, radix, p)) % p;
+			radix = mul_mod(radix, q % p, p);
+		}
+		digits.push(mul_mod((residue + p - value) % p, pow_mod(radix, p - 2, p), p));
+	}
+	let mut result = BigUint::zero();
+	for (&digit, &p) in digits.iter().zip(&primes).rev() {
+		result.mul_u64(p);
+		result.add_u64(digit);
+	}
+	result
+}
+
+/// Uniformly random spanning tree of a connected undirected multigraph by Wilson's algorithm:
+/// loop-erased random walks from every vertex to the tree grown so far. Returns the edge indexes,
+/// `None` if the graph isn't connected. Expected time is the mean hitting time
+pub fn random_spanning_tree(graph: &Graph, rng: &mut Rng) -> Option<Vec<usize>> {
+	let n = graph.vertexes();
+	if n == 0 {
+		return Some(Vec::new());
+	}
+	let connected = DFSSpace::new(graph).find_connected_components(graph).component_list.len() == 1;
+	if !connected {
+		return None;
+	}
+	let mut in_tree = vec![false; n];
+	// Last edge the walk left the vertex by, `(to, edge_index)`: following it erases the loops
+	let mut next = vec![(0, 0); n];
+	in_tree[rng.below(n)] = true;
+	let mut tree = Vec::with_capacity(n - 1);
+	for start in 0..n {
+		let mut v = start;
+		while !in_tree[v] {
+			let edge = graph.edges[v][rng.below(graph.edges[v].len())];
+			next[v] = (edge.to, edge.edge_index);
+			v = edge.to;
+		}
+		let mut v = start;
+		while !in_tree[v] {
+			in_tree[v] = true;
+			tree.push(next[v].1);
+			v = next[v].0;
+		}
+	}
+	tree.sort();
+	Some(tree)
+}
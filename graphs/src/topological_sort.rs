@@ -0,0 +1,130 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use crate::{CsrGraph, Cycle, GraphView};
+
+fn in_degrees<G: GraphView>(graph: &G) -> Vec<usize> {
+	let mut in_degree = vec![0; graph.vertexes()];
+	for v in 0..graph.vertexes() {
+		for edge in graph.neighbours(v) {
+			in_degree[edge.to] += 1;
+		}
+	}
+	in_degree
+}
+
+/// Cycle among the vertexes left by Kahn's algorithm: each of them has an incoming edge from another one,
+/// so walking backwards along such edges has to come back
+fn cycle_among<G: GraphView>(graph: &G, left: &[bool]) -> Cycle {
+	let incoming = CsrGraph::reversed(graph);
+	let start = left.iter().position(|&l| l).unwrap();
+	let mut position = vec![usize::MAX; graph.vertexes()];
+	let (mut vertexes, mut edges) = (Vec::new(), Vec::new());
+	let mut v = start;
+	while position[v] == usize::MAX {
+		position[v] = vertexes.len();
+		vertexes.push(v);
+		let edge = incoming.edges_of(v).iter().find(|edge| left[edge.to]).unwrap();
+		edges.push(edge.edge_index);
+		v = edge.to;
+	}
+	// The walk goes against the edges: `edges[i]` leads from `v` to `vertexes[i]`
+	let mut vertexes = vertexes.split_off(position[v]);
+	let mut edges = edges.split_off(position[v]);
+	vertexes.reverse();
+	edges.reverse();
+	vertexes.rotate_right(1);
+	Cycle { vertexes, edges }
+}
+
+/// Kahn's algorithm taking the available vertex with the smallest key every time,
+/// or a cycle if there is no topological order. O((n + m) log n)
+pub fn topological_sort_by_key<G, K, F>(graph: &G, key: F) -> Result<Vec<usize>, Cycle>
+	where G: GraphView, K: Ord, F: Fn(usize) -> K
+{
+	let n = graph.vertexes();
+	let mut in_degree = in_degrees(graph);
+	let mut available = (0..n)
+		.filter(|&v| in_degree[v] == 0)
+		.map(|v| Reverse((key(v), v)))
+		.collect::<BinaryHeap<_>>();
+	let mut order = Vec::with_capacity(n);
+	while let Some(Reverse((_, v))) = available.pop() {
+		order.push(v);
+		for edge in graph.neighbours(v) {
+			in_degree[edge.to] -= 1;
+			if in_degree[edge.to] == 0 {
+				available.push(Reverse((key(edge.to), edge.to)));
+			}
+		}
+	}
+	if order.len() < n {
+		let left = in_degree.iter().map(|&d| d > 0).collect::<Vec<_>>();
+		return Err(cycle_among(graph, &left));
+	}
+	Ok(order)
+}
+
+/// Lexicographically smallest topological order
+pub fn smallest_topological_sort<G: GraphView>(graph: &G) -> Result<Vec<usize>, Cycle> {
+	topological_sort_by_key(graph, |v| v)
+}
+
+/// Lexicographically largest topological order
+pub fn largest_topological_sort<G: GraphView>(graph: &G) -> Result<Vec<usize>, Cycle> {
+	topological_sort_by_key(graph, Reverse)
+}
+
+fn extend_orders<G: GraphView>(graph: &G, in_degree: &mut [usize], order: &mut Vec<usize>, orders: &mut Vec<Vec<usize>>) {
+	if order.len() == in_degree.len() {
+		orders.push(order.clone());
+		return;
+	}
+	for v in 0..in_degree.len() {
+		if in_degree[v] != 0 || order.contains(&v) {
+			continue;
+		}
+		order.push(v);
+		for edge in graph.neighbours(v) {
+			in_degree[edge.to] -= 1;
+		}
+		extend_orders(graph, in_degree, order, orders);
+		for edge in graph.neighbours(v) {
+			in_degree[edge.to] += 1;
+		}
+		order.pop();
+	}
+}
+
+/// Every topological order in the lexicographic order, none for a cyclic graph.
+/// There may be up to n! of them
+pub fn all_topological_sorts<G: GraphView>(graph: &G) -> Vec<Vec<usize>> {
+	let mut orders = Vec::new();
+	extend_orders(graph, &mut in_degrees(graph), &mut Vec::new(), &mut orders);
+	orders
+}
+
+/// Number of topological orders (0 for a cyclic graph): the orders of every set of vertexes
+/// closed under predecessors, by the last vertex of the order. O(2ⁿ n), for n up to about 25
+pub fn count_topological_sorts<G: GraphView>(graph: &G) -> u128 {
+	let n = graph.vertexes();
+	debug_assert!(n < usize::BITS as usize);
+	let mut predecessors = vec![0_usize; n];
+	for v in 0..n {
+		for edge in graph.neighbours(v) {
+			predecessors[edge.to] |= 1 << v;
+		}
+	}
+	let mut count = vec![0_u128; 1 << n];
+	count[0] = 1;
+	for mask in 0..1_usize << n {
+		if count[mask] == 0 {
+			continue;
+		}
+		for (v, &before) in predecessors.iter().enumerate() {
+			if mask >> v & 1 == 0 && before & !mask == 0 {
+				count[mask | 1 << v] += count[mask];
+			}
+		}
+	}
+	count[(1 << n) - 1]
+}
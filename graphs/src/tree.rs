@@ -0,0 +1,399 @@
+use std::collections::{HashMap, VecDeque};
+use crate::{DFSSpace, Graph, GraphView};
+
+/// Connected and has exactly `n - 1` edges
+pub fn is_tree<G: GraphView>(graph: &G) -> bool {
+	let degrees = (0..graph.vertexes()).map(|v| graph.degree(v)).sum::<usize>();
+	graph.vertexes() > 0
+		&& degrees == 2 * (graph.vertexes() - 1)
+		&& DFSSpace::new(graph).find_connected_components(graph).component_list.len() == 1
+}
+
+/// Prüfer code of a labelled tree in O(n) (the leaf with the smallest label is removed first)
+pub fn prufer_code<G: GraphView>(graph: &G) -> Vec<usize> {
+	debug_assert!(is_tree(graph), "Prüfer code is defined for trees only");
+	let n = graph.vertexes();
+	if n <= 2 {
+		return Vec::new();
+	}
+	let parent = RootedTree::new(graph, n - 1).parent;
+	let mut degree = (0..n).map(|v| graph.degree(v)).collect::<Vec<_>>();
+
+	let mut code = Vec::with_capacity(n - 2);
+	let mut pointer = degree.iter().position(|&d| d == 1).unwrap();
+	let mut leaf = pointer;
+	for _ in 0..n - 2 {
+		let next = parent[leaf].unwrap();
+		code.push(next);
+		degree[next] -= 1;
+		if degree[next] == 1 && next < pointer {
+			// The parent became the smallest leaf
+			leaf = next;
+		} else {
+			pointer += 1;
+			while degree[pointer] != 1 {
+				pointer += 1;
+			}
+			leaf = pointer;
+		}
+	}
+	code
+}
+
+/// Tree on `n = code.len() + 2` vertexes with the given Prüfer code, O(n).
+/// `None` if a value of the code isn't a vertex (every sequence of vertexes is a code)
+pub fn tree_from_prufer_code(code: &[usize]) -> Option<Graph> {
+	let n = code.len() + 2;
+	if code.iter().any(|&v| v >= n) {
+		return None;
+	}
+	let mut tree = Graph::new(n);
+	let mut degree = vec![1_usize; n];
+	for &v in code {
+		degree[v] += 1;
+	}
+
+	let mut pointer = degree.iter().position(|&d| d == 1).unwrap();
+	let mut leaf = pointer;
+	for &v in code {
+		tree.add_undirected_edge(leaf, v);
+		degree[v] -= 1;
+		if degree[v] == 1 && v < pointer {
+			leaf = v;
+		} else {
+			pointer += 1;
+			while degree[pointer] != 1 {
+				pointer += 1;
+			}
+			leaf = pointer;
+		}
+	}
+	tree.add_undirected_edge(leaf, n - 1);
+	Some(tree)
+}
+
+#[derive(Debug, Clone)]
+pub struct RootedTree {
+	pub root: usize,
+	pub parent: Vec<Option<usize>>,
+	/// Index of the edge to the parent
+	pub parent_edge: Vec<Option<usize>>,
+	pub depth: Vec<usize>,
+	pub children: Vec<Vec<usize>>,
+	/// Vertexes in BFS order: every parent goes before its children
+	pub order: Vec<usize>,
+}
+
+impl RootedTree {
+	pub fn new<G: GraphView>(graph: &G, root: usize) -> Self {
+		let mut tree = Self::empty(graph.vertexes(), root);
+		tree.grow(graph, root, &mut vec![false; graph.vertexes()]);
+		tree
+	}
+
+	/// Every connected component is rooted at its smallest vertex (`root` is the root of the first one),
+	/// `order` goes through the components one by one
+	pub fn forest<G: GraphView>(graph: &G) -> Self {
+		let n = graph.vertexes();
+		let mut tree = Self::empty(n, 0);
+		let mut visited = vec![false; n];
+		for root in 0..n {
+			if !visited[root] {
+				tree.grow(graph, root, &mut visited);
+			}
+		}
+		tree
+	}
+
+	fn empty(n: usize, root: usize) -> Self {
+		RootedTree {
+			root,
+			parent: vec![None; n],
+			parent_edge: vec![None; n],
+			depth: vec![0; n],
+			children: vec![Vec::new(); n],
+			order: Vec::with_capacity(n),
+		}
+	}
+
+	/// BFS from `root` over the vertexes that aren't visited yet
+	fn grow<G: GraphView>(&mut self, graph: &G, root: usize, visited: &mut [bool]) {
+		visited[root] = true;
+		let mut queue = VecDeque::from([root]);
+		while let Some(v) = queue.pop_front() {
+			self.order.push(v);
+			for edge in graph.neighbours(v) {
+				if !visited[edge.to] {
+					visited[edge.to] = true;
+					self.parent[edge.to] = Some(v);
+					self.parent_edge[edge.to] = Some(edge.edge_index);
+					self.depth[edge.to] = self.depth[v] + 1;
+					self.children[v].push(edge.to);
+					queue.push_back(edge.to);
+				}
+			}
+		}
+	}
+
+	pub fn vertexes(&self) -> usize {
+		self.parent.len()
+	}
+
+	pub fn subtree_sizes(&self) -> Vec<usize> {
+		let mut size = vec![1; self.vertexes()];
+		for &v in self.order.iter().rev() {
+			if let Some(p) = self.parent[v] {
+				size[p] += size[v];
+			}
+		}
+		size
+	}
+}
+
+/// Binary lifting: `up[k][v]` is the `2^k`-th ancestor of `v` (the root is its own ancestor)
+#[derive(Debug, Clone)]
+pub struct BinaryLifting {
+	pub up: Vec<Vec<usize>>,
+	pub depth: Vec<usize>,
+}
+
+impl BinaryLifting {
+	pub fn new(tree: &RootedTree) -> Self {
+		let n = tree.vertexes();
+		let mut levels = 1;
+		while (1 << levels) < n {
+			levels += 1;
+		}
+		let mut up = vec![(0..n).map(|v| tree.parent[v].unwrap_or(v)).collect::<Vec<_>>()];
+		for k in 1..levels {
+			let next = (0..n).map(|v| up[k - 1][up[k - 1][v]]).collect();
+			up.push(next);
+		}
+		BinaryLifting { up, depth: tree.depth.clone() }
+	}
+
+	pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+		if k > self.depth[v] {
+			return None;
+		}
+		for (level, up) in self.up.iter().enumerate() {
+			if (k >> level) & 1 == 1 {
+				v = up[v];
+			}
+		}
+		Some(v)
+	}
+
+	pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+		if self.depth[u] < self.depth[v] {
+			std::mem::swap(&mut u, &mut v);
+		}
+		u = self.kth_ancestor(u, self.depth[u] - self.depth[v]).unwrap();
+		if u == v {
+			return u;
+		}
+		for up in self.up.iter().rev() {
+			if up[u] != up[v] {
+				u = up[u];
+				v = up[v];
+			}
+		}
+		self.up[0][u]
+	}
+
+	/// Number of edges on the path between `u` and `v`
+	pub fn distance(&self, u: usize, v: usize) -> usize {
+		self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+	}
+}
+
+/// Longest path of the tree (as a list of vertexes), found by two BFS runs
+pub fn tree_diameter<G: GraphView>(graph: &G) -> Vec<usize> {
+	if graph.vertexes() == 0 {
+		return Vec::new();
+	}
+	let farthest = |tree: &RootedTree| *tree.order.last().unwrap();
+	let one_end = farthest(&RootedTree::new(graph, 0));
+	let from_end = RootedTree::new(graph, one_end);
+	let mut path = vec![farthest(&from_end)];
+	while let Some(p) = from_end.parent[*path.last().unwrap()] {
+		path.push(p);
+	}
+	path
+}
+
+/// One or two vertexes in the middle of the diameter (minimizing the eccentricity)
+pub fn tree_center<G: GraphView>(graph: &G) -> Vec<usize> {
+	let diameter = tree_diameter(graph);
+	let len = diameter.len();
+	if len == 0 {
+		return Vec::new();
+	}
+	if len % 2 == 1 {
+		vec![diameter[len / 2]]
+	} else {
+		let mut center = vec![diameter[len / 2 - 1], diameter[len / 2]];
+		center.sort();
+		center
+	}
+}
+
+/// One or two vertexes whose removal leaves components of size at most `n / 2`
+pub fn tree_centroids<G: GraphView>(graph: &G) -> Vec<usize> {
+	let n = graph.vertexes();
+	if n == 0 {
+		return Vec::new();
+	}
+	let tree = RootedTree::new(graph, 0);
+	let size = tree.subtree_sizes();
+	(0..n)
+		.filter(|&v| {
+			let largest_child = tree.children[v].iter().map(|&c| size[c]).max().unwrap_or(0);
+			largest_child.max(n - size[v]) <= n / 2
+		})
+		.collect()
+}
+
+/// Centroid decomposition: the centroid of the tree is the root,
+/// centroids of the remaining components are its children and so on
+#[derive(Debug, Clone)]
+pub struct CentroidDecomposition {
+	pub root: usize,
+	pub parent: Vec<Option<usize>>,
+	/// Depth in the centroid tree (at most log₂ n)
+	pub level: Vec<usize>,
+}
+
+impl CentroidDecomposition {
+	pub fn new<G: GraphView>(graph: &G) -> Self {
+		let n = graph.vertexes();
+		assert!(n > 0, "Centroid decomposition of an empty tree");
+		let mut removed = vec![false; n];
+		let mut parent = vec![None; n];
+		let mut level = vec![0; n];
+		let mut size = vec![0; n];
+		let mut bfs_parent = vec![None; n];
+		let mut root = 0;
+
+		// (any vertex of the component, centroid of the enclosing component)
+		let mut components = vec![(0, None)];
+		while let Some((start, parent_centroid)) = components.pop() {
+			// BFS order of the component to compute subtree sizes without recursion
+			let mut order = vec![start];
+			bfs_parent[start] = None;
+			let mut i = 0;
+			while i < order.len() {
+				let v = order[i];
+				for edge in graph.neighbours(v) {
+					if !removed[edge.to] && bfs_parent[v] != Some(edge.to) {
+						bfs_parent[edge.to] = Some(v);
+						order.push(edge.to);
+					}
+				}
+				i += 1;
+			}
+			for &v in &order {
+				size[v] = 1;
+			}
+			for &v in order.iter().skip(1).rev() {
+				size[bfs_parent[v].unwrap()] += size[v];
+			}
+
+			let total = order.len();
+			let mut centroid = start;
+			loop {
+				let heavy = graph.neighbours(centroid)
+					.find(|e| !removed[e.to] && bfs_parent[centroid] != Some(e.to) && size[e.to] * 2 > total);
+				match heavy {
+					Some(edge) => centroid = edge.to,
+					None => break,
+				}
+			}
+
+			removed[centroid] = true;
+			parent[centroid] = parent_centroid;
+			match parent_centroid {
+				Some(p) => level[centroid] = level[p] + 1,
+				None => root = centroid,
+			}
+			for edge in graph.neighbours(centroid) {
+				if !removed[edge.to] {
+					components.push((edge.to, Some(centroid)));
+				}
+			}
+		}
+
+		CentroidDecomposition { root, parent, level }
+	}
+}
+
+/// AHU canonical forms. Ids of isomorphic rooted trees are equal
+/// as long as they are computed by the same canonizer
+#[derive(Debug, Clone, Default)]
+pub struct TreeCanonizer {
+	ids: HashMap<Vec<usize>, usize>,
+}
+
+impl TreeCanonizer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Id of every subtree of the tree rooted at `root`
+	pub fn subtree_ids<G: GraphView>(&mut self, graph: &G, root: usize) -> Vec<usize> {
+		let tree = RootedTree::new(graph, root);
+		let mut id = vec![0; tree.vertexes()];
+		for &v in tree.order.iter().rev() {
+			let mut children = tree.children[v].iter().map(|&c| id[c]).collect::<Vec<_>>();
+			children.sort();
+			let next_id = self.ids.len();
+			id[v] = *self.ids.entry(children).or_insert(next_id);
+		}
+		id
+	}
+
+	pub fn rooted_id<G: GraphView>(&mut self, graph: &G, root: usize) -> usize {
+		self.subtree_ids(graph, root)[root]
+	}
+
+	/// Sorted ids of the tree rooted at each of its centers
+	pub fn unrooted_id<G: GraphView>(&mut self, graph: &G) -> Vec<usize> {
+		let mut ids = tree_center(graph).into_iter()
+			.map(|c| self.rooted_id(graph, c))
+			.collect::<Vec<_>>();
+		ids.sort();
+		ids
+	}
+}
+
+/// Parenthesis representation of the rooted tree with sorted children, e.g. `(()(()))`
+pub fn rooted_canonical_form<G: GraphView>(graph: &G, root: usize) -> String {
+	let tree = RootedTree::new(graph, root);
+	let mut form = vec![String::new(); tree.vertexes()];
+	for &v in tree.order.iter().rev() {
+		let mut children = tree.children[v].iter()
+			.map(|&c| std::mem::take(&mut form[c]))
+			.collect::<Vec<_>>();
+		children.sort();
+		form[v] = format!("({})", children.concat());
+	}
+	std::mem::take(&mut form[root])
+}
+
+/// The smallest of the rooted canonical forms over the centers
+pub fn unrooted_canonical_form<G: GraphView>(graph: &G) -> String {
+	tree_center(graph).into_iter()
+		.map(|c| rooted_canonical_form(graph, c))
+		.min()
+		.unwrap_or_default()
+}
+
+pub fn are_rooted_trees_isomorphic<A: GraphView, B: GraphView>(a: &A, a_root: usize, b: &B, b_root: usize) -> bool {
+	let mut canonizer = TreeCanonizer::new();
+	a.vertexes() == b.vertexes() && canonizer.rooted_id(a, a_root) == canonizer.rooted_id(b, b_root)
+}
+
+pub fn are_trees_isomorphic<A: GraphView, B: GraphView>(a: &A, b: &B) -> bool {
+	let mut canonizer = TreeCanonizer::new();
+	a.vertexes() == b.vertexes() && canonizer.unrooted_id(a) == canonizer.unrooted_id(b)
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use crate::{canonical_form, BivariatePolynomial, CanonicalForm, DFSSpace, Graph, Polynomial};
+
+fn loops_of(graph: &Graph) -> Vec<usize> {
+	let mut loops = graph.edges.iter()
+		.enumerate()
+		.flat_map(|(v, edges)| edges.iter().filter(move |e| e.to == v).map(|e| e.edge_index))
+		.collect::<Vec<_>>();
+	loops.sort();
+	loops.dedup();
+	loops
+}
+
+/// Deletion–contraction on multigraphs:
+/// T(G) = y T(G - e) for a loop, x T(G / e) for a bridge and T(G - e) + T(G / e) otherwise.
+/// Loops are taken out at once and the rest is split into blocks, whose polynomials multiply
+#[derive(Debug, Clone, Default)]
+pub struct TuttePolynomialSolver {
+	memo: HashMap<CanonicalForm, BivariatePolynomial>,
+}
+
+impl TuttePolynomialSolver {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn solve(&mut self, graph: &Graph) -> BivariatePolynomial {
+		let loops = loops_of(graph);
+		let graph = graph.without_edges(&loops);
+
+		let (_cutting_points, blocks) = DFSSpace::new(&graph).find_cutting_points_with_components(&graph);
+		blocks.component_list.iter()
+			.map(|block| self.solve_block(&graph.edge_subgraph(block).0))
+			.fold(BivariatePolynomial::monomial(0, loops.len(), 1), |acc, p| acc * p)
+	}
+
+	/// Block without loops: a bridge or a 2-connected multigraph
+	fn solve_block(&mut self, block: &Graph) -> BivariatePolynomial {
+		let m = block.edges();
+		if m == 1 {
+			return BivariatePolynomial::monomial(1, 0, 1);
+		}
+		if block.vertexes() == 2 {
+			// m parallel edges: x + y + y² + … + y^(m - 1)
+			return (1..m).fold(BivariatePolynomial::monomial(1, 0, 1), |acc, j| acc + BivariatePolynomial::monomial(0, j, 1));
+		}
+
+		let key = canonical_form(block);
+		if let Some(known) = self.memo.get(&key) {
+			return known.clone();
+		}
+		// No edge of a 2-connected block is a bridge or a loop
+		let edge_index = block.edges[0][0].edge_index;
+		let result = self.solve(&block.without_edges(&[edge_index])) + self.solve(&block.contracted(edge_index));
+		self.memo.insert(key, result.clone());
+		result
+	}
+}
+
+/// Tutte polynomial of a multigraph together with what's needed to specialize it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TuttePolynomial {
+	pub polynomial: BivariatePolynomial,
+	pub vertexes: usize,
+	pub edges: usize,
+	pub components: usize,
+}
+
+impl TuttePolynomial {
+	pub fn new(graph: &Graph) -> Self {
+		TuttePolynomial {
+			polynomial: TuttePolynomialSolver::new().solve(graph),
+			vertexes: graph.vertexes(),
+			edges: graph.edges(),
+			components: DFSSpace::new(graph).find_connected_components(graph).component_list.len(),
+		}
+	}
+
+	/// Rank of the cycle matroid: `n - c`
+	pub fn rank(&self) -> usize {
+		self.vertexes - self.components
+	}
+
+	/// Cyclomatic number: `m - n + c`
+	pub fn nullity(&self) -> usize {
+		self.edges - self.rank()
+	}
+
+	pub fn evaluate(&self, x: i64, y: i64) -> i64 {
+		self.polynomial.evaluate(x, y)
+	}
+
+	/// P(G; λ) = (-1)^(n - c) λ^c T(G; 1 - λ, 0)
+	pub fn chromatic_polynomial(&self) -> Polynomial {
+		let one_minus_lambda = Polynomial::new(vec![1, -1]);
+		let sign = (-1_i64).pow(self.rank() as u32);
+		Polynomial::monomial(self.components, sign) * self.polynomial.substitute(&one_minus_lambda, &Polynomial::zero())
+	}
+
+	/// Number of nowhere-zero flows with values in a group of order λ:
+	/// F(G; λ) = (-1)^(m - n + c) T(G; 0, 1 - λ)
+	pub fn flow_polynomial(&self) -> Polynomial {
+		let one_minus_lambda = Polynomial::new(vec![1, -1]);
+		let sign = (-1_i64).pow(self.nullity() as u32);
+		Polynomial::monomial(0, sign) * self.polynomial.substitute(&Polynomial::zero(), &one_minus_lambda)
+	}
+
+	/// Probability that the graph stays as connected as it is when every edge survives independently
+	/// with probability `p`: R(G; p) = p^(n - c) (1 - p)^(m - n + c) T(G; 1, 1 / (1 - p))
+	pub fn reliability_polynomial(&self) -> Polynomial {
+		let p = Polynomial::monomial(1, 1);
+		let q = Polynomial::new(vec![1, -1]);
+		let nullity = self.nullity();
+		// T(1, y) as a polynomial in y
+		let at_x_one = self.polynomial.substitute(&Polynomial::one(), &p);
+		let mut result = Polynomial::zero();
+		for (j, &c) in at_x_one.coefficients.iter().enumerate() {
+			result += Polynomial::monomial(0, c) * q.pow(nullity - j);
+		}
+		result * p.pow(self.rank())
+	}
+
+	/// T(1, 1): spanning trees (maximal spanning forests if the graph is disconnected)
+	pub fn spanning_trees(&self) -> i64 {
+		self.evaluate(1, 1)
+	}
+
+	/// T(2, 1): acyclic subsets of edges
+	pub fn spanning_forests(&self) -> i64 {
+		self.evaluate(2, 1)
+	}
+
+	/// T(1, 2): subsets of edges keeping the number of components
+	pub fn connected_spanning_subgraphs(&self) -> i64 {
+		self.evaluate(1, 2)
+	}
+
+	/// T(2, 0)
+	pub fn acyclic_orientations(&self) -> i64 {
+		self.evaluate(2, 0)
+	}
+}
+
+pub fn tutte_polynomial(graph: &Graph) -> BivariatePolynomial {
+	TuttePolynomialSolver::new().solve(graph)
+}
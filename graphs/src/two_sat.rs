@@ -0,0 +1,146 @@
+use std::ops::Not;
+use crate::{bfs, DFSSpace, Graph};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Literal {
+	pub variable: usize,
+	pub negated: bool,
+}
+
+impl Literal {
+	pub fn positive(variable: usize) -> Self {
+		Literal { variable, negated: false }
+	}
+
+	pub fn negative(variable: usize) -> Self {
+		Literal { variable, negated: true }
+	}
+
+	/// Vertex of the implication graph: `2 x` for `x` and `2 x + 1` for `¬x`
+	fn vertex(self) -> usize {
+		2 * self.variable + self.negated as usize
+	}
+
+	fn from_vertex(vertex: usize) -> Self {
+		Literal { variable: vertex / 2, negated: vertex & 1 == 1 }
+	}
+
+	/// Value of the literal under an assignment of variables
+	pub fn evaluate(self, assignment: &[bool]) -> bool {
+		assignment[self.variable] != self.negated
+	}
+}
+
+impl Not for Literal {
+	type Output = Self;
+
+	fn not(self) -> Self {
+		Literal { variable: self.variable, negated: !self.negated }
+	}
+}
+
+/// Clauses that can't be satisfied together: they imply both `x ⇒ ¬x` and `¬x ⇒ x`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsatisfiableCore {
+	pub variable: usize,
+	/// Indexes of the clauses (in the order they were added)
+	pub clauses: Vec<usize>,
+	/// Chain of implications `x ⇒ … ⇒ ¬x ⇒ … ⇒ x`
+	pub implications: Vec<Literal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TwoSatResult {
+	Satisfiable(Vec<bool>),
+	Unsatisfiable(UnsatisfiableCore),
+}
+
+impl TwoSatResult {
+	pub fn assignment(&self) -> Option<&Vec<bool>> {
+		match self {
+			TwoSatResult::Satisfiable(assignment) => Some(assignment),
+			TwoSatResult::Unsatisfiable(_) => None,
+		}
+	}
+}
+
+/// 2-CNF formula. A clause `a ∨ b` is the pair of implications `¬a ⇒ b` and `¬b ⇒ a`,
+/// the formula is satisfiable iff no `x` is in one strongly connected component with `¬x`
+#[derive(Debug, Clone, Default)]
+pub struct TwoSat {
+	pub variables: usize,
+	pub clauses: Vec<(Literal, Literal)>,
+}
+
+impl TwoSat {
+	pub fn new(variables: usize) -> Self {
+		TwoSat { variables, clauses: Vec::new() }
+	}
+
+	/// `a ∨ b`, returns the index of the clause
+	pub fn add_clause(&mut self, a: Literal, b: Literal) -> usize {
+		debug_assert!(a.variable < self.variables && b.variable < self.variables, "Unknown variable");
+		self.clauses.push((a, b));
+		self.clauses.len() - 1
+	}
+
+	/// `a ⇒ b`
+	pub fn add_implication(&mut self, a: Literal, b: Literal) -> usize {
+		self.add_clause(!a, b)
+	}
+
+	/// `a` has to be true
+	pub fn add_unit(&mut self, a: Literal) -> usize {
+		self.add_clause(a, a)
+	}
+
+	/// Implication graph: edge `2 i` is `¬a ⇒ b` and edge `2 i + 1` is `¬b ⇒ a` for clause `i`
+	fn implication_graph(&self) -> Graph {
+		let mut graph = Graph::new(2 * self.variables);
+		for &(a, b) in &self.clauses {
+			graph.add_directed_edge((!a).vertex(), b.vertex());
+			graph.add_directed_edge((!b).vertex(), a.vertex());
+		}
+		graph
+	}
+
+	pub fn solve(&self) -> TwoSatResult {
+		let graph = self.implication_graph();
+		let (_, components) = DFSSpace::new(&graph).tarjan_condensation(&graph);
+		let component = |literal: Literal| components.component_of(literal.vertex());
+
+		if let Some(variable) = (0..self.variables).find(|&x| component(Literal::positive(x)) == component(Literal::negative(x))) {
+			return TwoSatResult::Unsatisfiable(self.core(&graph, variable));
+		}
+		// Components are in the topological order: a literal later than its negation can't imply it
+		let assignment = (0..self.variables)
+			.map(|x| component(Literal::positive(x)) > component(Literal::negative(x)))
+			.collect();
+		TwoSatResult::Satisfiable(assignment)
+	}
+
+	/// Shortest implication paths `x ⇒ ¬x` and `¬x ⇒ x` and the clauses they use
+	fn core(&self, graph: &Graph, variable: usize) -> UnsatisfiableCore {
+		let (positive, negative) = (Literal::positive(variable), Literal::negative(variable));
+		let mut edges = shortest_path(graph, positive.vertex(), negative.vertex());
+		edges.extend(shortest_path(graph, negative.vertex(), positive.vertex()));
+
+		let mut implications = vec![positive];
+		let mut clauses = Vec::new();
+		for (to, edge_index) in edges {
+			implications.push(Literal::from_vertex(to));
+			clauses.push(edge_index / 2);
+		}
+		clauses.sort();
+		clauses.dedup();
+		UnsatisfiableCore { variable, clauses, implications }
+	}
+}
+
+/// `(vertex, edge index)` of every step of a shortest path
+fn shortest_path(graph: &Graph, from: usize, to: usize) -> Vec<(usize, usize)> {
+	let paths = bfs(graph, from);
+	let vertexes = paths.path_to(to).expect("Vertex should be reachable");
+	let edges = paths.edges_to(to).unwrap();
+	vertexes.into_iter().skip(1).zip(edges).collect()
+}
@@ -0,0 +1,197 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use crate::{CsrGraph, WeightedEdge, WeightedGraph};
+
+/// Read-only access to a graph: what traversals need, whatever the representation is.
+/// An undirected edge is seen from both of its ends with the same index, as in `WeightedGraph`
+pub trait GraphView {
+	type Weight: Copy + Debug;
+
+	fn vertexes(&self) -> usize;
+
+	/// Upper bound of edge indexes (the number of edges if they are compact)
+	fn edge_index_bound(&self) -> usize;
+
+	fn neighbours(&self, vertex: usize) -> impl Iterator<Item = WeightedEdge<Self::Weight>> + '_;
+
+	fn degree(&self, vertex: usize) -> usize {
+		self.neighbours(vertex).count()
+	}
+}
+
+impl<T: Clone + Copy + Debug> GraphView for WeightedGraph<T> {
+	type Weight = T;
+
+	fn vertexes(&self) -> usize {
+		self.edges.len()
+	}
+
+	fn edge_index_bound(&self) -> usize {
+		self.edge_index_bound.max(self.total_edges)
+	}
+
+	fn neighbours(&self, vertex: usize) -> impl Iterator<Item = WeightedEdge<T>> + '_ {
+		self.edges[vertex].iter().copied()
+	}
+
+	fn degree(&self, vertex: usize) -> usize {
+		self.edges[vertex].len()
+	}
+}
+
+/// Every arc turned around. Incoming arcs are gathered once, weights and edge indexes are kept
+#[derive(Debug, Clone)]
+pub struct Reversed<'a, G: GraphView> {
+	pub graph: &'a G,
+	incoming: CsrGraph<G::Weight>,
+}
+
+impl<'a, G: GraphView> Reversed<'a, G> {
+	pub fn new(graph: &'a G) -> Self {
+		Reversed { graph, incoming: CsrGraph::reversed(graph) }
+	}
+}
+
+impl<G: GraphView> GraphView for Reversed<'_, G> {
+	type Weight = G::Weight;
+
+	fn vertexes(&self) -> usize {
+		self.graph.vertexes()
+	}
+
+	fn edge_index_bound(&self) -> usize {
+		self.graph.edge_index_bound()
+	}
+
+	fn neighbours(&self, vertex: usize) -> impl Iterator<Item = WeightedEdge<G::Weight>> + '_ {
+		self.incoming.edges_of(vertex).iter().copied()
+	}
+}
+
+/// Subgraph induced by the vertexes for which `keep` holds. Vertex numbers stay the same,
+/// removed vertexes are just isolated
+#[derive(Debug, Clone)]
+pub struct VertexSubgraph<'a, G, F> {
+	pub graph: &'a G,
+	pub keep: F,
+}
+
+impl<'a, G: GraphView, F: Fn(usize) -> bool> VertexSubgraph<'a, G, F> {
+	pub fn new(graph: &'a G, keep: F) -> Self {
+		VertexSubgraph { graph, keep }
+	}
+}
+
+impl<G: GraphView, F: Fn(usize) -> bool> GraphView for VertexSubgraph<'_, G, F> {
+	type Weight = G::Weight;
+
+	fn vertexes(&self) -> usize {
+		self.graph.vertexes()
+	}
+
+	fn edge_index_bound(&self) -> usize {
+		self.graph.edge_index_bound()
+	}
+
+	fn neighbours(&self, vertex: usize) -> impl Iterator<Item = WeightedEdge<G::Weight>> + '_ {
+		let kept = (self.keep)(vertex);
+		self.graph.neighbours(vertex).filter(move |edge| kept && (self.keep)(edge.to))
+	}
+}
+
+/// Subgraph of the edges (by index) for which `keep` holds
+#[derive(Debug, Clone)]
+pub struct EdgeSubgraph<'a, G, F> {
+	pub graph: &'a G,
+	pub keep: F,
+}
+
+impl<'a, G: GraphView, F: Fn(usize) -> bool> EdgeSubgraph<'a, G, F> {
+	pub fn new(graph: &'a G, keep: F) -> Self {
+		EdgeSubgraph { graph, keep }
+	}
+}
+
+impl<G: GraphView, F: Fn(usize) -> bool> GraphView for EdgeSubgraph<'_, G, F> {
+	type Weight = G::Weight;
+
+	fn vertexes(&self) -> usize {
+		self.graph.vertexes()
+	}
+
+	fn edge_index_bound(&self) -> usize {
+		self.graph.edge_index_bound()
+	}
+
+	fn neighbours(&self, vertex: usize) -> impl Iterator<Item = WeightedEdge<G::Weight>> + '_ {
+		self.graph.neighbours(vertex).filter(|edge| (self.keep)(edge.edge_index))
+	}
+}
+
+/// Graph that is never stored: neighbours are generated by a closure on demand.
+/// `edge_index_bound` is the bound of the edge indexes the closure produces,
+/// `'a` is the lifetime of whatever the generated iterators borrow
+#[derive(Debug, Clone)]
+pub struct ImplicitGraph<'a, F> {
+	pub vertexes: usize,
+	pub edge_index_bound: usize,
+	pub neighbours: F,
+	borrowed: PhantomData<&'a ()>,
+}
+
+impl<'a, T, I, F> ImplicitGraph<'a, F>
+	where T: Copy + Debug, I: Iterator<Item = WeightedEdge<T>> + 'a, F: Fn(usize) -> I
+{
+	pub fn new(vertexes: usize, edge_index_bound: usize, neighbours: F) -> Self {
+		ImplicitGraph { vertexes, edge_index_bound, neighbours, borrowed: PhantomData }
+	}
+}
+
+impl<'a, T, I, F> GraphView for ImplicitGraph<'a, F>
+	where T: Copy + Debug, I: Iterator<Item = WeightedEdge<T>> + 'a, F: Fn(usize) -> I
+{
+	type Weight = T;
+
+	fn vertexes(&self) -> usize {
+		self.vertexes
+	}
+
+	fn edge_index_bound(&self) -> usize {
+		self.edge_index_bound
+	}
+
+	fn neighbours(&self, vertex: usize) -> impl Iterator<Item = WeightedEdge<T>> + '_ {
+		(self.neighbours)(vertex)
+	}
+}
+
+/// Undirected grid of `width × height` cells (`cell = y * width + x`) without blocked cells.
+/// Edge `2 cell` goes right from the cell and `2 cell + 1` goes down
+pub fn grid_view<F>(width: usize, height: usize, open: F)
+	-> ImplicitGraph<'static, impl Fn(usize) -> std::vec::IntoIter<WeightedEdge<()>>>
+	where F: Fn(usize) -> bool
+{
+	let neighbours = move |cell: usize| {
+		let (x, y) = (cell % width, cell / width);
+		let mut edges = Vec::with_capacity(4);
+		if open(cell) {
+			let mut push = |to: usize, edge_index: usize| if open(to) {
+				edges.push(WeightedEdge { to, edge_index, weight: () });
+			};
+			if x + 1 < width {
+				push(cell + 1, 2 * cell);
+			}
+			if y + 1 < height {
+				push(cell + width, 2 * cell + 1);
+			}
+			if x > 0 {
+				push(cell - 1, 2 * (cell - 1));
+			}
+			if y > 0 {
+				push(cell - width, 2 * (cell - width) + 1);
+			}
+		}
+		edges.into_iter()
+	};
+	ImplicitGraph::new(width * height, 2 * width * height, neighbours)
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+#[test]
+fn chromatic_polynomial_counts_colourings() {
+	let mut rng = Rng::new(21);
+	for _ in 0..CASES {
+		let loops = rng.below(4) == 0;
+		let graph = random_multigraph(&mut rng, 7, 15, loops);
+		let polynomial = chromatic_polynomial(&graph);
+		for k in 0..5 {
+			assert_eq!(polynomial.evaluate(k as i64), count_colourings(&graph, k), "k = {}\n{}", k, describe(&graph));
+			assert_eq!(is_k_colourable(&graph, k), count_colourings(&graph, k) > 0, "k = {}\n{}", k, describe(&graph));
+		}
+		let expected = (0..=graph.vertexes()).find(|&k| count_colourings(&graph, k) > 0);
+		assert_eq!(chromatic_number(&graph), expected, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn chromatic_polynomial_of_simple_graphs() {
+	let mut rng = Rng::new(22);
+	for _ in 0..CASES {
+		let graph = random_simple_graph(&mut rng, 8);
+		let polynomial = chromatic_polynomial(&graph);
+		// Monic of degree n, the next coefficient is -m
+		assert_eq!(polynomial.degree(), graph.vertexes());
+		assert_eq!(polynomial.coefficients[graph.vertexes()], 1);
+		assert_eq!(polynomial.coefficients[graph.vertexes() - 1], -(graph.edges() as i64), "{}", describe(&graph));
+		for k in 0..4 {
+			assert_eq!(polynomial.evaluate(k as i64), count_colourings(&graph, k), "k = {}\n{}", k, describe(&graph));
+		}
+	}
+}
+
+#[test]
+fn known_chromatic_polynomials() {
+	let x = Polynomial::linear(0);
+	let x_minus_1 = Polynomial::linear(1);
+	let falling = |n: usize| (0..n).fold(Polynomial::one(), |acc, i| acc * Polynomial::linear(i as i64));
+	for n in 1..7 {
+		assert_eq!(chromatic_polynomial(&complete_graph(n)), falling(n));
+		assert_eq!(chromatic_polynomial(&path_graph(n)), x.clone() * x_minus_1.pow(n - 1));
+		assert_eq!(chromatic_polynomial(&Graph::new(n)), x.pow(n));
+	}
+	for n in 3..8 {
+		let sign = Polynomial::monomial(0, if n % 2 == 0 { 1 } else { -1 });
+		assert_eq!(chromatic_polynomial(&cycle_graph(n)), x_minus_1.pow(n) + sign * x_minus_1.clone());
+	}
+	// Petersen graph: 12960 proper 4-colourings, none with 2
+	let petersen = chromatic_polynomial(&petersen_graph());
+	assert_eq!((petersen.evaluate(2), petersen.evaluate(3), petersen.evaluate(4)), (0, 120, 12960));
+	assert_eq!(chromatic_number(&petersen_graph()), Some(3));
+	assert_eq!(chromatic_number(&Graph::new(0)), Some(0));
+
+	let mut looped = path_graph(3);
+	looped.add_undirected_edge(1, 1);
+	assert!(chromatic_polynomial(&looped).is_zero());
+	assert_eq!(chromatic_number(&looped), None);
+}
+
+#[test]
+fn solver_memo_is_reused() {
+	let mut rng = Rng::new(24);
+	let mut solver = ChromaticPolynomialSolver::new();
+	for _ in 0..CASES {
+		let graph = random_simple_graph(&mut rng, 8);
+		let permutation = rng.permutation(graph.vertexes());
+		let mut relabelled = Graph::new(graph.vertexes());
+		for (from, to) in graph.edge_ends() {
+			relabelled.add_undirected_edge(permutation[from], permutation[to]);
+		}
+		let expected = chromatic_polynomial(&graph);
+		assert_eq!(solver.solve(&graph), expected, "{}", describe(&graph));
+		assert_eq!(solver.solve(&relabelled), expected, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn polynomial_arithmetic() {
+	let p = Polynomial::new(vec![1, 2, 0, 0]);
+	assert_eq!(p.coefficients, vec![1, 2]);
+	assert_eq!(p.degree(), 1);
+	assert_eq!(p.to_string(), "2 1");
+	assert_eq!(Polynomial::zero().to_string(), "0");
+	let q = Polynomial::linear(3) * Polynomial::linear(-1);
+	assert_eq!(q.coefficients, vec![-3, -2, 1]);
+	assert_eq!(q.evaluate(3), 0);
+	assert_eq!(q.div_exact(&Polynomial::linear(3)), Polynomial::linear(-1));
+	assert_eq!((q.clone() - q.clone()).degree(), 0);
+	assert!((q.clone() - q).is_zero());
+	assert_eq!(Polynomial::linear(1).pow(3).coefficients, vec![-1, 3, -3, 1]);
+}
+
@@ -0,0 +1,117 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn adjacency(graph: &Graph) -> Vec<Vec<bool>> {
+	let n = graph.vertexes();
+	let mut adjacent = vec![vec![false; n]; n];
+	for (from, to) in graph.edge_ends() {
+		adjacent[from][to] = true;
+		adjacent[to][from] = true;
+	}
+	adjacent
+}
+
+fn subset(mask: usize, n: usize) -> Vec<usize> {
+	(0..n).filter(|&v| mask >> v & 1 == 1).collect()
+}
+
+fn is_clique(adjacent: &[Vec<bool>], vertexes: &[usize]) -> bool {
+	vertexes.iter().all(|&u| vertexes.iter().all(|&v| u == v || adjacent[u][v]))
+}
+
+fn is_independent(adjacent: &[Vec<bool>], vertexes: &[usize]) -> bool {
+	vertexes.iter().all(|&u| vertexes.iter().all(|&v| !adjacent[u][v]))
+}
+
+fn is_cover(graph: &Graph, vertexes: &[usize]) -> bool {
+	graph.edge_ends().into_iter().all(|(from, to)| vertexes.contains(&from) || vertexes.contains(&to))
+}
+
+#[test]
+fn maximal_cliques_match_brute_force() {
+	let mut rng = Rng::new(450);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 24, true);
+		let n = graph.vertexes();
+		let adjacent = adjacency(&graph);
+		let cliques = (1..1usize << n)
+			.map(|mask| subset(mask, n))
+			.filter(|clique| is_clique(&adjacent, clique))
+			.collect::<Vec<_>>();
+		let mut expected = cliques.iter()
+			.filter(|clique| (0..n).all(|v| clique.contains(&v) || !clique.iter().all(|&u| adjacent[u][v])))
+			.cloned()
+			.collect::<Vec<_>>();
+		let mut actual = maximal_cliques(&graph);
+		expected.sort();
+		actual.sort();
+		assert_eq!(actual, expected, "{}", describe(&graph));
+
+		let maximum = maximum_clique(&graph);
+		assert!(is_clique(&adjacent, &maximum));
+		assert_eq!(maximum.len(), cliques.iter().map(|clique| clique.len()).max().unwrap(), "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn independent_sets_and_vertex_covers() {
+	let mut rng = Rng::new(451);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 20, true);
+		let n = graph.vertexes();
+		let adjacent = adjacency(&graph);
+		let largest = (0..1usize << n)
+			.map(|mask| subset(mask, n))
+			.filter(|set| is_independent(&adjacent, set))
+			.map(|set| set.len())
+			.max()
+			.unwrap();
+
+		let independent = maximum_independent_set(&graph);
+		assert!(is_independent(&adjacent, &independent), "{}", describe(&graph));
+		assert_eq!(independent.len(), largest, "{}", describe(&graph));
+
+		let cover = minimum_vertex_cover(&graph);
+		assert!(is_cover(&graph, &cover));
+		assert_eq!(cover.len(), n - largest);
+
+		let approximate = approximate_vertex_cover(&graph);
+		assert!(is_cover(&graph, &approximate), "{}", describe(&graph));
+		assert!(approximate.len() <= 2 * cover.len());
+	}
+}
+
+#[test]
+fn complement() {
+	let mut rng = Rng::new(452);
+	for _ in 0..CASES {
+		let graph = random_simple_graph(&mut rng, 10);
+		let n = graph.vertexes();
+		let complement = graph.complement();
+		assert_eq!(complement.edges(), n * (n - 1) / 2 - graph.edges());
+		let (adjacent, opposite) = (adjacency(&graph), adjacency(&complement));
+		for u in 0..n {
+			for v in 0..n {
+				assert_eq!(opposite[u][v], u != v && !adjacent[u][v]);
+			}
+		}
+	}
+	assert_eq!(complete_graph(5).complement().edges(), 0);
+	assert!(are_isomorphic(&petersen_graph().complement().complement(), &petersen_graph()).is_some());
+}
+
+#[test]
+fn known_cliques() {
+	assert_eq!(maximum_clique(&petersen_graph()).len(), 2);
+	assert_eq!(maximum_independent_set(&petersen_graph()).len(), 4);
+	assert_eq!(maximum_clique(&complete_graph(7)), (0..7).collect::<Vec<_>>());
+	assert_eq!(maximal_cliques(&complete_bipartite_graph(3, 4)).len(), 12);
+	assert_eq!(minimum_vertex_cover(&complete_bipartite_graph(3, 5)).len(), 3);
+	assert_eq!(maximum_independent_set(&cycle_graph(9)).len(), 4);
+	assert!(maximal_cliques(&Graph::new(0)).is_empty());
+	let mut isolated = maximal_cliques(&Graph::new(2));
+	isolated.sort();
+	assert_eq!(isolated, vec![vec![0], vec![1]]);
+}
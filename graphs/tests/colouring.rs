@@ -0,0 +1,156 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn assert_proper(graph: &Graph, colouring: &Decomposition) {
+	assert_eq!(colouring.elements, graph.vertexes());
+	for (from, to) in graph.edge_ends() {
+		assert!(from == to || colouring.component_of(from) != colouring.component_of(to), "{}", describe(graph));
+	}
+}
+
+fn max_degree(graph: &Graph) -> usize {
+	(0..graph.vertexes()).map(|v| graph.edges[v].len()).max().unwrap_or_default()
+}
+
+/// Largest minimum degree over the subgraphs, by peeling
+fn degeneracy(graph: &Graph) -> usize {
+	let n = graph.vertexes();
+	let mut removed = vec![false; n];
+	let mut result = 0;
+	for _ in 0..n {
+		let degree = |v: usize| graph.edges[v].iter().filter(|e| !removed[e.to]).count();
+		let v = (0..n).filter(|&v| !removed[v]).min_by_key(|&v| degree(v)).unwrap();
+		result = result.max(degree(v));
+		removed[v] = true;
+	}
+	result
+}
+
+#[test]
+fn greedy_colourings_are_proper_and_bounded() {
+	let mut rng = Rng::new(440);
+	for _ in 0..CASES {
+		let graph = random_simple_graph(&mut rng, 12);
+		for order in [ColouringOrder::Natural, ColouringOrder::LargestFirst, ColouringOrder::SmallestLast] {
+			let colouring = greedy_colouring(&graph, order);
+			assert_proper(&graph, &colouring);
+			assert!(colouring.component_list.len() <= max_degree(&graph) + 1);
+		}
+		let colours = greedy_colouring(&graph, ColouringOrder::SmallestLast).component_list.len();
+		assert!(colours <= degeneracy(&graph) + 1, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn smallest_last_order_is_a_permutation() {
+	let mut rng = Rng::new(441);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 20, true);
+		let mut order = smallest_last_order(&graph);
+		order.sort();
+		assert_eq!(order, (0..graph.vertexes()).collect::<Vec<_>>());
+	}
+}
+
+#[test]
+fn loops_and_parallel_edges_are_ignored() {
+	let mut rng = Rng::new(442);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 8, 16, true);
+		let mut simple = Graph::new(graph.vertexes());
+		for (from, to) in graph.edge_ends().into_iter().filter(|(from, to)| from != to) {
+			simple.add_undirected_edge(from, to);
+		}
+		assert_proper(&graph, &dsatur(&graph));
+		assert_proper(&graph, &greedy_colouring(&graph, ColouringOrder::SmallestLast));
+		assert_eq!(exact_colouring(&graph).component_list.len(), chromatic_number(&simple).unwrap());
+	}
+}
+
+#[test]
+fn exact_colouring_is_optimal() {
+	let mut rng = Rng::new(443);
+	for _ in 0..CASES {
+		let graph = random_simple_graph(&mut rng, 10);
+		let exact = exact_colouring(&graph);
+		assert_proper(&graph, &exact);
+		assert_eq!(exact.component_list.len(), chromatic_number(&graph).unwrap(), "{}", describe(&graph));
+		let heuristic = dsatur(&graph);
+		assert_proper(&graph, &heuristic);
+		assert!(heuristic.component_list.len() >= exact.component_list.len());
+	}
+}
+
+#[test]
+fn known_chromatic_numbers() {
+	assert_eq!(exact_colouring(&petersen_graph()).component_list.len(), 3);
+	assert_eq!(exact_colouring(&complete_graph(6)).component_list.len(), 6);
+	assert_eq!(exact_colouring(&wheel_graph(6)).component_list.len(), 3);
+	assert_eq!(exact_colouring(&wheel_graph(7)).component_list.len(), 4);
+	assert_eq!(exact_colouring(&herschel_graph()).component_list.len(), 2);
+	assert_eq!(exact_colouring(&Graph::new(0)).component_list.len(), 0);
+}
+
+#[test]
+fn dsatur_is_optimal_on_bipartite_graphs() {
+	let mut rng = Rng::new(444);
+	for _ in 0..CASES {
+		let graph = random_tree(rng.below(12) + 2, &mut rng);
+		assert_eq!(dsatur(&graph).component_list.len(), 2);
+	}
+	assert_eq!(dsatur(&hypercube_graph(4)).component_list.len(), 2);
+	assert_eq!(dsatur(&grid_graph(5, 7)).component_list.len(), 2);
+}
+
+#[test]
+fn two_colouring_or_odd_cycle() {
+	let mut rng = Rng::new(445);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 14, true);
+		match two_colouring(&graph) {
+			Ok(colouring) => {
+				assert!(colouring.component_list.len() <= 2);
+				for (from, to) in graph.edge_ends() {
+					assert_ne!(colouring.component_of(from), colouring.component_of(to), "{}", describe(&graph));
+				}
+			}
+			Err(cycle) => {
+				let ends = graph.edge_ends();
+				let len = cycle.vertexes.len();
+				assert_eq!(len & 1, 1, "{}", describe(&graph));
+				assert_eq!(cycle.edges.len(), len);
+				let mut distinct = cycle.vertexes.clone();
+				distinct.sort();
+				distinct.dedup();
+				assert_eq!(distinct.len(), len);
+				for (i, &e) in cycle.edges.iter().enumerate() {
+					let (a, b) = (cycle.vertexes[i], cycle.vertexes[(i + 1) % len]);
+					assert!(ends[e] == (a, b) || ends[e] == (b, a), "{}", describe(&graph));
+				}
+				assert!(bipartition(&graph).is_none());
+			}
+		}
+	}
+}
+
+#[test]
+fn edge_colouring_uses_at_most_max_degree_plus_one_colours() {
+	let mut rng = Rng::new(446);
+	for _ in 0..CASES {
+		let graph = random_simple_graph(&mut rng, 14);
+		let colouring = edge_colouring(&graph);
+		let ends = graph.edge_ends();
+		assert_eq!(colouring.elements, ends.len());
+		assert!(colouring.component_list.len() <= max_degree(&graph) + 1, "{}", describe(&graph));
+		for class in &colouring.component_list {
+			let mut touched = class.iter().flat_map(|&e| [ends[e].0, ends[e].1]).collect::<Vec<_>>();
+			touched.sort();
+			touched.dedup();
+			assert_eq!(touched.len(), 2 * class.len(), "{}", describe(&graph));
+		}
+	}
+	assert_eq!(edge_colouring(&complete_graph(7)).component_list.len(), 7);
+	assert!(edge_colouring(&complete_bipartite_graph(3, 4)).component_list.len() <= 5);
+}
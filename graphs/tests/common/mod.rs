@@ -0,0 +1,149 @@
+//! Small random graphs and brute-force oracles shared by the property tests
+#![allow(dead_code)]
+
+use graphs::*;
+
+/// Random graphs checked by every property
+pub const CASES: usize = 300;
+
+/// Undirected multigraph with `1..=max_vertexes` vertexes and up to `max_edges` edges,
+/// parallel edges included and loops if `loops` is set
+pub fn random_multigraph(rng: &mut Rng, max_vertexes: usize, max_edges: usize, loops: bool) -> Graph {
+	let n = rng.below(max_vertexes) + 1;
+	let mut graph = Graph::new(n);
+	for _ in 0..rng.below(max_edges + 1) {
+		let (from, to) = (rng.below(n), rng.below(n));
+		if from != to || loops {
+			graph.add_undirected_edge(from, to);
+		}
+	}
+	graph
+}
+
+/// Directed multigraph with loops and parallel edges
+pub fn random_directed_graph(rng: &mut Rng, max_vertexes: usize, max_edges: usize) -> Graph {
+	let n = rng.below(max_vertexes) + 1;
+	let mut graph = Graph::new(n);
+	for _ in 0..rng.below(max_edges + 1) {
+		graph.add_directed_edge(rng.below(n), rng.below(n));
+	}
+	graph
+}
+
+/// Simple undirected graph with a random density
+pub fn random_simple_graph(rng: &mut Rng, max_vertexes: usize) -> Graph {
+	let n = rng.below(max_vertexes) + 1;
+	let p = rng.next_f64();
+	gnp_random_graph(n, p, false, rng)
+}
+
+/// Edge list to reproduce a failing case
+pub fn describe(graph: &Graph) -> String {
+	format_edge_list(graph, Indexing::ZeroBased)
+}
+
+pub struct UnionFind {
+	parent: Vec<usize>,
+	pub components: usize,
+}
+
+impl UnionFind {
+	pub fn new(n: usize) -> Self {
+		UnionFind { parent: (0..n).collect(), components: n }
+	}
+
+	pub fn find(&mut self, v: usize) -> usize {
+		if self.parent[v] != v {
+			self.parent[v] = self.find(self.parent[v]);
+		}
+		self.parent[v]
+	}
+
+	pub fn union(&mut self, a: usize, b: usize) {
+		let (a, b) = (self.find(a), self.find(b));
+		if a != b {
+			self.parent[a] = b;
+			self.components -= 1;
+		}
+	}
+}
+
+/// Connected components of the undirected graph restricted to the kept vertexes and edges
+pub fn count_components<E, V>(graph: &Graph, keep_edge: E, keep_vertex: V) -> usize
+	where E: Fn(usize) -> bool, V: Fn(usize) -> bool
+{
+	let n = graph.vertexes();
+	let mut union_find = UnionFind::new(n);
+	for (index, (from, to)) in graph.edge_ends().into_iter().enumerate() {
+		if keep_edge(index) && keep_vertex(from) && keep_vertex(to) {
+			union_find.union(from, to);
+		}
+	}
+	union_find.components - (0..n).filter(|&v| !keep_vertex(v)).count()
+}
+
+/// `reach[u][v]`: there is a directed path (possibly empty) from `u` to `v`
+pub fn reachability(graph: &Graph) -> Vec<Vec<bool>> {
+	let n = graph.vertexes();
+	let mut reach = vec![vec![false; n]; n];
+	for (v, row) in reach.iter_mut().enumerate() {
+		row[v] = true;
+	}
+	for (from, to) in graph.edge_ends() {
+		reach[from][to] = true;
+	}
+	for k in 0..n {
+		let through = reach[k].clone();
+		for row in reach.iter_mut().filter(|row| row[k]) {
+			row.iter_mut().zip(&through).for_each(|(to, &via)| *to |= via);
+		}
+	}
+	reach
+}
+
+/// Partition with sorted parts in sorted order, to compare partitions regardless of numbering
+pub fn normalized(partition: &[Vec<usize>]) -> Vec<Vec<usize>> {
+	let mut parts = partition.iter()
+		.map(|part| {
+			let mut part = part.clone();
+			part.sort();
+			part
+		})
+		.collect::<Vec<_>>();
+	parts.sort();
+	parts
+}
+
+pub fn permutations(n: usize) -> Vec<Vec<usize>> {
+	if n == 0 {
+		return vec![Vec::new()];
+	}
+	let mut result = Vec::new();
+	for permutation in permutations(n - 1) {
+		for i in 0..n {
+			let mut extended = permutation.clone();
+			extended.insert(i, n - 1);
+			result.push(extended);
+		}
+	}
+	result
+}
+
+/// Proper colourings with `k` colours by enumeration
+pub fn count_colourings(graph: &Graph, k: usize) -> i64 {
+	let n = graph.vertexes();
+	let ends = graph.edge_ends();
+	let mut colours = vec![0; n];
+	let mut count = 0;
+	for code in 0..k.pow(n as u32) {
+		let mut rest = code;
+		for colour in colours.iter_mut() {
+			*colour = rest % k;
+			rest /= k;
+		}
+		if ends.iter().all(|&(from, to)| colours[from] != colours[to]) {
+			count += 1;
+		}
+	}
+	if n == 0 { 1 } else { count }
+}
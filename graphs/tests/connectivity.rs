@@ -0,0 +1,217 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+/// Weight of the edges between the vertexes in `side` (a bitmask) and the rest
+fn cut_weight(graph: &WeightedGraph<i64>, side: usize) -> i64 {
+	(0..graph.vertexes())
+		.filter(|&v| side >> v & 1 == 1)
+		.flat_map(|v| graph.edges[v].iter())
+		.filter(|edge| side >> edge.to & 1 == 0)
+		.map(|edge| edge.weight)
+		.sum()
+}
+
+fn mask(side: &[usize]) -> usize {
+	side.iter().map(|&v| 1 << v).sum()
+}
+
+/// Lightest cut with `u` on its side and `v` off it
+fn naive_min_cut(graph: &WeightedGraph<i64>, u: usize, v: usize) -> i64 {
+	(0..1_usize << graph.vertexes())
+		.filter(|side| side >> u & 1 == 1 && side >> v & 1 == 0)
+		.map(|side| cut_weight(graph, side))
+		.min()
+		.unwrap()
+}
+
+fn assert_valid_cut(graph: &WeightedGraph<i64>, cut: &MinCut) {
+	let n = graph.vertexes();
+	assert!(!cut.side.is_empty() && cut.side.len() < n, "{:?}", cut);
+	assert!(cut.side.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", cut);
+	let side = mask(&cut.side);
+	assert_eq!(cut_weight(graph, side), cut.weight, "{:?}", cut);
+	let mut crossing = (0..n)
+		.filter(|&v| side >> v & 1 == 1)
+		.flat_map(|v| graph.edges[v].iter())
+		.filter(|edge| side >> edge.to & 1 == 0)
+		.map(|edge| edge.edge_index)
+		.collect::<Vec<_>>();
+	crossing.sort();
+	assert_eq!(crossing, cut.edges);
+}
+
+/// `source` and `sink` stay connected when the vertexes in `removed` (a bitmask) are deleted
+fn connected_without(graph: &Graph, removed: usize, source: usize, sink: usize) -> bool {
+	let mut union_find = UnionFind::new(graph.vertexes());
+	for (from, to) in graph.edge_ends() {
+		if removed >> from & 1 == 0 && removed >> to & 1 == 0 {
+			union_find.union(from, to);
+		}
+	}
+	union_find.find(source) == union_find.find(sink)
+}
+
+/// Fewest vertexes whose removal disconnects the rest, `n - 1` if no set does
+fn naive_vertex_connectivity(graph: &Graph) -> usize {
+	let n = graph.vertexes();
+	(0..1_usize << n)
+		.filter(|&removed| (removed.count_ones() as usize) + 2 <= n)
+		.filter(|&removed| count_components(graph, |_| true, |v| removed >> v & 1 == 0) > 1)
+		.map(|removed| removed.count_ones() as usize)
+		.min()
+		.unwrap_or(n.saturating_sub(1))
+}
+
+#[test]
+fn stoer_wagner_finds_the_minimum_cut() {
+	let mut rng = Rng::new(500);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 20, true);
+		let weighted = with_random_weights(&graph, 0, 10, &mut rng);
+		let n = graph.vertexes();
+		let Some(cut) = stoer_wagner(&weighted) else {
+			assert_eq!(n, 1);
+			continue;
+		};
+		assert_valid_cut(&weighted, &cut);
+		let best = (1..(1_usize << n) - 1).map(|side| cut_weight(&weighted, side)).min().unwrap();
+		assert_eq!(cut.weight, best, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn edge_connectivity_of_known_graphs() {
+	let weight = |graph: &Graph| edge_connectivity(graph).unwrap().weight;
+	assert_eq!(weight(&complete_graph(6)), 5);
+	assert_eq!(weight(&cycle_graph(7)), 2);
+	assert_eq!(weight(&path_graph(5)), 1);
+	assert_eq!(weight(&petersen_graph()), 3);
+	assert_eq!(weight(&hypercube_graph(4)), 4);
+	assert_eq!(weight(&Graph::new(3)), 0);
+	assert!(edge_connectivity(&Graph::new(1)).is_none());
+
+	// Two triangles joined by a single edge
+	let mut graph = Graph::new(6);
+	for (from, to) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+		graph.add_undirected_edge(from, to);
+	}
+	let cut = edge_connectivity(&graph).unwrap();
+	assert_eq!(cut.weight, 1);
+	assert_eq!(cut.edges, vec![6]);
+	assert!(cut.side == vec![0, 1, 2] || cut.side == vec![3, 4, 5]);
+}
+
+#[test]
+fn vertex_connectivity_matches_brute_force() {
+	let mut rng = Rng::new(501);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 25, true);
+		let n = graph.vertexes();
+		let expected = naive_vertex_connectivity(&graph);
+		assert_eq!(vertex_connectivity(&graph), expected, "{}", describe(&graph));
+		match minimum_vertex_separator(&graph) {
+			Some(separator) => {
+				assert_eq!(separator.len(), expected);
+				let removed = mask(&separator);
+				assert!(separator.len() + 2 <= n);
+				assert!(count_components(&graph, |_| true, |v| removed >> v & 1 == 0) > 1, "{}", describe(&graph));
+			}
+			None => assert_eq!(expected, n.saturating_sub(1), "{}", describe(&graph)),
+		}
+	}
+}
+
+#[test]
+fn separators_between_two_vertexes() {
+	let mut rng = Rng::new(502);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 20, false);
+		let n = graph.vertexes();
+		let (source, sink) = (rng.below(n), rng.below(n));
+		let Some(separator) = minimum_vertex_separator_between(&graph, source, sink) else {
+			assert!(source == sink || graph.edges[source].iter().any(|edge| edge.to == sink));
+			continue;
+		};
+		assert!(!separator.contains(&source) && !separator.contains(&sink));
+		let removed = mask(&separator);
+		assert!(!connected_without(&graph, removed, source, sink), "{}", describe(&graph));
+		// No smaller set of other vertexes separates them
+		let smallest = (0..1_usize << n)
+			.filter(|&removed| removed >> source & 1 == 0 && removed >> sink & 1 == 0)
+			.filter(|&removed| !connected_without(&graph, removed, source, sink))
+			.map(|removed| removed.count_ones() as usize)
+			.min()
+			.unwrap();
+		assert_eq!(separator.len(), smallest, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn vertex_connectivity_of_known_graphs() {
+	assert_eq!(vertex_connectivity(&complete_graph(5)), 4);
+	assert!(minimum_vertex_separator(&complete_graph(5)).is_none());
+	assert_eq!(vertex_connectivity(&cycle_graph(8)), 2);
+	assert_eq!(vertex_connectivity(&petersen_graph()), 3);
+	assert_eq!(vertex_connectivity(&hypercube_graph(3)), 3);
+	assert_eq!(vertex_connectivity(&complete_bipartite_graph(3, 5)), 3);
+	assert_eq!(vertex_connectivity(&Graph::new(1)), 0);
+	assert_eq!(minimum_vertex_separator(&path_graph(3)), Some(vec![1]));
+}
+
+#[test]
+fn gomory_hu_tree_gives_all_minimum_cuts() {
+	let mut rng = Rng::new(503);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 8, 18, true);
+		let weighted = with_random_weights(&graph, 0, 10, &mut rng);
+		let n = graph.vertexes();
+		let gomory_hu = GomoryHuTree::new(&weighted);
+		assert_eq!(gomory_hu.tree.vertexes(), n);
+		let mut union_find = UnionFind::new(n);
+		for v in 0..n {
+			for edge in &gomory_hu.tree.edges[v] {
+				union_find.union(v, edge.to);
+			}
+		}
+		assert_eq!(union_find.components, 1);
+		assert_eq!(gomory_hu.tree.edges.iter().map(Vec::len).sum::<usize>(), 2 * (n - 1));
+		for u in 0..n {
+			for v in (0..n).filter(|&v| v != u) {
+				let cut = gomory_hu.min_cut(u, v);
+				assert_eq!(cut.weight, naive_min_cut(&weighted, u, v), "{} {} {}", describe(&graph), u, v);
+				assert_eq!(gomory_hu.min_cut_value(u, v), cut.weight);
+				assert!(cut.side.contains(&u) && !cut.side.contains(&v));
+				assert_valid_cut(&weighted, &cut);
+			}
+		}
+	}
+}
+
+#[test]
+fn dinic_matches_successive_shortest_paths() {
+	let mut rng = Rng::new(504);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 10, 30);
+		let n = graph.vertexes();
+		let mut network = FlowNetwork::new(n);
+		for (from, to) in graph.edge_ends() {
+			network.add_arc(from, to, rng.range(0, 10), rng.range(0, 5));
+		}
+		let (source, sink) = (rng.below(n), rng.below(n));
+		if source == sink {
+			continue;
+		}
+		let expected = network.clone().min_cost_max_flow(source, sink).unwrap().flow;
+		assert_eq!(network.max_flow(source, sink), expected, "{}", describe(&graph));
+		let side = network.reachable_in_residual(source);
+		assert!(side[source] && !side[sink]);
+		let crossing = (0..n)
+			.filter(|&v| side[v])
+			.flat_map(|v| network.graph.edges[v].iter().filter(|edge| edge.edge_index % 2 == 0 && !side[edge.to]))
+			.map(|edge| network.capacity[edge.edge_index])
+			.sum::<i64>();
+		assert_eq!(crossing, expected);
+	}
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn same_neighbours<A: GraphView, B: GraphView<Weight = A::Weight>>(a: &A, b: &B) -> bool
+	where A::Weight: PartialEq
+{
+	a.vertexes() == b.vertexes() && a.edge_index_bound() == b.edge_index_bound()
+		&& (0..a.vertexes()).all(|v| a.neighbours(v).eq(b.neighbours(v)))
+}
+
+#[test]
+fn csr_keeps_the_graph() {
+	let mut rng = Rng::new(381);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 15, true);
+		let csr = CsrGraph::from_graph(&graph);
+		assert!(same_neighbours(&graph, &csr), "{}", describe(&graph));
+		assert!(same_neighbours(&graph, &csr.to_graph()), "{}", describe(&graph));
+		for v in 0..graph.vertexes() {
+			assert_eq!(GraphView::degree(&csr, v), graph.edges[v].len());
+		}
+	}
+}
+
+#[test]
+fn csr_from_arcs_matches_graph() {
+	let mut rng = Rng::new(382);
+	for _ in 0..CASES {
+		let n = 1 + rng.below(8);
+		let directed = rng.chance(0.5);
+		let arcs = (0..rng.below(12))
+			.map(|_| (rng.below(n), rng.below(n), rng.range(0, 9)))
+			.collect::<Vec<_>>();
+		let mut graph = WeightedGraph::new(n);
+		for &(from, to, weight) in &arcs {
+			if directed {
+				graph.add_weighted_directed_edge(from, to, weight);
+			} else {
+				graph.add_weighted_undirected_edge(from, to, weight);
+			}
+		}
+		// Undirected edges from both ends may come in another order, compare sorted lists
+		let csr = CsrGraph::from_arcs(n, &arcs, directed);
+		for v in 0..n {
+			let key = |edge: &WeightedEdge<i64>| (edge.edge_index, edge.to, edge.weight);
+			let mut expected = graph.edges[v].clone();
+			let mut actual = csr.edges_of(v).to_vec();
+			expected.sort_by_key(key);
+			actual.sort_by_key(key);
+			assert_eq!(actual, expected);
+		}
+	}
+}
+
+#[test]
+fn csr_reversed_matches_reversed() {
+	let mut rng = Rng::new(383);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 10, 15);
+		let reversed = CsrGraph::reversed(&graph);
+		let expected = graph.reversed();
+		for v in 0..graph.vertexes() {
+			let mut actual = reversed.edges_of(v).iter().map(|edge| (edge.edge_index, edge.to)).collect::<Vec<_>>();
+			let mut wanted = expected.edges[v].iter().map(|edge| (edge.edge_index, edge.to)).collect::<Vec<_>>();
+			actual.sort();
+			wanted.sort();
+			assert_eq!(actual, wanted, "{}", describe(&graph));
+		}
+	}
+}
+
+#[test]
+fn dfs_on_csr_matches_graph() {
+	let mut rng = Rng::new(384);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 14, true);
+		let csr = CsrGraph::from_graph(&graph);
+		let mut dfs = DFSSpace::new(&csr);
+		assert_eq!(dfs.find_bridges(&csr), DFSSpace::new(&graph).find_bridges(&graph));
+		let (points, blocks) = DFSSpace::new(&csr).find_cutting_points_with_components(&csr);
+		let (expected_points, expected_blocks) = DFSSpace::new(&graph).find_cutting_points_with_components(&graph);
+		assert_eq!(points, expected_points);
+		assert_eq!(blocks.component_map, expected_blocks.component_map);
+
+		let directed = random_directed_graph(&mut rng, 10, 14);
+		let csr = CsrGraph::from_graph(&directed);
+		let (_, components) = DFSSpace::new(&csr).condensation(&csr);
+		let (_, expected) = DFSSpace::new(&directed).condensation(&directed);
+		assert_eq!(components.component_map, expected.component_map, "{}", describe(&directed));
+	}
+}
+
+/// Floyd–Warshall over the edges of a weighted graph
+fn all_distances(graph: &WeightedGraph<i64>) -> Vec<Vec<Option<i64>>> {
+	let n = graph.vertexes();
+	let mut distance = vec![vec![None; n]; n];
+	for (v, row) in distance.iter_mut().enumerate() {
+		row[v] = Some(0);
+		for edge in &graph.edges[v] {
+			if row[edge.to].is_none_or(|d| edge.weight < d) {
+				row[edge.to] = Some(edge.weight);
+			}
+		}
+	}
+	for k in 0..n {
+		for i in 0..n {
+			for j in 0..n {
+				if let (Some(a), Some(b)) = (distance[i][k], distance[k][j]) {
+					if distance[i][j].is_none_or(|d| a + b < d) {
+						distance[i][j] = Some(a + b);
+					}
+				}
+			}
+		}
+	}
+	distance
+}
+
+/// The parent edges should form paths of exactly the reported length
+fn check_paths<D: Copy + TryInto<i64>>(graph: &WeightedGraph<i64>, paths: &ShortestPaths<D>, weighted: bool) {
+	for v in 0..graph.vertexes() {
+		let Some(path) = paths.path_to(v) else {
+			assert!(paths.distance[v].is_none());
+			continue;
+		};
+		let edges = paths.edges_to(v).unwrap();
+		assert_eq!((path[0], path.len()), (paths.source, edges.len() + 1));
+		let mut length = 0;
+		for (step, &edge_index) in path.windows(2).zip(&edges) {
+			let edge = graph.edges[step[0]].iter()
+				.find(|edge| edge.edge_index == edge_index && edge.to == step[1])
+				.expect("Path should follow edges");
+			length += if weighted { edge.weight } else { 1 };
+		}
+		assert_eq!(Some(length), paths.distance[v].and_then(|d| d.try_into().ok()));
+	}
+}
+
+#[test]
+fn bfs_matches_floyd_warshall() {
+	let mut rng = Rng::new(385);
+	for _ in 0..CASES {
+		let graph = with_weights(&random_directed_graph(&mut rng, 9, 16), |_| 1i64);
+		let distance = all_distances(&graph);
+		let csr = CsrGraph::from_graph(&graph);
+		for (source, row) in distance.iter().enumerate() {
+			let paths = bfs(&csr, source);
+			let expected = row.iter().map(|d| d.map(|d| d as usize)).collect::<Vec<_>>();
+			assert_eq!(paths.distance, expected);
+			check_paths(&graph, &paths, false);
+		}
+	}
+}
+
+#[test]
+fn dijkstra_matches_floyd_warshall() {
+	let mut rng = Rng::new(386);
+	for _ in 0..CASES {
+		let structure = if rng.chance(0.5) {
+			random_directed_graph(&mut rng, 9, 16)
+		} else {
+			random_multigraph(&mut rng, 9, 16, true)
+		};
+		let graph = with_random_weights(&structure, 0, 20, &mut rng);
+		let distance = all_distances(&graph);
+		for (source, row) in distance.iter().enumerate() {
+			let paths = dijkstra(&graph, source);
+			assert_eq!(&paths.distance, row, "{}", describe(&structure));
+			check_paths(&graph, &paths, true);
+		}
+	}
+}
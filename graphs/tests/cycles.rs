@@ -0,0 +1,199 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn assert_valid_cycle<T>(graph: &WeightedGraph<T>, cycle: &Cycle, directed: bool)
+	where T: Copy + std::fmt::Debug
+{
+	let len = cycle.vertexes.len();
+	assert!(len > 0);
+	assert_eq!(cycle.edges.len(), len);
+	let mut distinct = cycle.vertexes.clone();
+	distinct.sort();
+	distinct.dedup();
+	assert_eq!(distinct.len(), len, "{:?}", cycle);
+	let mut edges = cycle.edges.clone();
+	edges.sort();
+	edges.dedup();
+	assert_eq!(edges.len(), len, "{:?}", cycle);
+	for (i, &e) in cycle.edges.iter().enumerate() {
+		let (from, to) = (cycle.vertexes[i], cycle.vertexes[(i + 1) % len]);
+		let joined = |a: usize, b: usize| graph.edges[a].iter().any(|edge| edge.edge_index == e && edge.to == b);
+		assert!(joined(from, to) || !directed && joined(to, from), "{:?}", cycle);
+	}
+}
+
+/// Simple cycles of a directed graph by plain DFS from their smallest vertex
+fn naive_cycles(graph: &Graph) -> Vec<Cycle> {
+	fn extend(graph: &Graph, start: usize, vertexes: &mut Vec<usize>, edges: &mut Vec<usize>, cycles: &mut Vec<Cycle>) {
+		let v = *vertexes.last().unwrap();
+		for edge in &graph.edges[v] {
+			edges.push(edge.edge_index);
+			if edge.to == start {
+				cycles.push(Cycle { vertexes: vertexes.clone(), edges: edges.clone() });
+			} else if edge.to > start && !vertexes.contains(&edge.to) {
+				vertexes.push(edge.to);
+				extend(graph, start, vertexes, edges, cycles);
+				vertexes.pop();
+			}
+			edges.pop();
+		}
+	}
+	let mut cycles = Vec::new();
+	for start in 0..graph.vertexes() {
+		extend(graph, start, &mut vec![start], &mut Vec::new(), &mut cycles);
+	}
+	cycles
+}
+
+fn sorted(mut cycles: Vec<Cycle>) -> Vec<(Vec<usize>, Vec<usize>)> {
+	let mut result = cycles.drain(..).map(|cycle| (cycle.vertexes, cycle.edges)).collect::<Vec<_>>();
+	result.sort();
+	result
+}
+
+#[test]
+fn find_any_cycle() {
+	let mut rng = Rng::new(490);
+	for _ in 0..CASES {
+		let directed = random_directed_graph(&mut rng, 10, 14);
+		match find_cycle(&directed, true) {
+			Some(cycle) => assert_valid_cycle(&directed, &cycle, true),
+			None => assert!(DFSSpace::new(&directed).test_acyclic(&directed), "{}", describe(&directed)),
+		}
+		let undirected = random_multigraph(&mut rng, 10, 12, true);
+		let forest = undirected.edges() + count_components(&undirected, |_| true, |_| true) == undirected.vertexes();
+		match find_cycle(&undirected, false) {
+			Some(cycle) => assert_valid_cycle(&undirected, &cycle, false),
+			None => assert!(forest, "{}", describe(&undirected)),
+		}
+	}
+}
+
+#[test]
+fn shortest_cycles() {
+	let mut rng = Rng::new(491);
+	for _ in 0..CASES {
+		for directed in [false, true] {
+			let graph = if directed { random_directed_graph(&mut rng, 10, 16) } else { random_multigraph(&mut rng, 10, 14, true) };
+			// The shortest cycle through an edge closes a shortest path between its ends avoiding it
+			let expected = graph.edge_ends().into_iter()
+				.enumerate()
+				.filter_map(|(e, (from, to))| {
+					let without = EdgeSubgraph::new(&graph, |edge| edge != e);
+					bfs(&without, to).distance[from].map(|d| d + 1)
+				})
+				.min();
+			assert_eq!(girth(&graph, directed), expected, "{}", describe(&graph));
+			if let Some(cycle) = shortest_cycle(&graph, directed) {
+				assert_valid_cycle(&graph, &cycle, directed);
+			}
+		}
+	}
+	assert_eq!(girth(&petersen_graph(), false), Some(5));
+	assert_eq!(girth(&hypercube_graph(4), false), Some(4));
+	assert_eq!(girth(&generalized_petersen_graph(12, 5), false), Some(6));
+	assert_eq!(girth(&random_tree(20, &mut rng), false), None);
+}
+
+#[test]
+fn cycle_basis_spans_the_cycle_space() {
+	let mut rng = Rng::new(492);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 16, true);
+		let basis = cycle_basis(&graph);
+		let components = count_components(&graph, |_| true, |_| true);
+		assert_eq!(basis.len(), graph.edges() + components - graph.vertexes());
+		// Independent over GF(2): the rank of the edge sets is full
+		let mut rows = Vec::new();
+		for cycle in &basis {
+			assert_valid_cycle(&graph, cycle, false);
+			let mut row = cycle.edges.iter().fold(0_u64, |row, &e| row | 1 << e);
+			for &pivot in &rows {
+				row = row.min(row ^ pivot);
+			}
+			assert_ne!(row, 0, "{}", describe(&graph));
+			rows.push(row);
+			rows.sort_by(|a: &u64, b| b.cmp(a));
+		}
+	}
+}
+
+#[test]
+fn johnson_matches_naive_enumeration() {
+	let mut rng = Rng::new(493);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 7, 12);
+		let cycles = simple_cycles(&graph);
+		for cycle in &cycles {
+			assert_valid_cycle(&graph, cycle, true);
+		}
+		assert_eq!(sorted(cycles), sorted(naive_cycles(&graph)), "{}", describe(&graph));
+	}
+	let mut complete = Graph::new(5);
+	for u in 0..5 {
+		for v in (0..5).filter(|&v| v != u) {
+			complete.add_directed_edge(u, v);
+		}
+	}
+	// C(5, k) (k - 1)! cycles of length k
+	assert_eq!(simple_cycles(&complete).len(), 10 + 20 + 30 + 24);
+}
+
+#[test]
+fn minimum_mean_cycle_is_optimal() {
+	let mut rng = Rng::new(494);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 7, 12);
+		let weighted = with_random_weights(&graph, -10, 10, &mut rng);
+		let weight_of = |cycle: &Cycle| cycle.edges.iter()
+			.zip(&cycle.vertexes)
+			.map(|(&e, &v)| weighted.edges[v].iter().find(|edge| edge.edge_index == e).unwrap().weight)
+			.sum::<i64>();
+		let cycles = naive_cycles(&graph);
+		match minimum_mean_cycle(&weighted) {
+			None => assert!(cycles.is_empty()),
+			Some((weight, cycle)) => {
+				assert_valid_cycle(&weighted, &cycle, true);
+				assert_eq!(weight, weight_of(&cycle));
+				let len = cycle.edges.len() as i64;
+				for other in &cycles {
+					assert!(weight * other.edges.len() as i64 <= weight_of(other) * len, "{}", describe(&graph));
+				}
+			}
+		}
+	}
+}
+
+#[test]
+fn negative_cycles_match_minimum_mean() {
+	let mut rng = Rng::new(495);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 7, 12);
+		let weighted = with_random_weights(&graph, -4, 10, &mut rng);
+		let has_negative_cycle = minimum_mean_cycle(&weighted).is_some_and(|(weight, _)| weight < 0);
+		match find_negative_cycle(&weighted) {
+			None => assert!(!has_negative_cycle, "{}", describe(&graph)),
+			Some(cycle) => {
+				assert_valid_cycle(&weighted, &cycle, true);
+				let weight = cycle.edges.iter()
+					.zip(&cycle.vertexes)
+					.map(|(&e, &v)| weighted.edges[v].iter().find(|edge| edge.edge_index == e).unwrap().weight)
+					.sum::<i64>();
+				assert!(weight < 0, "{:?} {}", cycle, describe(&graph));
+			}
+		}
+	}
+	let cycle_of = |arcs: &[(usize, usize, i64)]| {
+		let mut graph = WeightedGraph::new(3);
+		for &(from, to, weight) in arcs {
+			graph.add_weighted_directed_edge(from, to, weight);
+		}
+		find_negative_cycle(&graph).map(|cycle| cycle.edges)
+	};
+	assert_eq!(cycle_of(&[(0, 1, -5), (1, 2, 2), (2, 0, 2)]).map(|edges| edges.len()), Some(3));
+	assert_eq!(cycle_of(&[(0, 1, -5), (1, 2, 2), (2, 0, 3)]), None);
+	assert_eq!(cycle_of(&[(0, 1, 1), (1, 1, -1)]), Some(vec![1]));
+	assert_eq!(cycle_of(&[(0, 1, -5), (1, 2, -5)]), None);
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn random_dag_with_parallel_edges(rng: &mut Rng) -> Graph {
+	let n = rng.below(9) + 1;
+	let (mut graph, order) = random_dag(n, rng.next_f64() * 0.6, rng);
+	for _ in 0..rng.below(3) {
+		let (a, b) = (rng.below(n), rng.below(n));
+		if a != b {
+			let (a, b) = if order.iter().position(|&v| v == a) < order.iter().position(|&v| v == b) { (a, b) } else { (b, a) };
+			graph.add_directed_edge(a, b);
+		}
+	}
+	graph
+}
+
+/// Every path from `from` (as lists of vertexes)
+fn all_paths(graph: &Graph, from: usize) -> Vec<Vec<usize>> {
+	let mut paths = vec![vec![from]];
+	for edge in &graph.edges[from] {
+		paths.extend(all_paths(graph, edge.to).into_iter().map(|mut path| {
+			path.insert(0, from);
+			path
+		}));
+	}
+	paths
+}
+
+#[test]
+fn transitive_closure_matches_reachability() {
+	let mut rng = Rng::new(470);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 12, 20);
+		let closure = transitive_closure(&graph);
+		let expected = reachability(&graph);
+		for (u, row) in expected.iter().enumerate() {
+			for (v, &reached) in row.iter().enumerate() {
+				assert_eq!(closure.reaches(u, v), reached, "{}", describe(&graph));
+			}
+			let reachable = (0..graph.vertexes()).filter(|&v| row[v]).collect::<Vec<_>>();
+			assert_eq!(closure.reachable_from(u), reachable);
+		}
+	}
+}
+
+#[test]
+fn transitive_reduction_is_minimal() {
+	let mut rng = Rng::new(471);
+	for _ in 0..CASES {
+		let graph = random_dag_with_parallel_edges(&mut rng);
+		let reduction = transitive_reduction(&graph).unwrap();
+		assert_eq!(reachability(&reduction), reachability(&graph), "{}", describe(&graph));
+		let ends = graph.edge_ends();
+		for (e, (from, to)) in reduction.edge_ends().into_iter().enumerate() {
+			assert!(ends.contains(&(from, to)));
+			assert_ne!(reachability(&reduction.without_edges(&[e])), reachability(&graph), "{}", describe(&graph));
+		}
+	}
+	let mut cyclic = path_graph(3);
+	cyclic.add_directed_edge(2, 2);
+	assert!(transitive_reduction(&cyclic).is_none());
+}
+
+#[test]
+fn longest_paths_and_path_counts() {
+	let mut rng = Rng::new(472);
+	for _ in 0..CASES {
+		let graph = random_dag_with_parallel_edges(&mut rng);
+		let n = graph.vertexes();
+		let paths = (0..n).flat_map(|v| all_paths(&graph, v)).collect::<Vec<_>>();
+
+		let longest = longest_path(&graph).unwrap();
+		assert!(paths.contains(&longest), "{}", describe(&graph));
+		assert_eq!(longest.len(), paths.iter().map(|path| path.len()).max().unwrap());
+
+		let weighted = with_random_weights(&graph, -5, 10, &mut rng);
+		let (weight, path) = longest_weighted_path(&weighted).unwrap();
+		let weight_of = |path: &[usize]| path.windows(2)
+			.map(|pair| weighted.edges[pair[0]].iter().filter(|e| e.to == pair[1]).map(|e| e.weight).max().unwrap())
+			.sum::<i64>();
+		assert_eq!(weight_of(&path), weight);
+		assert_eq!(weight, paths.iter().map(|path| weight_of(path)).max().unwrap(), "{}", describe(&graph));
+
+		let counts = count_paths_between_all(&graph, 1_000_000_007).unwrap();
+		for (u, row) in counts.iter().enumerate() {
+			let from_u = all_paths(&graph, u);
+			for (v, &count) in row.iter().enumerate() {
+				assert_eq!(count, from_u.iter().filter(|path| path.last() == Some(&v)).count() as u64);
+			}
+		}
+	}
+}
+
+#[test]
+fn path_counts_modulo() {
+	// 2^40 paths through a chain of doubled edges
+	let mut graph = Graph::new(41);
+	for v in 0..40 {
+		graph.add_directed_edge(v, v + 1);
+		graph.add_directed_edge(v, v + 1);
+	}
+	assert_eq!(count_paths(&graph, 0, 1_000_000_007).unwrap()[40], (1u64 << 40) % 1_000_000_007);
+	assert_eq!(count_paths(&graph, 3, u64::MAX).unwrap()[40], 1 << 37);
+	assert_eq!(count_paths(&graph, 3, u64::MAX).unwrap()[2], 0);
+	assert_eq!(longest_path(&graph).unwrap(), (0..=40).collect::<Vec<_>>());
+	graph.add_directed_edge(40, 0);
+	assert!(count_paths(&graph, 0, 7).is_none());
+	assert!(longest_path(&graph).is_none());
+}
+
+#[test]
+fn dominators_match_brute_force() {
+	let mut rng = Rng::new(473);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 10, 20);
+		let n = graph.vertexes();
+		let root = rng.below(n);
+		let tree = DominatorTree::new(&graph, root);
+		let reachable = reachability(&graph)[root].clone();
+		// `d` dominates `v` if `v` can't be reached from the root avoiding `d`
+		let dominates = |d: usize, v: usize| {
+			if !reachable[v] || !reachable[d] {
+				return false;
+			}
+			if d == root || d == v {
+				return true;
+			}
+			let without = VertexSubgraph::new(&graph, |u| u != d);
+			bfs(&without, root).distance[v].is_none()
+		};
+		for (v, &reached) in reachable.iter().enumerate() {
+			assert_eq!(tree.is_reachable(v), reached);
+			for d in 0..n {
+				assert_eq!(tree.dominates(d, v), dominates(d, v), "{} from {}: {} over {}", describe(&graph), root, d, v);
+			}
+			match tree.idom[v] {
+				None => assert!(v == root || !reached),
+				// The immediate dominator is dominated by all the strict dominators
+				Some(d) => assert!((0..n).all(|e| e == v || !dominates(e, v) || dominates(e, d)), "{}", describe(&graph)),
+			}
+			let mut dominators = tree.dominators(v);
+			dominators.sort();
+			assert_eq!(dominators, (0..n).filter(|&d| dominates(d, v)).collect::<Vec<_>>());
+		}
+	}
+}
@@ -0,0 +1,327 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+#[test]
+fn connected_components_match_union_find() {
+	let mut rng = Rng::new(11);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 12, true);
+		let components = DFSSpace::new(&graph).find_connected_components(&graph);
+		let mut union_find = UnionFind::new(graph.vertexes());
+		for (from, to) in graph.edge_ends() {
+			union_find.union(from, to);
+		}
+		for u in 0..graph.vertexes() {
+			for v in 0..graph.vertexes() {
+				assert_eq!(components.component_of(u) == components.component_of(v), union_find.find(u) == union_find.find(v),
+				           "{}", describe(&graph));
+			}
+		}
+	}
+}
+
+#[test]
+fn bridges_match_edge_removal() {
+	let mut rng = Rng::new(12);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 12, true);
+		let components = count_components(&graph, |_| true, |_| true);
+		let mut expected = (0..graph.edges())
+			.filter(|&e| count_components(&graph, |f| f != e, |_| true) > components)
+			.collect::<Vec<_>>();
+		let mut bridges = DFSSpace::new(&graph).find_bridges(&graph);
+		bridges.sort();
+		expected.sort();
+		assert_eq!(bridges, expected, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn parallel_edges_are_not_bridges() {
+	// A double edge and a single one, the search starting at either end of the double edge
+	for (a, b, c) in [(0, 1, 2), (1, 0, 2), (2, 1, 0)] {
+		let mut graph = Graph::new(3);
+		graph.add_undirected_edge(a, b);
+		graph.add_undirected_edge(b, a);
+		graph.add_undirected_edge(b, c);
+		assert_eq!(DFSSpace::new(&graph).find_bridges(&graph), vec![2]);
+	}
+
+	// A loop next to a bridge doesn't save it
+	let mut graph = Graph::new(2);
+	graph.add_undirected_edge(0, 0);
+	graph.add_undirected_edge(0, 1);
+	assert_eq!(DFSSpace::new(&graph).find_bridges(&graph), vec![1]);
+}
+
+#[test]
+fn edge_biconnected_components_match_bridges() {
+	let mut rng = Rng::new(13);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 12, true);
+		let bridges = DFSSpace::new(&graph).find_bridges(&graph);
+		let mut union_find = UnionFind::new(graph.vertexes());
+		for (index, (from, to)) in graph.edge_ends().into_iter().enumerate() {
+			if !bridges.contains(&index) {
+				union_find.union(from, to);
+			}
+		}
+		let components = find_edge_biconnected_components(&graph);
+		for u in 0..graph.vertexes() {
+			for v in 0..graph.vertexes() {
+				assert_eq!(components.component_of(u) == components.component_of(v), union_find.find(u) == union_find.find(v),
+				           "{}", describe(&graph));
+			}
+		}
+	}
+}
+
+#[test]
+fn cutting_points_match_vertex_removal() {
+	let mut rng = Rng::new(14);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 12, true);
+		let components = count_components(&graph, |_| true, |_| true);
+		let expected = (0..graph.vertexes())
+			.filter(|&v| count_components(&graph, |_| true, |u| u != v) > components)
+			.collect::<Vec<_>>();
+		let (cutting_points, _) = DFSSpace::new(&graph).find_cutting_points_with_components(&graph);
+		assert_eq!(cutting_points, expected, "{}", describe(&graph));
+	}
+}
+
+/// Two edges are in one block iff no vertex separates them: splitting any vertex into
+/// a separate copy for every incident edge leaves them connected. A loop is a block of its own
+fn brute_force_blocks(graph: &Graph) -> Vec<Vec<usize>> {
+	let ends = graph.edge_ends();
+	let m = ends.len();
+	let mut together = vec![vec![true; m]; m];
+	for split in 0..graph.vertexes() {
+		// Vertexes `0..n` and a copy `n + e` of `split` for every edge `e`
+		let mut union_find = UnionFind::new(graph.vertexes() + m);
+		let copy = |v: usize, e: usize| if v == split { graph.vertexes() + e } else { v };
+		for (e, &(from, to)) in ends.iter().enumerate() {
+			union_find.union(copy(from, e), copy(to, e));
+		}
+		let representative = (0..m).map(|e| union_find.find(copy(ends[e].0, e))).collect::<Vec<_>>();
+		for (e, row) in together.iter_mut().enumerate() {
+			for (f, same) in row.iter_mut().enumerate() {
+				*same &= representative[e] == representative[f];
+			}
+		}
+	}
+	let is_loop = |e: usize| ends[e].0 == ends[e].1;
+	let mut blocks: Vec<Vec<usize>> = Vec::new();
+	for (e, connected) in together.iter().enumerate() {
+		match blocks.iter_mut().find(|block| !is_loop(e) && !is_loop(block[0]) && connected[block[0]]) {
+			Some(block) => block.push(e),
+			None => blocks.push(vec![e]),
+		}
+	}
+	blocks
+}
+
+#[test]
+fn biconnected_components_match_vertex_splitting() {
+	let mut rng = Rng::new(15);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 8, 11, true);
+		let (_, blocks) = DFSSpace::new(&graph).find_cutting_points_with_components(&graph);
+		assert_eq!(normalized(&blocks.component_list), normalized(&brute_force_blocks(&graph)), "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn condensation_matches_reachability() {
+	let mut rng = Rng::new(16);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 9, 14);
+		let reach = reachability(&graph);
+		let (condensed, components) = DFSSpace::new(&graph).condensation(&graph);
+		for (u, row) in reach.iter().enumerate() {
+			for (v, &forward) in row.iter().enumerate() {
+				assert_eq!(components.component_of(u) == components.component_of(v), forward && reach[v][u],
+				           "{}", describe(&graph));
+			}
+		}
+
+		let mut expected = graph.edge_ends().into_iter()
+			.map(|(from, to)| (components.component_of(from), components.component_of(to)))
+			.filter(|(from, to)| from != to)
+			.collect::<Vec<_>>();
+		expected.sort();
+		expected.dedup();
+		let mut edges = condensed.edge_ends();
+		edges.sort();
+		assert_eq!(edges, expected, "{}", describe(&graph));
+		// Components are numbered in a topological order
+		assert!(edges.iter().all(|(from, to)| from < to), "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn topological_sort_matches_reachability() {
+	let mut rng = Rng::new(17);
+	for case in 0..CASES {
+		let graph = if case & 1 == 0 {
+			random_directed_graph(&mut rng, 9, 12)
+		} else {
+			let n = rng.below(10);
+			random_dag(n, rng.next_f64(), &mut rng).0
+		};
+		let reach = reachability(&graph);
+		let has_cycle = graph.edge_ends().into_iter().any(|(from, to)| reach[to][from]);
+		let (order, acyclic) = DFSSpace::new(&graph).topological_sort(&graph);
+		assert_eq!(acyclic, !has_cycle, "{}", describe(&graph));
+		if acyclic {
+			let mut position = vec![0; graph.vertexes()];
+			for (i, &v) in order.iter().enumerate() {
+				position[v] = i;
+			}
+			assert!(graph.edge_ends().into_iter().all(|(from, to)| position[from] < position[to]), "{}", describe(&graph));
+		}
+	}
+}
+
+#[test]
+fn tarjan_condensation_matches_kosaraju() {
+	let mut rng = Rng::new(18);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 10, 16);
+		let (kosaraju_graph, kosaraju) = DFSSpace::new(&graph).condensation(&graph);
+		let (tarjan_graph, tarjan) = DFSSpace::new(&graph).tarjan_condensation(&graph);
+		assert_eq!(normalized(&tarjan.component_list), normalized(&kosaraju.component_list), "{}", describe(&graph));
+		assert_eq!(tarjan_graph.edges(), kosaraju_graph.edges());
+		assert!(tarjan_graph.edge_ends().iter().all(|(from, to)| from < to), "{}", describe(&graph));
+	}
+}
+
+/// `u` and `v` are in different components once `w` is removed
+fn separates(graph: &Graph, w: usize, u: usize, v: usize) -> bool {
+	let mut union_find = UnionFind::new(graph.vertexes());
+	for (from, to) in graph.edge_ends() {
+		if from != w && to != w {
+			union_find.union(from, to);
+		}
+	}
+	union_find.find(u) != union_find.find(v)
+}
+
+#[test]
+fn block_cut_tree_matches_vertex_removal() {
+	let mut rng = Rng::new(18);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 12, true);
+		let n = graph.vertexes();
+		let tree = BlockCutTree::new(&graph);
+		let (cutting_points, _) = DFSSpace::new(&graph).find_cutting_points_with_components(&graph);
+		assert_eq!(tree.cut_vertexes, cutting_points);
+
+		// Every edge is in exactly one block, which contains its ends
+		let mut block_of_edge = vec![None; graph.edges()];
+		for (b, block) in tree.blocks.iter().enumerate() {
+			for &e in &block.edges {
+				assert_eq!(block_of_edge[e].replace(b), None);
+			}
+		}
+		for (e, (from, to)) in graph.edge_ends().into_iter().enumerate() {
+			let block = &tree.blocks[block_of_edge[e].unwrap()];
+			assert!(block.vertexes.contains(&from) && block.vertexes.contains(&to), "{}", describe(&graph));
+		}
+		// Blocks and cut vertexes alternate in a forest
+		assert_eq!(tree.tree.edges() + count_components(&graph, |_| true, |_| true), tree.tree.vertexes());
+
+		for u in 0..n {
+			for v in 0..n {
+				let connected = !separates(&graph, n, u, v);
+				let separators = (0..n)
+					.filter(|&w| w != u && w != v && separates(&graph, w, u, v))
+					.collect::<Vec<_>>();
+				assert_eq!(tree.are_connected(u, v), connected);
+				assert_eq!(tree.same_block(u, v), connected && separators.is_empty(), "{} {} {}", u, v, describe(&graph));
+				let mut found = tree.separating_vertexes(u, v);
+				if let Some(found) = &mut found {
+					found.sort();
+				}
+				assert_eq!(found, connected.then_some(separators), "{} {} {}", u, v, describe(&graph));
+			}
+		}
+	}
+}
+
+fn is_bridgeless_and_connected(graph: &Graph) -> bool {
+	count_components(graph, |_| true, |_| true) <= 1 && DFSSpace::new(graph).find_bridges(graph).is_empty()
+}
+
+/// Fewest edges (parallel ones allowed) to make the graph connected and bridgeless, by trying all multisets
+fn brute_force_augmentation(graph: &Graph) -> usize {
+	let n = graph.vertexes();
+	let pairs = (0..n).flat_map(|u| (u + 1..n).map(move |v| (u, v))).collect::<Vec<_>>();
+	fn search(graph: &Graph, pairs: &[(usize, usize)], from: usize, left: usize) -> bool {
+		if left == 0 {
+			return is_bridgeless_and_connected(graph);
+		}
+		(from..pairs.len()).any(|i| {
+			let mut extended = graph.clone();
+			extended.add_undirected_edge(pairs[i].0, pairs[i].1);
+			search(&extended, pairs, i, left - 1)
+		})
+	}
+	(0..).find(|&k| search(graph, &pairs, 0, k)).unwrap()
+}
+
+#[test]
+fn bridge_tree_matches_edge_removal() {
+	let mut rng = Rng::new(19);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 12, true);
+		let n = graph.vertexes();
+		let tree = BridgeTree::new(&graph);
+		assert_eq!(tree.components.component_map, find_edge_biconnected_components(&graph).component_map);
+		assert_eq!(tree.tree.edges(), tree.bridges.len());
+		for u in 0..n {
+			for v in 0..n {
+				let connected = !separates(&graph, n, u, v);
+				let separating = tree.bridges.iter()
+					.copied()
+					.filter(|&e| {
+						let mut union_find = UnionFind::new(n);
+						for (index, (from, to)) in graph.edge_ends().into_iter().enumerate() {
+							if index != e {
+								union_find.union(from, to);
+							}
+						}
+						union_find.find(u) != union_find.find(v)
+					})
+					.collect::<Vec<_>>();
+				assert_eq!(tree.are_connected(u, v), connected);
+				assert_eq!(tree.are_two_edge_connected(u, v), connected && separating.is_empty());
+				assert_eq!(tree.bridges_between(u, v), connected.then_some(separating.len()), "{} {} {}", u, v, describe(&graph));
+				let mut on_path = tree.bridges_on_path(u, v);
+				if let Some(on_path) = &mut on_path {
+					on_path.sort();
+				}
+				assert_eq!(on_path, connected.then_some(separating), "{} {} {}", u, v, describe(&graph));
+			}
+		}
+	}
+}
+
+#[test]
+fn bridge_augmentation_is_minimum() {
+	let mut rng = Rng::new(20);
+	for case in 0..CASES {
+		let graph = random_multigraph(&mut rng, if case.is_multiple_of(3) { 5 } else { 12 }, 12, true);
+		let mut augmented = graph.clone();
+		let added = BridgeTree::new(&graph).augmentation();
+		for &(u, v) in &added {
+			augmented.add_undirected_edge(u, v);
+		}
+		assert!(is_bridgeless_and_connected(&augmented), "{:?} {}", added, describe(&graph));
+		if graph.vertexes() <= 5 {
+			assert_eq!(added.len(), brute_force_augmentation(&graph), "{}", describe(&graph));
+		}
+	}
+}
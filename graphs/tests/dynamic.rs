@@ -0,0 +1,107 @@
+mod common;
+
+use std::collections::HashMap;
+use graphs::*;
+
+/// Neighbours of every live vertex as sorted `(neighbour, edge, weight)` lists
+fn adjacency(graph: &DynamicGraph<i64>) -> HashMap<VertexHandle, Vec<(VertexHandle, EdgeHandle, i64)>> {
+	graph.vertex_handles()
+		.map(|v| {
+			let mut neighbours = graph.neighbours(v).collect::<Vec<_>>();
+			neighbours.sort();
+			(v, neighbours)
+		})
+		.collect()
+}
+
+/// The same from the list of live edges
+fn expected_adjacency(graph: &DynamicGraph<i64>, edges: &HashMap<EdgeHandle, (VertexHandle, VertexHandle, i64)>)
+	-> HashMap<VertexHandle, Vec<(VertexHandle, EdgeHandle, i64)>>
+{
+	let mut adjacency = graph.vertex_handles().map(|v| (v, Vec::new())).collect::<HashMap<_, _>>();
+	for (&edge, &(from, to, weight)) in edges {
+		adjacency.get_mut(&from).unwrap().push((to, edge, weight));
+		if !graph.directed {
+			adjacency.get_mut(&to).unwrap().push((from, edge, weight));
+		}
+	}
+	adjacency.values_mut().for_each(|neighbours| neighbours.sort());
+	adjacency
+}
+
+#[test]
+fn random_updates_match_edge_list() {
+	let mut rng = Rng::new(71);
+	for case in 0..common::CASES / 3 {
+		let mut graph = DynamicGraph::new(case & 1 == 0);
+		let mut vertexes = Vec::new();
+		let mut removed_vertexes = Vec::new();
+		let mut edges = HashMap::new();
+		let mut removed_edges = Vec::new();
+		for _ in 0..200 {
+			match rng.below(10) {
+				0 | 1 => vertexes.push(graph.add_vertex()),
+				2 if !vertexes.is_empty() => {
+					let vertex = vertexes.swap_remove(rng.below(vertexes.len()));
+					assert!(graph.remove_vertex(vertex));
+					edges.retain(|_, &mut (from, to, _)| from != vertex && to != vertex);
+					removed_vertexes.push(vertex);
+				}
+				3..=6 if !vertexes.is_empty() => {
+					let (from, to) = (vertexes[rng.below(vertexes.len())], vertexes[rng.below(vertexes.len())]);
+					let weight = rng.range(-9, 9);
+					edges.insert(graph.add_edge(from, to, weight), (from, to, weight));
+				}
+				7 | 8 if !edges.is_empty() => {
+					let edge = *edges.keys().nth(rng.below(edges.len())).unwrap();
+					assert_eq!(graph.remove_edge(edge), Some(edges.remove(&edge).unwrap().2));
+					removed_edges.push(edge);
+				}
+				_ => {}
+			}
+			assert_eq!((graph.vertexes(), graph.edges()), (vertexes.len(), edges.len()));
+			// Reused slots don't revive old handles
+			assert!(removed_vertexes.iter().all(|&v| !graph.contains_vertex(v)));
+			assert!(removed_edges.iter().all(|&e| !graph.contains_edge(e) && graph.remove_edge(e).is_none()));
+		}
+		assert_eq!(adjacency(&graph), expected_adjacency(&graph, &edges));
+		for (&edge, &(from, to, weight)) in &edges {
+			assert_eq!(graph.edge_ends(edge), Some((from, to)));
+			assert_eq!(graph.weight(edge), Some(weight));
+		}
+
+		let (snapshot, snapshot_mapping) = graph.to_graph();
+		snapshot.validate_compact_enumeration();
+		assert_eq!((snapshot.vertexes(), snapshot.edges()), (vertexes.len(), edges.len()));
+		for (&edge, &(from, to, _)) in &edges {
+			let (from, to) = (snapshot_mapping.vertex(from).unwrap().index, snapshot_mapping.vertex(to).unwrap().index);
+			let ends = snapshot.edge_ends()[snapshot_mapping.edge(edge).unwrap().index];
+			assert!(ends == (from, to) || (!graph.directed && ends == (to, from)));
+		}
+
+		let mapping = graph.compact();
+		assert_eq!(mapping, snapshot_mapping);
+		assert_eq!((graph.vertex_capacity(), graph.edge_capacity()), (vertexes.len(), edges.len()));
+		let edges = edges.into_iter()
+			.map(|(edge, (from, to, weight))| (mapping.edge(edge).unwrap(), (mapping.vertex(from).unwrap(), mapping.vertex(to).unwrap(), weight)))
+			.collect::<HashMap<_, _>>();
+		assert!(edges.keys().all(|&e| graph.contains_edge(e)));
+		assert_eq!(adjacency(&graph), expected_adjacency(&graph, &edges));
+	}
+}
+
+#[test]
+fn graph_round_trip() {
+	let mut rng = Rng::new(72);
+	for _ in 0..common::CASES {
+		let graph = with_random_weights(&common::random_multigraph(&mut rng, 8, 12, true), 0, 9, &mut rng);
+		let dynamic = DynamicGraph::from_graph(&graph, false);
+		let (copy, _) = dynamic.to_graph();
+		assert_eq!(copy.edges, graph.edges.iter().map(|edges| {
+			// Undirected edges are added in the order of their indexes
+			let mut edges = edges.clone();
+			edges.sort_by_key(|edge| edge.edge_index);
+			edges
+		}).collect::<Vec<_>>());
+	}
+}
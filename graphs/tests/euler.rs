@@ -0,0 +1,104 @@
+mod common;
+
+use std::collections::HashSet;
+use graphs::*;
+use common::*;
+
+/// Whether the unused edges can all be walked starting at `v` (and ending at `end`, if given)
+fn can_walk(v: usize, used: &mut [bool], ends: &[(usize, usize)], directed: bool, end: Option<usize>) -> bool {
+	if used.iter().all(|&u| u) {
+		return end.is_none_or(|end| end == v);
+	}
+	for (e, &(from, to)) in ends.iter().enumerate() {
+		let next = if from == v { to } else if !directed && to == v { from } else { continue };
+		if !used[e] {
+			used[e] = true;
+			let found = can_walk(next, used, ends, directed, end);
+			used[e] = false;
+			if found {
+				return true;
+			}
+		}
+	}
+	false
+}
+
+fn assert_is_tour(graph: &Graph, tour: &EulerTour, directed: bool) {
+	let ends = graph.edge_ends();
+	assert_eq!(tour.edges.len(), graph.edges());
+	assert_eq!(tour.vertexes.len(), graph.edges() + 1);
+	assert_eq!(tour.edges.iter().collect::<HashSet<_>>().len(), graph.edges());
+	for (i, &e) in tour.edges.iter().enumerate() {
+		let step = (tour.vertexes[i], tour.vertexes[i + 1]);
+		assert!(step == ends[e] || (!directed && step == (ends[e].1, ends[e].0)), "{}", describe(graph));
+	}
+}
+
+#[test]
+fn euler_tours_match_exhaustive_search() {
+	let mut rng = Rng::new(41);
+	for case in 0..CASES * 4 {
+		let directed = case & 1 == 0;
+		let graph = if directed { random_directed_graph(&mut rng, 5, 7) } else { random_multigraph(&mut rng, 5, 7, true) };
+		let ends = graph.edge_ends();
+		let mut used = vec![false; ends.len()];
+		let n = graph.vertexes();
+		let has_path = ends.is_empty() || (0..n).any(|v| can_walk(v, &mut used, &ends, directed, None));
+		let has_circuit = ends.is_empty() || (0..n).any(|v| can_walk(v, &mut used, &ends, directed, Some(v)));
+
+		match euler_path(&graph, directed) {
+			Ok(tour) => assert_is_tour(&graph, &tour, directed),
+			Err(_) => assert!(!has_path, "{}", describe(&graph)),
+		}
+		assert_eq!(euler_path(&graph, directed).is_ok(), has_path, "{}", describe(&graph));
+		match euler_circuit(&graph, directed) {
+			Ok(tour) => {
+				assert_is_tour(&graph, &tour, directed);
+				assert_eq!(tour.vertexes.first(), tour.vertexes.last());
+			}
+			Err(_) => assert!(!has_circuit, "{}", describe(&graph)),
+		}
+		assert_eq!(euler_circuit(&graph, directed).is_ok(), has_circuit, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn de_bruijn_sequences_contain_every_word_once() {
+	for alphabet in 1..4 {
+		for order in 1..5 {
+			let sequence = de_bruijn_sequence(alphabet, order);
+			assert_eq!(sequence.len(), alphabet.pow(order as u32));
+			let words = (0..sequence.len())
+				.map(|i| (0..order).map(|j| sequence[(i + j) % sequence.len()]).collect::<Vec<_>>())
+				.collect::<HashSet<_>>();
+			assert_eq!(words.len(), sequence.len());
+		}
+	}
+}
+
+#[test]
+fn euler_errors() {
+	// Star with three leaves: every vertex has odd degree
+	let mut star = Graph::new(4);
+	for leaf in 1..4 {
+		star.add_undirected_edge(0, leaf);
+	}
+	assert_eq!(euler_path(&star, false), Err(EulerError::UnbalancedVertexes(vec![0, 1, 2, 3])));
+	assert_eq!(euler_circuit(&path_graph(3), false), Err(EulerError::UnbalancedVertexes(vec![0, 2])));
+	assert!(euler_path(&path_graph(3), false).is_ok());
+
+	// Two directed cycles without a common vertex are balanced but not connected
+	let mut cycles = Graph::new(4);
+	for (from, to) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+		cycles.add_directed_edge(from, to);
+	}
+	assert_eq!(euler_circuit(&cycles, true), Err(EulerError::Disconnected));
+	assert_eq!(euler_path(&cycles, true), Err(EulerError::Disconnected));
+
+	// Isolated vertexes don't matter
+	let mut graph = Graph::new(5);
+	graph.add_directed_edge(1, 3);
+	let tour = euler_path(&graph, true).unwrap();
+	assert_eq!(tour.vertexes, vec![1, 3]);
+	assert!(euler_circuit(&graph, true).is_err());
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+#[test]
+fn assignment_matches_brute_force() {
+	let mut rng = Rng::new(33);
+	for _ in 0..CASES {
+		let (a, b) = (rng.below(6) + 1, rng.below(6) + 1);
+		let (workers, jobs) = (a.min(b), a.max(b));
+		let costs = (0..workers).map(|_| (0..jobs).map(|_| rng.range(-10, 10)).collect()).collect::<Vec<Vec<i64>>>();
+		let totals = permutations(jobs).into_iter()
+			.map(|job_of| (0..workers).map(|i| costs[i][job_of[i]]).sum::<i64>())
+			.collect::<Vec<_>>();
+		let best = *totals.iter().min().unwrap();
+
+		let assignment = AssignmentProblem::from_weight_matrix(&costs).solve().unwrap();
+		assert_eq!(assignment.total_cost, best, "{:?}", costs);
+		assert_eq!((0..workers).map(|i| costs[i][assignment.job_of[i]]).sum::<i64>(), best);
+		let maximum = AssignmentProblem::from_weight_matrix(&costs).solve_maximizing().unwrap();
+		assert_eq!(maximum.total_cost, *totals.iter().max().unwrap(), "{:?}", costs);
+
+		// The same as a min-cost flow: source → workers → jobs → sink
+		let (source, sink) = (workers + jobs, workers + jobs + 1);
+		let mut network = FlowNetwork::new(workers + jobs + 2);
+		for (i, row) in costs.iter().enumerate() {
+			network.add_arc(source, i, 1, 0);
+			for (j, &cost) in row.iter().enumerate() {
+				network.add_arc(i, workers + j, 1, cost);
+			}
+		}
+		for j in 0..jobs {
+			network.add_arc(workers + j, sink, 1, 0);
+		}
+		let result = network.min_cost_max_flow(source, sink).unwrap();
+		assert_eq!((result.flow, result.cost), (workers as i64, best), "{:?}", costs);
+	}
+}
+
+#[test]
+fn maximum_flow_equals_minimum_cut() {
+	let mut rng = Rng::new(34);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 7, 14);
+		let n = graph.vertexes();
+		if n < 2 {
+			continue;
+		}
+		let capacities = with_random_weights(&graph, 0, 9, &mut rng);
+		let mut network = FlowNetwork::from_capacity_graph(&capacities);
+		let flow = network.min_cost_max_flow(0, n - 1).unwrap().flow;
+		let ends = graph.edge_ends();
+		// Cuts are sets of vertexes containing the source but not the sink
+		let minimum_cut = (0..1_usize << n)
+			.filter(|side| side & 1 == 1 && side >> (n - 1) & 1 == 0)
+			.map(|side| capacities.edges.iter().enumerate()
+				.flat_map(|(from, edges)| edges.iter().map(move |edge| (from, edge)))
+				.filter(|(from, edge)| side >> from & 1 == 1 && side >> edge.to & 1 == 0)
+				.map(|(_, edge)| edge.weight)
+				.sum::<i64>())
+			.min()
+			.unwrap();
+		assert_eq!(flow, minimum_cut, "{:?}", ends);
+	}
+}
+
+#[test]
+fn assignment_with_forbidden_pairs() {
+	let mut rng = Rng::new(35);
+	for _ in 0..CASES {
+		let (workers, jobs) = (rng.below(5) + 1, rng.below(5) + 1);
+		let mut graph = WeightedGraph::new(workers + jobs);
+		for _ in 0..rng.below(14) {
+			graph.add_weighted_undirected_edge(rng.below(workers), workers + rng.below(jobs), rng.range(-10, 10));
+		}
+		let problem = AssignmentProblem::from_bipartite_graph(&graph, workers);
+		let cost = |worker: usize, job: usize| graph.edges[worker].iter()
+			.filter(|edge| edge.to == workers + job)
+			.map(|edge| edge.weight)
+			.min();
+		let totals = permutations(jobs.max(workers)).into_iter()
+			.filter_map(|job_of| (0..workers).map(|i| cost(i, *job_of.get(i)?)).sum::<Option<i64>>())
+			.collect::<Vec<_>>();
+
+		let (minimum, maximum) = (problem.solve(), problem.solve_maximizing());
+		assert_eq!(minimum.as_ref().map(|a| a.total_cost), totals.iter().copied().min());
+		assert_eq!(maximum.as_ref().map(|a| a.total_cost), totals.iter().copied().max());
+		for assignment in minimum.iter().chain(&maximum) {
+			assert!(assignment.job_of.iter().all(|&job| job < jobs));
+			let sum = (0..workers).map(|i| cost(i, assignment.job_of[i]).expect("Only edges are assigned")).sum::<i64>();
+			assert_eq!(sum, assignment.total_cost);
+		}
+	}
+}
+
+#[test]
+fn assignment_without_a_perfect_matching() {
+	// Both workers can only do job 0
+	let costs = vec![vec![Some(1), None, None], vec![Some(2), None, None]];
+	let problem = AssignmentProblem::from_partial_matrix(&costs);
+	assert_eq!(problem.solve(), None);
+	assert_eq!(problem.solve_maximizing(), None);
+	assert_eq!(AssignmentProblem::from_weight_matrix(&[vec![1], vec![2]]).solve(), None);
+
+	let costs = vec![vec![Some(5), Some(1)], vec![None, Some(7)]];
+	let problem = AssignmentProblem::from_partial_matrix(&costs);
+	assert_eq!(problem.solve_maximizing(), Some(Assignment { total_cost: 12, job_of: vec![0, 1] }));
+}
+
+#[test]
+fn flow_errors() {
+	// source → 1 → sink only through infinite arcs
+	let mut network = FlowNetwork::new(3);
+	network.add_arc(0, 1, INFINITE_CAPACITY, 1);
+	network.add_arc(1, 2, INFINITE_CAPACITY, 1);
+	assert_eq!(network.clone().min_cost_max_flow(0, 2), Err(FlowError::Unbounded));
+	assert_eq!(network.min_cost_flow(0, 2, 5), Ok(FlowResult { flow: 5, cost: 10 }));
+
+	// A bounded arc in parallel is used first and the rest is still unbounded
+	let mut network = FlowNetwork::new(2);
+	network.add_arc(0, 1, 3, 0);
+	network.add_arc(0, 1, INFINITE_CAPACITY, 1);
+	assert_eq!(network.min_cost_max_flow(0, 1), Err(FlowError::Unbounded));
+	assert_eq!(network.flow[0], 3);
+
+	let mut network = FlowNetwork::new(3);
+	network.add_arc(0, 1, 1, 1);
+	network.add_arc(1, 2, 1, -3);
+	network.add_arc(2, 1, 1, 1);
+	assert_eq!(network.min_cost_max_flow(0, 1), Err(FlowError::NegativeCycle));
+
+	let mut network = FlowNetwork::new(2);
+	network.add_arc(0, 1, i64::MAX / 8, i64::MAX / 8);
+	assert_eq!(network.min_cost_max_flow(0, 1), Err(FlowError::CostOverflow));
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::collections::HashSet;
+use graphs::*;
+use common::*;
+
+/// Every edge is a distinct pair of different vertexes (ordered if `directed`)
+fn assert_simple(graph: &Graph, directed: bool) {
+	let mut pairs = HashSet::new();
+	for (from, to) in graph.edge_ends() {
+		assert_ne!(from, to, "{}", describe(graph));
+		let pair = if directed { (from, to) } else { (from.min(to), from.max(to)) };
+		assert!(pairs.insert(pair), "{}", describe(graph));
+	}
+}
+
+#[test]
+fn unordered_pairs_are_enumerated_in_order() {
+	let mut index = 0;
+	for j in 1..200 {
+		for i in 0..j {
+			assert_eq!(unordered_pair(index), (i, j));
+			index += 1;
+		}
+	}
+	// Around values where the square root loses precision
+	for j in [1_usize << 26, (1 << 31) + 7, 3_037_000_499] {
+		let first = j * (j - 1) / 2;
+		assert_eq!(unordered_pair(first), (0, j));
+		assert_eq!(unordered_pair(first + j - 1), (j - 1, j));
+		assert_eq!(unordered_pair(first - 1), (j - 2, j - 1));
+	}
+}
+
+#[test]
+fn gnm_random_graphs_have_distinct_edges() {
+	let mut rng = Rng::new(700);
+	for _ in 0..CASES {
+		let n = rng.below(9);
+		let directed = rng.chance(0.5);
+		let pairs = if directed { n * n.saturating_sub(1) } else { n * n.saturating_sub(1) / 2 };
+		let m = rng.below(pairs + 1);
+		let graph = gnm_random_graph(n, m, directed, &mut rng);
+		assert_eq!(graph.vertexes(), n);
+		assert_eq!(graph.edges(), m);
+		assert_simple(&graph, directed);
+	}
+	// All the pairs
+	let graph = gnm_random_graph(6, 15, false, &mut rng);
+	assert!(are_isomorphic(&graph, &complete_graph(6)).is_some());
+	assert_eq!(gnm_random_graph(5, 20, true, &mut rng).edges(), 20);
+}
+
+#[test]
+fn gnm_random_graphs_cover_every_pair() {
+	let mut rng = Rng::new(701);
+	let mut seen = HashSet::new();
+	for _ in 0..200 {
+		seen.extend(gnm_random_graph(5, 2, true, &mut rng).edge_ends());
+	}
+	assert_eq!(seen.len(), 20);
+}
+
+#[test]
+fn random_regular_graphs_are_simple_and_regular() {
+	let mut rng = Rng::new(702);
+	for _ in 0..CASES {
+		let n = rng.below(12) + 1;
+		let degree = rng.below(n);
+		if n * degree % 2 == 1 {
+			continue;
+		}
+		let graph = random_regular_graph(n, degree, &mut rng);
+		assert_eq!(graph.vertexes(), n);
+		assert_eq!(graph.edges(), n * degree / 2);
+		assert!(graph.edges.iter().all(|edges| edges.len() == degree), "{}", describe(&graph));
+		assert_simple(&graph, false);
+	}
+	assert_eq!(random_regular_graph(0, 0, &mut rng).vertexes(), 0);
+	assert!(are_isomorphic(&random_regular_graph(7, 6, &mut rng), &complete_graph(7)).is_some());
+}
+
+#[test]
+fn random_trees_and_dags() {
+	let mut rng = Rng::new(703);
+	for _ in 0..CASES {
+		let n = rng.below(12);
+		let tree = random_tree(n, &mut rng);
+		assert_eq!(tree.vertexes(), n);
+		assert!(n == 0 || is_tree(&tree), "{}", describe(&tree));
+
+		let (dag, order) = random_dag(n, rng.next_f64(), &mut rng);
+		let mut position = vec![0; n];
+		for (i, &v) in order.iter().enumerate() {
+			position[v] = i;
+		}
+		assert!(dag.edge_ends().iter().all(|&(from, to)| position[from] < position[to]), "{}", describe(&dag));
+	}
+}
+
+#[test]
+fn rng_stays_in_range() {
+	let mut rng = Rng::new(704);
+	for _ in 0..CASES {
+		let n = rng.below(10) + 1;
+		assert!(rng.below(n) < n);
+		let from = rng.range(-5, 5);
+		let to = from + rng.range(0, 5);
+		assert!((from..=to).contains(&rng.range(from, to)));
+		let x = rng.next_f64();
+		assert!((0.0..1.0).contains(&x));
+		let mut permutation = rng.permutation(n);
+		permutation.sort();
+		assert_eq!(permutation, (0..n).collect::<Vec<_>>());
+	}
+	let mut again = Rng::new(704);
+	let mut rng = Rng::new(704);
+	assert_eq!((0..10).map(|_| rng.next_u64()).collect::<Vec<_>>(), (0..10).map(|_| again.next_u64()).collect::<Vec<_>>());
+	rng.range(i64::MIN, i64::MAX);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use std::collections::HashSet;
+use graphs::*;
+use common::*;
+
+fn distinct_edge_indexes(graph: &Graph) -> usize {
+	graph.edges.iter().flatten().map(|edge| edge.edge_index).collect::<HashSet<_>>().len()
+}
+
+#[test]
+fn remove_edges_counts_every_edge_once() {
+	let mut rng = Rng::new(1);
+	for _ in 0..CASES {
+		let mut graph = random_multigraph(&mut rng, 8, 14, true);
+		let removed = (0..rng.below(graph.edges() + 1))
+			.map(|_| rng.below(graph.edges()))
+			.collect::<Vec<_>>();
+		let description = describe(&graph);
+		graph.remove_edges(&removed);
+		assert_eq!(graph.edges(), distinct_edge_indexes(&graph), "Removing {:?} from\n{}", removed, description);
+	}
+}
+
+#[test]
+fn without_edges_keeps_compact_indexes() {
+	let mut rng = Rng::new(2);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 8, 14, true);
+		let removed = (0..rng.below(graph.edges() + 1))
+			.map(|_| rng.below(graph.edges()))
+			.collect::<Vec<_>>();
+		let kept = (0..graph.edges()).filter(|e| !removed.contains(e)).collect::<Vec<_>>();
+		let smaller = graph.without_edges(&removed);
+		smaller.validate_compact_enumeration();
+		let ends = graph.edge_ends();
+		assert_eq!(smaller.edge_ends(), kept.iter().map(|&e| ends[e]).collect::<Vec<_>>(), "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn contraction_merges_ends() {
+	let mut rng = Rng::new(3);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 8, 14, false);
+		if graph.edges() == 0 {
+			continue;
+		}
+		let edge = rng.below(graph.edges());
+		let contracted = graph.contracted(edge);
+		contracted.validate_compact_enumeration();
+		assert_eq!(contracted.vertexes(), graph.vertexes() - 1);
+		assert_eq!(contracted.edges(), graph.edges() - 1);
+		// Contracting an edge never changes connectivity of the rest
+		let components = count_components(&graph, |_| true, |_| true);
+		assert_eq!(count_components(&contracted, |_| true, |_| true), components, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn reversed_swaps_ends() {
+	let mut rng = Rng::new(4);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 8, 14);
+		let reversed_ends = graph.reversed().edge_ends();
+		let ends = graph.edge_ends().into_iter().map(|(from, to)| (to, from)).collect::<Vec<_>>();
+		assert_eq!(reversed_ends, ends, "{}", describe(&graph));
+	}
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+#[test]
+fn hamiltonian_paths_match_exhaustive_search() {
+	let mut rng = Rng::new(42);
+	for _ in 0..CASES {
+		let graph = random_simple_graph(&mut rng, 7);
+		let n = graph.vertexes();
+		let mut adjacent = vec![vec![false; n]; n];
+		for (from, to) in graph.edge_ends() {
+			adjacent[from][to] = true;
+			adjacent[to][from] = true;
+		}
+		let orders = permutations(n);
+		let is_path = |order: &Vec<usize>| order.windows(2).all(|w| adjacent[w[0]][w[1]]);
+		let has_path = orders.iter().any(is_path);
+		let has_cycle = n == 1 || (n >= 3 && orders.iter().any(|order| is_path(order) && adjacent[order[n - 1]][order[0]]));
+
+		for (cycle, expected) in [(true, has_cycle), (false, has_path)] {
+			let result = if cycle { hamiltonian_cycle(&graph) } else { hamiltonian_path(&graph) };
+			assert_eq!(result.path().is_some(), expected, "cycle: {}\n{}", cycle, describe(&graph));
+			if let Some(path) = result.path() {
+				let mut vertexes = path.vertexes.clone();
+				vertexes.sort();
+				assert_eq!(vertexes, (0..n).collect::<Vec<_>>());
+				assert!(is_path(&path.vertexes));
+				if cycle && n > 1 {
+					assert!(adjacent[path.vertexes[n - 1]][path.vertexes[0]]);
+				}
+			}
+		}
+	}
+}
+
+#[test]
+fn non_existence_proofs() {
+	let proof = |result: HamiltonianResult| match result {
+		HamiltonianResult::NotFound(proof) => proof,
+		HamiltonianResult::Found { path, .. } => panic!("Unexpected route {:?}", path),
+	};
+	assert_eq!(proof(hamiltonian_cycle(&path_graph(4))), NonExistenceProof::LowDegree(0));
+
+	let mut triangles = Graph::new(6);
+	for (from, to) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+		triangles.add_undirected_edge(from, to);
+	}
+	assert_eq!(proof(hamiltonian_cycle(&triangles)), NonExistenceProof::Disconnected);
+	assert_eq!(proof(hamiltonian_path(&triangles)), NonExistenceProof::Disconnected);
+
+	// Two triangles sharing vertex 2
+	let mut bowtie = Graph::new(5);
+	for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)] {
+		bowtie.add_undirected_edge(from, to);
+	}
+	assert_eq!(proof(hamiltonian_cycle(&bowtie)), NonExistenceProof::CutVertex(2));
+	assert!(hamiltonian_path(&bowtie).path().is_some());
+
+	let mut star = Graph::new(4);
+	for leaf in 1..4 {
+		star.add_undirected_edge(0, leaf);
+	}
+	assert_eq!(proof(hamiltonian_path(&star)), NonExistenceProof::TooManyLeaves(vec![1, 2, 3]));
+	assert_eq!(proof(hamiltonian_cycle(&complete_bipartite_graph(2, 4))),
+	           NonExistenceProof::BipartiteImbalance { smaller: 2, larger: 4 });
+	assert_eq!(proof(hamiltonian_cycle(&petersen_graph())), NonExistenceProof::Exhaustive(HamiltonianMethod::BitmaskDp));
+}
+
+#[test]
+fn methods() {
+	let method = |result: HamiltonianResult| match result {
+		HamiltonianResult::Found { path, method } => {
+			assert!(path.vertexes.len() == path.edges.len() || path.vertexes.len() == path.edges.len() + 1);
+			method
+		}
+		HamiltonianResult::NotFound(proof) => panic!("Unexpected proof {:?}", proof),
+	};
+	assert_eq!(method(hamiltonian_cycle(&Graph::new(1))), HamiltonianMethod::Trivial);
+	assert_eq!(method(hamiltonian_cycle(&complete_graph(30))), HamiltonianMethod::DiracOre);
+	assert_eq!(method(hamiltonian_path(&petersen_graph())), HamiltonianMethod::BitmaskDp);
+	assert_eq!(method(hamiltonian_cycle(&cycle_graph(25))), HamiltonianMethod::Backtracking);
+	assert_eq!(method(hamiltonian_cycle(&hypercube_graph(5))), HamiltonianMethod::Backtracking);
+}
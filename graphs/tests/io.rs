@@ -0,0 +1,136 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn malformed_at<T: std::fmt::Debug>(result: GraphFormatResult<T>) -> usize {
+	match result {
+		Err(GraphFormatError::Malformed { line, .. }) => line,
+		other => panic!("expected a malformed input error, got {:?}", other),
+	}
+}
+
+#[test]
+fn formats_round_trip() {
+	let mut rng = Rng::new(5);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 8, 14, true);
+		let ends = graph.edge_ends();
+		for indexing in [Indexing::ZeroBased, Indexing::OneBased] {
+			let text = format_edge_list(&graph, indexing);
+			assert_eq!(parse_edge_list(&text, indexing, false).unwrap().edge_ends(), ends);
+		}
+		assert_eq!(parse_dimacs(&format_dimacs(&graph)).unwrap().edge_ends(), ends);
+		assert_eq!(parse_graphml(&format_graphml(&graph, false)).unwrap().graph.edge_ends(), ends);
+
+		let dot = parse_dot(&format_dot(&graph, &DotStyle::default())).unwrap();
+		assert_eq!(dot.names, (0..graph.vertexes()).map(|v| v.to_string()).collect::<Vec<_>>());
+		assert_eq!(dot.graph.edge_ends(), ends);
+
+		let matrix = format_adjacency_matrix(&graph, false);
+		let parsed = parse_adjacency_matrix(&matrix, MatrixLayout::Spaced, false).unwrap();
+		assert_eq!(format_adjacency_matrix(&parsed, false), matrix);
+
+		let simple = random_simple_graph(&mut rng, 8);
+		let triangle = format_triangular_adjacency_matrix(&simple);
+		let parsed = parse_triangular_adjacency_matrix(&triangle, MatrixLayout::Packed).unwrap();
+		assert_eq!(format_triangular_adjacency_matrix(&parsed), triangle);
+
+		let directed = random_directed_graph(&mut rng, 8, 14);
+		let matrix = format_adjacency_matrix(&directed, true);
+		let parsed = parse_adjacency_matrix(&matrix, MatrixLayout::Spaced, true).unwrap();
+		assert_eq!(format_adjacency_matrix(&parsed, true), matrix);
+		let weighted = with_random_weights(&directed, -5, 5, &mut rng);
+		let text = format_weighted_edge_list(&weighted, Indexing::OneBased);
+		let parsed = parse_weighted_edge_list::<i64>(&text, Indexing::OneBased, true).unwrap();
+		assert_eq!(format_weighted_edge_list(&parsed, Indexing::OneBased), text);
+	}
+}
+
+#[test]
+fn matrix_layout_is_chosen_by_the_caller() {
+	// Ten parallel edges, not a packed row
+	let graph = parse_adjacency_matrix("2\n0 10\n10 0\n", MatrixLayout::Spaced, false).unwrap();
+	assert_eq!(graph.edge_ends(), vec![(0, 1); 10]);
+	let graph = parse_adjacency_matrix("3\n011\n101\n110\n", MatrixLayout::Packed, false).unwrap();
+	assert_eq!(graph.edge_ends(), vec![(0, 1), (0, 2), (1, 2)]);
+	let graph = parse_triangular_adjacency_matrix("3\n1\n0 1\n", MatrixLayout::Spaced).unwrap();
+	assert_eq!(graph.edge_ends(), vec![(0, 1), (1, 2)]);
+	let graph = parse_triangular_adjacency_matrix("3\n1\n01\n", MatrixLayout::Packed).unwrap();
+	assert_eq!(graph.edge_ends(), vec![(0, 1), (1, 2)]);
+
+	assert!(matches!(
+		parse_adjacency_matrix("2\n0 1\n1 0\n", MatrixLayout::Packed, false),
+		Err(GraphFormatError::InvalidToken { line: 2, .. })
+	));
+	assert!(matches!(
+		parse_adjacency_matrix("2\n02\n20\n", MatrixLayout::Packed, true),
+		Err(GraphFormatError::InvalidToken { line: 2, .. })
+	));
+	assert_eq!(malformed_at(parse_adjacency_matrix("2\n01\n10\n", MatrixLayout::Spaced, false)), 1);
+	assert_eq!(malformed_at(parse_adjacency_matrix("2\n0 1\n0 0\n", MatrixLayout::Spaced, false)), 2);
+}
+
+#[test]
+fn huge_headers_are_rejected_before_allocating() {
+	let huge = "4611686018427387904";
+	assert_eq!(malformed_at(parse_edge_list(&format!("{} 0\n", huge), Indexing::ZeroBased, false)), 1);
+	assert_eq!(malformed_at(parse_weighted_edge_list::<i64>(&format!("{} 0\n", huge), Indexing::OneBased, true)), 1);
+	assert_eq!(malformed_at(parse_dimacs(&format!("c\np edge {} 0\n", huge))), 2);
+	assert_eq!(malformed_at(parse_dimacs_network::<i64>(&format!("p max {} 0\n", huge))), 1);
+	for layout in [MatrixLayout::Spaced, MatrixLayout::Packed] {
+		assert_eq!(malformed_at(parse_adjacency_matrix(&format!("{}\n0\n", huge), layout, false)), 1);
+		assert_eq!(malformed_at(parse_adjacency_matrix(&format!("{}\n", usize::MAX), layout, true)), 1);
+		assert_eq!(malformed_at(parse_triangular_adjacency_matrix(&format!("{}\n1\n", huge), layout)), 1);
+	}
+	// Isolated vertexes below the limit are fine
+	let graph = parse_edge_list(&format!("{} 0\n", 1 << 10), Indexing::ZeroBased, false).unwrap();
+	assert_eq!(graph.vertexes(), 1 << 10);
+	assert_eq!(malformed_at(parse_edge_list(&format!("{} 0\n", MAX_PARSED_VERTEXES + 1), Indexing::ZeroBased, false)), 1);
+}
+
+#[test]
+fn huge_matrix_entries_are_rejected_before_adding_edges() {
+	assert_eq!(malformed_at(parse_adjacency_matrix("2\n0 4000000000\n0 0\n", MatrixLayout::Spaced, true)), 2);
+	assert_eq!(malformed_at(parse_triangular_adjacency_matrix("2\n99999999999999\n", MatrixLayout::Spaced)), 2);
+	assert_eq!(malformed_at(parse_adjacency_matrix(&format!("1\n{}\n", usize::MAX), MatrixLayout::Spaced, false)), 2);
+	// The limit is on the sum, and an undirected edge is counted once
+	let half = MAX_PARSED_EDGES / 2 + 1;
+	assert_eq!(malformed_at(parse_adjacency_matrix(&format!("2\n{} 0\n0 {}\n", half, half), MatrixLayout::Spaced, false)), 3);
+	let graph = parse_adjacency_matrix("2\n0 3\n3 0\n", MatrixLayout::Spaced, false).unwrap();
+	assert_eq!(graph.edges(), 3);
+}
+
+#[test]
+fn invalid_inputs_are_reported_with_their_line() {
+	assert!(matches!(
+		parse_edge_list("3 2\n0 1\n1 3\n", Indexing::ZeroBased, false),
+		Err(GraphFormatError::VertexOutOfRange { line: 3, vertex: 3, vertexes: 3 })
+	));
+	assert!(matches!(
+		parse_edge_list("3 2\n1 2\n0 1\n", Indexing::OneBased, false),
+		Err(GraphFormatError::VertexOutOfRange { line: 3, vertex: 0, vertexes: 3 })
+	));
+	assert!(matches!(parse_edge_list("3 2\n0 1\n", Indexing::ZeroBased, false), Err(GraphFormatError::UnexpectedEnd { .. })));
+	assert!(matches!(parse_edge_list("", Indexing::ZeroBased, false), Err(GraphFormatError::UnexpectedEnd { .. })));
+	assert!(matches!(
+		parse_weighted_edge_list::<i64>("2 1\n1 2 x\n", Indexing::OneBased, false),
+		Err(GraphFormatError::InvalidToken { line: 2, .. })
+	));
+	assert_eq!(malformed_at(parse_edge_list("2 1\n0 1\n1 0\n", Indexing::ZeroBased, false)), 3);
+
+	assert_eq!(malformed_at(parse_dimacs("e 1 2\np edge 2 1\n")), 1);
+	assert!(matches!(parse_dimacs("p edge 2 1\nx 1 2\n"), Err(GraphFormatError::InvalidToken { line: 2, .. })));
+	assert!(matches!(parse_dimacs("c only a comment\n"), Err(GraphFormatError::UnexpectedEnd { .. })));
+	assert!(matches!(
+		parse_dimacs_network::<i64>("p max 2 1\nn 1 x\n"),
+		Err(GraphFormatError::InvalidToken { line: 2, .. })
+	));
+
+	assert_eq!(malformed_at(parse_dot("graph {\n0 -- 1\n/* to the end\n1 -- 2\n}\n")), 3);
+	assert_eq!(malformed_at(parse_dot("graph {\n0 -- 1 /* closed */\n\"open\n}\n")), 3);
+
+	let graphml = "<graphml><graph>\n<node id=\"a\"/>\n<edge source=\"a\" target=\"b\"/>\n</graph></graphml>";
+	assert_eq!(malformed_at(parse_graphml(graphml)), 3);
+	assert_eq!(malformed_at(parse_graphml("<graphml>\n<node id=\"a\"/>\n</graphml>")), 2);
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn relabelled(graph: &Graph, permutation: &[usize]) -> Graph {
+	let mut result = Graph::new(graph.vertexes());
+	for (from, to) in graph.edge_ends() {
+		result.add_undirected_edge(permutation[from], permutation[to]);
+	}
+	result
+}
+
+fn sorted_ends(graph: &Graph) -> Vec<(usize, usize)> {
+	let mut ends = graph.edge_ends().into_iter().map(|(u, v)| (u.min(v), u.max(v))).collect::<Vec<_>>();
+	ends.sort();
+	ends
+}
+
+fn is_isomorphism(a: &Graph, b: &Graph, mapping: &[usize]) -> bool {
+	sorted_ends(&relabelled(a, mapping)) == sorted_ends(b)
+}
+
+/// Group generated by the permutations, by closing it under composition
+fn generated_group(n: usize, generators: &[Vec<usize>]) -> usize {
+	let mut group = std::collections::HashSet::from([(0..n).collect::<Vec<_>>()]);
+	let mut queue = vec![(0..n).collect::<Vec<_>>()];
+	while let Some(element) = queue.pop() {
+		for generator in generators {
+			let product = element.iter().map(|&v| generator[v]).collect::<Vec<_>>();
+			if group.insert(product.clone()) {
+				queue.push(product);
+			}
+		}
+	}
+	group.len()
+}
+
+#[test]
+fn relabelled_graphs_are_isomorphic() {
+	let mut rng = Rng::new(401);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 12, 20, true);
+		let other = relabelled(&graph, &rng.permutation(graph.vertexes()));
+		assert_eq!(canonical_form(&graph), canonical_form(&other), "{}", describe(&graph));
+		let mapping = are_isomorphic(&graph, &other).expect("Relabelled graph should be isomorphic");
+		assert!(is_isomorphism(&graph, &other, &mapping), "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn isomorphism_matches_brute_force() {
+	let mut rng = Rng::new(402);
+	for _ in 0..CASES {
+		let n = rng.below(6);
+		let a = gnm_random_graph(n, rng.below(n * n.saturating_sub(1) / 2 + 1), false, &mut rng);
+		let b = gnm_random_graph(n, a.edges(), false, &mut rng);
+		let expected = permutations(n).into_iter().any(|p| is_isomorphism(&a, &b, &p));
+		let mapping = are_isomorphic(&a, &b);
+		assert_eq!(mapping.is_some(), expected, "{}\n{}", describe(&a), describe(&b));
+		assert!(mapping.is_none_or(|mapping| is_isomorphism(&a, &b, &mapping)));
+	}
+}
+
+#[test]
+fn automorphism_groups_match_brute_force() {
+	let mut rng = Rng::new(403);
+	let mut graphs = vec![
+		complete_graph(5), cycle_graph(7), star_graph(5), hypercube_graph(3), grid_graph(2, 3),
+		complete_bipartite_graph(3, 3), Graph::new(4),
+	];
+	graphs.extend((0..CASES).map(|_| random_multigraph(&mut rng, 7, 9, true)));
+	for graph in &graphs {
+		let n = graph.vertexes();
+		let labelling = canonical_labelling(graph);
+		for automorphism in &labelling.automorphisms {
+			assert!(is_isomorphism(graph, graph, automorphism), "{}", describe(graph));
+		}
+		let expected = permutations(n).into_iter().filter(|p| is_isomorphism(graph, graph, p)).count();
+		assert_eq!(generated_group(n, &labelling.automorphisms), expected, "{}", describe(graph));
+		assert_eq!(labelling.group_order, expected as u128, "{}", describe(graph));
+	}
+}
+
+#[test]
+fn symmetric_graphs() {
+	assert_eq!(canonical_labelling(&petersen_graph()).group_order, 120);
+	assert_eq!(canonical_labelling(&complete_graph(12)).group_order, 479_001_600);
+	assert_eq!(canonical_labelling(&hypercube_graph(5)).group_order, 3840);
+	assert!(are_isomorphic(&petersen_graph(), &generalized_petersen_graph(5, 2)).is_some());
+	// Both are 3-regular on 10 vertexes, but only the prism has 4-cycles
+	assert!(are_isomorphic(&petersen_graph(), &generalized_petersen_graph(5, 1)).is_none());
+}
+
+#[test]
+fn counts_unlabelled_graphs() {
+	// Simple graphs on 4 and 5 vertexes
+	for (n, expected) in [(4, 11), (5, 34)] {
+		let pairs = (0..n).flat_map(|u| (u + 1..n).map(move |v| (u, v))).collect::<Vec<_>>();
+		let graphs = (0..1 << pairs.len())
+			.map(|mask: usize| {
+				let mut graph = Graph::new(n);
+				for (i, &(u, v)) in pairs.iter().enumerate() {
+					if mask >> i & 1 == 1 {
+						graph.add_undirected_edge(u, v);
+					}
+				}
+				graph
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(isomorphism_classes(&graphs).component_list.len(), expected);
+	}
+	// Trees on 7 vertexes from all Prüfer codes
+	let trees = (0..7_usize.pow(5))
+		.map(|code| tree_from_prufer_code(&(0..5).map(|i| code / 7_usize.pow(i) % 7).collect::<Vec<_>>()).unwrap())
+		.collect::<Vec<_>>();
+	assert_eq!(isomorphism_classes(&trees).component_list.len(), 11);
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn brute_force_matching_size(graph: &Graph) -> usize {
+	let ends = graph.edge_ends();
+	let mut best = 0;
+	for subset in 0..1_usize << ends.len() {
+		let mut used = vec![false; graph.vertexes()];
+		let valid = ends.iter().enumerate()
+			.filter(|&(e, _)| subset >> e & 1 == 1)
+			.all(|(_, &(from, to))| {
+				let free = from != to && !used[from] && !used[to];
+				used[from] = true;
+				used[to] = true;
+				free
+			});
+		if valid {
+			best = best.max(subset.count_ones() as usize);
+		}
+	}
+	best
+}
+
+fn assert_is_matching(graph: &Graph, matching: &Matching) {
+	let ends = graph.edge_ends();
+	let mut used = vec![false; graph.vertexes()];
+	for &e in &matching.matched_edges {
+		let (from, to) = ends[e];
+		assert!(from != to && !used[from] && !used[to], "{}", describe(graph));
+		used[from] = true;
+		used[to] = true;
+	}
+}
+
+/// Random bipartite multigraph with parts `0..left` and `left..n`
+fn random_bipartite_graph(rng: &mut Rng) -> Graph {
+	let left = rng.below(5) + 1;
+	let right = rng.below(5) + 1;
+	let mut graph = Graph::new(left + right);
+	for _ in 0..rng.below(12) {
+		graph.add_undirected_edge(rng.below(left), left + rng.below(right));
+	}
+	graph
+}
+
+#[test]
+fn maximum_matching_matches_brute_force() {
+	let mut rng = Rng::new(31);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 8, 12, true);
+		let matching = maximum_matching(&graph);
+		assert_is_matching(&graph, &matching);
+		assert_eq!(matching.size(), brute_force_matching_size(&graph), "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn bipartite_matching_gives_koenig_certificates() {
+	let mut rng = Rng::new(32);
+	for _ in 0..CASES {
+		let graph = random_bipartite_graph(&mut rng);
+		let best = brute_force_matching_size(&graph);
+		let result = maximum_bipartite_matching(&graph).expect("Graph is bipartite");
+		assert_is_matching(&graph, &result.matching);
+		assert_eq!(result.matching.size(), best, "{}", describe(&graph));
+		assert_eq!(result.vertex_cover.len(), best, "{}", describe(&graph));
+		assert!(graph.edge_ends().iter().all(|(from, to)| result.vertex_cover.contains(from) || result.vertex_cover.contains(to)));
+		assert_eq!(result.independent_set.len() + best, graph.vertexes());
+	}
+}
+
+#[test]
+fn known_matchings() {
+	assert_eq!(maximum_matching(&petersen_graph()).size(), 5);
+	assert_eq!(maximum_matching(&cycle_graph(7)).size(), 3);
+	assert_eq!(maximum_matching(&complete_graph(6)).size(), 3);
+	assert_eq!(maximum_matching(&Graph::new(3)).size(), 0);
+
+	// Two triangles joined by an edge: the perfect matching needs that edge, through both blossoms
+	let mut graph = Graph::new(6);
+	for (from, to) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+		graph.add_undirected_edge(from, to);
+	}
+	let matching = maximum_matching(&graph);
+	assert_eq!(matching.size(), 3);
+	assert!(matching.matched_edges.contains(&6));
+	assert_eq!(matching.mate[2], Some(3));
+
+	let result = maximum_bipartite_matching(&complete_bipartite_graph(3, 5)).unwrap();
+	assert_eq!(result.matching.size(), 3);
+	assert_eq!(result.vertex_cover, vec![0, 1, 2]);
+	assert!(maximum_bipartite_matching(&cycle_graph(5)).is_none());
+}
+
+#[test]
+fn bipartitions() {
+	let sides = bipartition(&cycle_graph(6)).unwrap();
+	assert_eq!(sides.component_list, vec![vec![0, 2, 4], vec![1, 3, 5]]);
+	assert!(bipartition(&cycle_graph(7)).is_none());
+	assert!(bipartition(&petersen_graph()).is_none());
+	// Always two sides, even without edges
+	assert_eq!(bipartition(&Graph::new(3)).unwrap().component_list, vec![vec![0, 1, 2], vec![]]);
+	assert_eq!(bipartition(&Graph::new(0)).unwrap().component_list.len(), 2);
+
+	// Explicit sides for Hopcroft–Karp: the right side first
+	let graph = complete_bipartite_graph(2, 4);
+	let sides = Decomposition::from_component_list(vec![vec![2, 3, 4, 5], vec![0, 1]]);
+	let result = hopcroft_karp(&graph, &sides);
+	assert_eq!(result.matching.size(), 2);
+	assert_eq!(result.vertex_cover, vec![0, 1]);
+}
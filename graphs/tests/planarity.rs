@@ -0,0 +1,212 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+/// Euler's formula for every component with edges proves that the rotation system is planar
+fn assert_valid_embedding(graph: &Graph, embedding: &PlanarEmbedding) {
+	for v in 0..graph.vertexes() {
+		let mut expected = graph.edges[v].iter().map(|e| (e.to, e.edge_index)).collect::<Vec<_>>();
+		let mut actual = embedding.rotation[v].iter().map(|e| (e.to, e.edge_index)).collect::<Vec<_>>();
+		expected.sort();
+		actual.sort();
+		assert_eq!(actual, expected, "{}", describe(graph));
+	}
+	let components = DFSSpace::new(graph).find_connected_components(graph);
+	let faces = embedding.faces();
+	let mut faces_of_component = vec![0; components.component_list.len()];
+	for face in &faces {
+		faces_of_component[components.component_of(face.vertexes[0])] += 1;
+	}
+	let mut edges_of_component = vec![0; components.component_list.len()];
+	for (from, _) in graph.edge_ends() {
+		edges_of_component[components.component_of(from)] += 1;
+	}
+	for (c, component) in components.component_list.iter().enumerate() {
+		if edges_of_component[c] > 0 {
+			assert_eq!(component.len() + faces_of_component[c], edges_of_component[c] + 2, "{}", describe(graph));
+		}
+	}
+}
+
+/// Smoothing the vertexes of degree 2 should give K₅ or K₃,₃
+fn assert_valid_witness(graph: &Graph, witness: &KuratowskiSubgraph) {
+	let ends = graph.edge_ends();
+	let mut subgraph = Graph::new(graph.vertexes());
+	for &e in &witness.edges {
+		subgraph.add_undirected_edge(ends[e].0, ends[e].1);
+	}
+	let degree = |v: usize| subgraph.edges[v].len();
+	assert!((0..graph.vertexes()).all(|v| degree(v) != 1 && (degree(v) <= 2 || subgraph.edges[v].iter().all(|e| e.to != v))));
+	let branches = (0..graph.vertexes()).filter(|&v| degree(v) >= 3).collect::<Vec<_>>();
+	let mut smoothed = Graph::new(branches.len());
+	let mut used = vec![false; subgraph.edges()];
+	for (i, &branch) in branches.iter().enumerate() {
+		for start in &subgraph.edges[branch] {
+			if used[start.edge_index] {
+				continue;
+			}
+			let (mut v, mut edge) = (start.to, start.edge_index);
+			used[edge] = true;
+			while degree(v) == 2 {
+				let next = subgraph.edges[v].iter().find(|e| e.edge_index != edge).unwrap();
+				(v, edge) = (next.to, next.edge_index);
+				used[edge] = true;
+			}
+			smoothed.add_undirected_edge(i, branches.binary_search(&v).unwrap());
+		}
+	}
+	assert!(used.iter().all(|&u| u), "Witness has a cycle without branch vertexes");
+	let expected = match witness.kind {
+		KuratowskiKind::K5 => complete_graph(5),
+		KuratowskiKind::K33 => complete_bipartite_graph(3, 3),
+	};
+	assert!(are_isomorphic(&smoothed, &expected).is_some(), "{:?} {}", witness, describe(graph));
+}
+
+fn check(graph: &Graph) -> bool {
+	match planarity(graph) {
+		Planarity::Planar(embedding) => {
+			assert_valid_embedding(graph, &embedding);
+			assert!(is_planar(graph));
+			true
+		}
+		Planarity::NonPlanar(witness) => {
+			assert_valid_witness(graph, &witness);
+			assert!(!is_planar(graph));
+			false
+		}
+	}
+}
+
+#[test]
+fn known_graphs() {
+	for graph in [complete_graph(4), grid_graph(4, 5), wheel_graph(7), hypercube_graph(3), generalized_petersen_graph(5, 1),
+	              generalized_petersen_graph(6, 1), Graph::new(3), herschel_graph()] {
+		assert!(check(&graph), "{}", describe(&graph));
+	}
+	for graph in [complete_graph(5), complete_bipartite_graph(3, 3), petersen_graph(), hypercube_graph(4),
+	              complete_graph(8), torus_graph(3, 3)] {
+		assert!(!check(&graph), "{}", describe(&graph));
+	}
+	let witness = match planarity(&complete_bipartite_graph(3, 4)) {
+		Planarity::NonPlanar(witness) => witness,
+		Planarity::Planar(_) => panic!("K₃,₄ is not planar"),
+	};
+	assert_eq!((witness.kind, witness.edges.len()), (KuratowskiKind::K33, 9));
+}
+
+#[test]
+fn random_graphs_are_certified() {
+	let mut rng = Rng::new(431);
+	let mut planar = 0;
+	for case in 0..CASES * 3 {
+		let graph = if case % 3 == 0 {
+			random_multigraph(&mut rng, 10, 20, true)
+		} else {
+			let n = 5 + rng.below(8);
+			gnm_random_graph(n, (n + rng.below(2 * n)).min(n * (n - 1) / 2), false, &mut rng)
+		};
+		planar += check(&graph) as usize;
+	}
+	// Both outcomes should be well represented
+	assert!(planar > CASES / 2 && planar < CASES * 5 / 2, "{}", planar);
+}
+
+#[test]
+fn dense_graphs_are_certified() {
+	// The witness is cut out of a prefix of at most 3n - 5 edges, not out of all the edges
+	let mut rng = Rng::new(434);
+	assert!(!check(&complete_graph(100)));
+	assert!(!check(&complete_bipartite_graph(40, 50)));
+	for _ in 0..10 {
+		assert!(!check(&gnm_random_graph(60, 600, false, &mut rng)));
+	}
+}
+
+#[test]
+fn dual_of_spanning_tree_complement_is_spanning_tree() {
+	let mut rng = Rng::new(432);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 9, 14, true);
+		if count_components(&graph, |_| true, |_| true) > 1 || graph.edges() == 0 {
+			continue;
+		}
+		let Planarity::Planar(embedding) = planarity(&graph) else {
+			continue;
+		};
+		let (dual, faces) = embedding.dual();
+		assert_eq!((dual.vertexes(), dual.edges()), (faces.len(), graph.edges()));
+		let tree = RootedTree::new(&graph, 0);
+		let in_tree = tree.parent_edge.iter().flatten().copied().collect::<Vec<_>>();
+		let cotree = dual.without_edges(&in_tree);
+		assert_eq!(cotree.edges() + 1, cotree.vertexes(), "{}", describe(&graph));
+		assert!(is_tree(&cotree), "{}", describe(&graph));
+		// Bridges are exactly the loops of the dual
+		let mut bridges = DFSSpace::new(&graph).find_bridges(&graph);
+		bridges.sort();
+		let loops = dual.edge_ends().into_iter().enumerate().filter(|(_, (u, v))| u == v).map(|(e, _)| e).collect::<Vec<_>>();
+		assert_eq!(bridges, loops, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn dual_keeps_edge_indexes_with_gaps() {
+	let mut triangle = Graph::new(3);
+	triangle.add_undirected_edge(0, 1);
+	triangle.add_undirected_edge(1, 2);
+	triangle.add_undirected_edge(2, 0);
+	let path_ends = |dual: &Graph| {
+		let mut ends = (0..dual.vertexes())
+			.flat_map(|f| dual.edges[f].iter().map(move |edge| (edge.edge_index, f, edge.to)))
+			.collect::<Vec<_>>();
+		ends.sort();
+		ends
+	};
+
+	// What is left of the triangle is a path: one face, its two edges are loops of the dual
+	let subgraph = EdgeSubgraph::new(&triangle, |e| e != 0);
+	let (dual, faces) = planarity(&subgraph).embedding().unwrap().dual();
+	assert_eq!((faces.len(), dual.edges()), (1, 2));
+	assert_eq!(path_ends(&dual), vec![(1, 0, 0), (1, 0, 0), (2, 0, 0), (2, 0, 0)]);
+
+	let mut path = triangle.clone();
+	path.remove_edges(&[0]);
+	let (dual, faces) = planarity(&path).embedding().unwrap().dual();
+	assert_eq!((faces.len(), dual.edges()), (1, 2));
+	assert_eq!(path_ends(&dual), vec![(1, 0, 0), (1, 0, 0), (2, 0, 0), (2, 0, 0)]);
+
+	// A square with a diagonal and a side removed is a triangle with a pendant edge, the only bridge
+	let mut square = Graph::new(4);
+	for v in 0..4 {
+		square.add_undirected_edge(v, (v + 1) % 4);
+	}
+	square.add_undirected_edge(0, 2);
+	square.remove_edges(&[1]);
+	let (dual, faces) = planarity(&square).embedding().unwrap().dual();
+	assert_eq!((faces.len(), dual.edges()), (2, 4));
+	assert!(path_ends(&dual).iter().all(|&(e, f, g)| e != 1 && (f == g) == (e == 0)));
+}
+
+#[test]
+fn large_triangulations_are_planar() {
+	let mut rng = Rng::new(433);
+	for size in [5, 12, 20] {
+		let mut graph = grid_graph(size, size);
+		for r in 0..size - 1 {
+			for c in 0..size - 1 {
+				let v = r * size + c;
+				if rng.chance(0.5) {
+					graph.add_undirected_edge(v, v + size + 1);
+				} else {
+					graph.add_undirected_edge(v + 1, v + size);
+				}
+			}
+		}
+		assert!(check(&graph));
+		// Chords between the opposite corners alternate around the outer face, so they would cross
+		graph.add_undirected_edge(0, size * size - 1);
+		graph.add_undirected_edge(size - 1, size * (size - 1));
+		assert!(!check(&graph));
+	}
+}
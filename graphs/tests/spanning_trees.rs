@@ -0,0 +1,109 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+/// Subsets of n - 1 edges without cycles
+fn brute_force_count(graph: &Graph) -> u64 {
+	let n = graph.vertexes();
+	let ends = graph.edge_ends();
+	(0..1usize << ends.len())
+		.filter(|mask| mask.count_ones() as usize == n - 1)
+		.filter(|&mask| {
+			let mut union_find = UnionFind::new(n);
+			for (_, &(from, to)) in ends.iter().enumerate().filter(|(e, _)| mask >> e & 1 == 1) {
+				union_find.union(from, to);
+			}
+			union_find.components == 1
+		})
+		.count() as u64
+}
+
+#[test]
+fn matches_brute_force() {
+	let mut rng = Rng::new(460);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 7, 11, true);
+		let expected = brute_force_count(&graph);
+		assert_eq!(spanning_tree_count(&graph).to_u128(), Some(expected as u128), "{}", describe(&graph));
+		assert_eq!(spanning_tree_count_mod(&graph, 1_000_000_007), expected % 1_000_000_007);
+		assert_eq!(spanning_tree_count_mod(&graph, 5), expected % 5);
+	}
+}
+
+#[test]
+fn known_counts() {
+	// Cayley's formula: as many trees as Prüfer codes
+	for n in 1..=30 {
+		assert_eq!(spanning_tree_count(&complete_graph(n)), BigUint::pow(n as u64, n.saturating_sub(2) as u32));
+	}
+	assert_eq!(spanning_tree_count(&complete_graph(30)).to_string(), "228767924549610000000000000000000000000000");
+	assert_eq!(spanning_tree_count(&complete_bipartite_graph(4, 6)), BigUint::from(4u64.pow(5) * 6u64.pow(3)));
+	assert_eq!(spanning_tree_count(&cycle_graph(17)), BigUint::from(17));
+	assert_eq!(spanning_tree_count(&petersen_graph()), BigUint::from(2000));
+	assert_eq!(spanning_tree_count(&hypercube_graph(4)), BigUint::from(42467328));
+	assert!(spanning_tree_count(&Graph::new(3)).is_zero());
+
+	let big = complete_graph(60);
+	let count = spanning_tree_count(&big);
+	assert_eq!(count, BigUint::pow(60, 58));
+	assert_eq!(count.rem_u64(998_244_353), spanning_tree_count_mod(&big, 998_244_353));
+}
+
+#[test]
+fn big_integers() {
+	let mut x = BigUint::pow(10, 40);
+	assert_eq!(x.to_string(), format!("1{}", "0".repeat(40)));
+	x.add_u64(7);
+	assert_eq!(x.div_rem_u64(1_000_000_000_000_000_000), 7);
+	assert_eq!(x.to_string(), format!("1{}", "0".repeat(22)));
+	assert!(BigUint::pow(2, 64) > BigUint::from(u64::MAX));
+	assert!(BigUint::pow(3, 50) < BigUint::pow(2, 80));
+	assert_eq!(BigUint::zero().to_string(), "0");
+	assert_eq!(BigUint::pow(2, 100).to_u128(), Some(1 << 100));
+	assert_eq!(BigUint::pow(2, 128).to_u128(), None);
+	assert_eq!(BigUint::pow(2, 100).rem_u64(1_000_000_007), 976371285);
+}
+
+#[test]
+fn wilson_gives_spanning_trees() {
+	let mut rng = Rng::new(461);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 20, true);
+		let n = graph.vertexes();
+		let connected = count_components(&graph, |_| true, |_| true) == 1;
+		match random_spanning_tree(&graph, &mut rng) {
+			None => assert!(!connected),
+			Some(tree) => {
+				assert!(connected);
+				assert_eq!(tree.len(), n - 1);
+				let ends = graph.edge_ends();
+				let mut union_find = UnionFind::new(n);
+				for &e in &tree {
+					union_find.union(ends[e].0, ends[e].1);
+				}
+				assert_eq!(union_find.components, 1, "{}", describe(&graph));
+			}
+		}
+	}
+}
+
+#[test]
+fn wilson_is_uniform() {
+	let mut rng = Rng::new(462);
+	// A square with a doubled diagonal and a loop
+	let mut graph = cycle_graph(4);
+	graph.add_undirected_edge(0, 2);
+	graph.add_undirected_edge(0, 2);
+	graph.add_undirected_edge(1, 1);
+	let trees = spanning_tree_count(&graph).to_u128().unwrap() as usize;
+	let samples = 2000 * trees;
+	let mut seen = std::collections::HashMap::new();
+	for _ in 0..samples {
+		*seen.entry(random_spanning_tree(&graph, &mut rng).unwrap()).or_insert(0) += 1;
+	}
+	assert_eq!(seen.len(), trees);
+	for &count in seen.values() {
+		assert!((1700..=2300).contains(&count), "{:?}", seen);
+	}
+}
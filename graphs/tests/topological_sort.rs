@@ -0,0 +1,113 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn is_topological(graph: &Graph, order: &[usize]) -> bool {
+	let mut position = vec![usize::MAX; graph.vertexes()];
+	for (i, &v) in order.iter().enumerate() {
+		position[v] = i;
+	}
+	order.len() == graph.vertexes() && graph.edge_ends().into_iter().all(|(from, to)| position[from] < position[to])
+}
+
+fn assert_valid_cycle(graph: &Graph, cycle: &Cycle) {
+	let ends = graph.edge_ends();
+	let len = cycle.vertexes.len();
+	assert!(len > 0);
+	assert_eq!(cycle.edges.len(), len);
+	let mut distinct = cycle.vertexes.clone();
+	distinct.sort();
+	distinct.dedup();
+	assert_eq!(distinct.len(), len);
+	for (i, &e) in cycle.edges.iter().enumerate() {
+		assert_eq!(ends[e], (cycle.vertexes[i], cycle.vertexes[(i + 1) % len]), "{}", describe(graph));
+	}
+}
+
+fn random_graph(rng: &mut Rng) -> Graph {
+	if rng.chance(0.5) {
+		random_directed_graph(rng, 7, 10)
+	} else {
+		let n = rng.below(7) + 1;
+		random_dag(n, rng.next_f64(), rng).0
+	}
+}
+
+#[test]
+fn orders_match_brute_force() {
+	let mut rng = Rng::new(480);
+	for _ in 0..CASES {
+		let graph = random_graph(&mut rng);
+		let mut expected = permutations(graph.vertexes()).into_iter()
+			.filter(|order| is_topological(&graph, order))
+			.collect::<Vec<_>>();
+		expected.sort();
+
+		assert_eq!(all_topological_sorts(&graph), expected, "{}", describe(&graph));
+		assert_eq!(count_topological_sorts(&graph), expected.len() as u128);
+		assert_eq!(DFSSpace::new(&graph).topological_sort(&graph).1, !expected.is_empty());
+		match (smallest_topological_sort(&graph), largest_topological_sort(&graph)) {
+			(Ok(smallest), Ok(largest)) => {
+				assert_eq!(&smallest, expected.first().unwrap());
+				assert_eq!(&largest, expected.last().unwrap());
+			}
+			(Err(a), Err(b)) => {
+				assert!(expected.is_empty());
+				assert_valid_cycle(&graph, &a);
+				assert_valid_cycle(&graph, &b);
+			}
+			_ => panic!("{}", describe(&graph)),
+		}
+	}
+}
+
+#[test]
+fn custom_priority() {
+	let mut rng = Rng::new(481);
+	for _ in 0..CASES {
+		let n = rng.below(12) + 1;
+		let (graph, _) = random_dag(n, rng.next_f64() * 0.5, &mut rng);
+		let priority = rng.permutation(n);
+		let order = topological_sort_by_key(&graph, |v| priority[v]).unwrap();
+		assert!(is_topological(&graph, &order));
+		// Relabelled by priority it's the smallest order
+		let mut relabelled = Graph::new(n);
+		for (from, to) in graph.edge_ends() {
+			relabelled.add_directed_edge(priority[from], priority[to]);
+		}
+		let smallest = smallest_topological_sort(&relabelled).unwrap();
+		assert_eq!(order.iter().map(|&v| priority[v]).collect::<Vec<_>>(), smallest);
+	}
+}
+
+#[test]
+fn cycles_in_larger_graphs() {
+	let mut rng = Rng::new(482);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 40, 60);
+		match smallest_topological_sort(&graph) {
+			Ok(order) => assert!(is_topological(&graph, &order)),
+			Err(cycle) => assert_valid_cycle(&graph, &cycle),
+		}
+	}
+	let mut looped = Graph::new(3);
+	looped.add_directed_edge(0, 1);
+	looped.add_directed_edge(1, 1);
+	assert_eq!(smallest_topological_sort(&looped), Err(Cycle { vertexes: vec![1], edges: vec![1] }));
+	assert_eq!(count_topological_sorts(&looped), 0);
+	assert!(all_topological_sorts(&looped).is_empty());
+}
+
+#[test]
+fn known_counts() {
+	assert_eq!(count_topological_sorts(&Graph::new(20)), (1..=20).product::<u128>());
+	assert_eq!(count_topological_sorts(&Graph::new(0)), 1);
+	// Two chains of 10 interleave in C(20, 10) ways
+	let mut chains = Graph::new(20);
+	for v in 0..9 {
+		chains.add_directed_edge(v, v + 1);
+		chains.add_directed_edge(10 + v, 11 + v);
+	}
+	assert_eq!(count_topological_sorts(&chains), 184756);
+}
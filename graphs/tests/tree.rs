@@ -0,0 +1,164 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+/// Distances from `source` by breadth-first search
+fn distances(tree: &Graph, source: usize) -> Vec<usize> {
+	RootedTree::new(tree, source).depth
+}
+
+#[test]
+fn prufer_codes_round_trip() {
+	let mut rng = Rng::new(51);
+	for _ in 0..CASES {
+		let n = rng.below(12) + 2;
+		let code = random_prufer_code(n, &mut rng);
+		let tree = tree_from_prufer_code(&code).unwrap();
+		assert!(is_tree(&tree));
+		assert_eq!(prufer_code(&tree), code);
+	}
+}
+
+#[test]
+fn prufer_codes_with_values_out_of_range_are_rejected() {
+	assert!(tree_from_prufer_code(&[7]).is_none());
+	assert!(tree_from_prufer_code(&[0, 4]).is_none());
+	assert!(tree_from_prufer_code(&[usize::MAX, 0]).is_none());
+	// The largest vertex is a valid value
+	let star = tree_from_prufer_code(&[3, 3]).unwrap();
+	assert_eq!((0..4).map(|v| star.degree(v)).collect::<Vec<_>>(), vec![1, 1, 1, 3]);
+	assert_eq!(tree_from_prufer_code(&[]).unwrap().edges(), 1);
+}
+
+#[test]
+fn lca_and_distances_match_breadth_first_search() {
+	let mut rng = Rng::new(52);
+	for _ in 0..CASES {
+		let n = rng.below(12) + 1;
+		let tree = random_tree(n, &mut rng);
+		let distance = (0..n).map(|v| distances(&tree, v)).collect::<Vec<_>>();
+		let root = rng.below(n);
+		let lifting = BinaryLifting::new(&RootedTree::new(&tree, root));
+		for u in 0..n {
+			for v in 0..n {
+				assert_eq!(lifting.distance(u, v), distance[u][v], "{}", describe(&tree));
+				let lca = lifting.lca(u, v);
+				assert_eq!(distance[u][lca] + distance[lca][v], distance[u][v]);
+				assert_eq!(distance[root][u] + distance[root][v] - 2 * distance[root][lca], distance[u][v]);
+			}
+		}
+
+		let eccentricity = distance.iter().map(|row| *row.iter().max().unwrap()).collect::<Vec<_>>();
+		assert_eq!(tree_diameter(&tree).len() - 1, *eccentricity.iter().max().unwrap(), "{}", describe(&tree));
+		let radius = *eccentricity.iter().min().unwrap();
+		assert_eq!(tree_center(&tree), (0..n).filter(|&v| eccentricity[v] == radius).collect::<Vec<_>>());
+	}
+}
+
+#[test]
+fn canonical_forms_detect_isomorphism() {
+	let mut rng = Rng::new(53);
+	for _ in 0..CASES {
+		let n = rng.below(12) + 1;
+		let tree = random_tree(n, &mut rng);
+		let permutation = rng.permutation(n);
+		let mut relabelled = Graph::new(n);
+		for (from, to) in tree.edge_ends() {
+			relabelled.add_undirected_edge(permutation[from], permutation[to]);
+		}
+		let root = rng.below(n);
+		assert!(are_trees_isomorphic(&tree, &relabelled), "{}", describe(&tree));
+		assert!(are_rooted_trees_isomorphic(&tree, root, &relabelled, permutation[root]), "{}", describe(&tree));
+
+		// Trees with different degree sequences are never isomorphic
+		let other = random_tree(n, &mut rng);
+		let degrees = |graph: &Graph| {
+			let mut degrees = graph.edges.iter().map(|edges| edges.len()).collect::<Vec<_>>();
+			degrees.sort();
+			degrees
+		};
+		if degrees(&tree) != degrees(&other) {
+			assert!(!are_trees_isomorphic(&tree, &other), "{}\n{}", describe(&tree), describe(&other));
+		}
+		assert_eq!(are_trees_isomorphic(&tree, &other), unrooted_canonical_form(&tree) == unrooted_canonical_form(&other));
+	}
+}
+
+/// Sizes of the components left after removing `removed` from the tree, among the `kept` vertexes
+fn component_sizes(tree: &Graph, kept: &[bool], removed: usize) -> Vec<usize> {
+	let mut union_find = UnionFind::new(tree.vertexes());
+	for (from, to) in tree.edge_ends() {
+		if from != removed && to != removed && kept[from] && kept[to] {
+			union_find.union(from, to);
+		}
+	}
+	let mut sizes = vec![0; tree.vertexes()];
+	for v in (0..tree.vertexes()).filter(|&v| v != removed && kept[v]) {
+		sizes[union_find.find(v)] += 1;
+	}
+	sizes
+}
+
+#[test]
+fn centroids_and_centroid_decomposition() {
+	let mut rng = Rng::new(54);
+	for _ in 0..CASES {
+		let n = rng.below(14) + 1;
+		let tree = random_tree(n, &mut rng);
+		let all = vec![true; n];
+		let centroids = (0..n)
+			.filter(|&v| component_sizes(&tree, &all, v).into_iter().all(|size| size <= n / 2))
+			.collect::<Vec<_>>();
+		assert_eq!(tree_centroids(&tree), centroids, "{}", describe(&tree));
+
+		// Every centroid splits the part of the tree below it in the centroid tree into halves
+		let decomposition = CentroidDecomposition::new(&tree);
+		assert_eq!(decomposition.parent[decomposition.root], None);
+		for c in 0..n {
+			let below = (0..n)
+				.map(|mut v| loop {
+					if v == c {
+						break true;
+					}
+					match decomposition.parent[v] {
+						Some(p) => v = p,
+						None => break false,
+					}
+				})
+				.collect::<Vec<_>>();
+			let part = below.iter().filter(|&&b| b).count();
+			assert!(component_sizes(&tree, &below, c).into_iter().all(|size| size <= part / 2), "{}", describe(&tree));
+			assert!(1 << decomposition.level[c] <= n);
+		}
+	}
+}
+
+#[test]
+fn ancestors_and_tree_recognition() {
+	let mut rng = Rng::new(55);
+	for _ in 0..CASES {
+		let n = rng.below(14) + 1;
+		let tree = random_tree(n, &mut rng);
+		let rooted = RootedTree::new(&tree, rng.below(n));
+		let lifting = BinaryLifting::new(&rooted);
+		for v in 0..n {
+			let mut ancestor = Some(v);
+			for k in 0..=n {
+				assert_eq!(lifting.kth_ancestor(v, k), ancestor);
+				ancestor = ancestor.and_then(|a| rooted.parent[a]);
+			}
+		}
+		assert!(is_tree(&tree));
+		if n > 1 {
+			let mut extra = tree.clone();
+			extra.add_undirected_edge(rng.below(n), rng.below(n));
+			assert!(!is_tree(&extra));
+			assert!(!is_tree(&tree.without_edges(&[rng.below(n - 1)])));
+		}
+	}
+	assert!(!is_tree(&Graph::new(0)));
+	assert_eq!(tree_diameter(&path_graph(5)), vec![0, 1, 2, 3, 4]);
+	assert_eq!(tree_center(&path_graph(6)), vec![2, 3]);
+	assert_eq!(tree_centroids(&path_graph(6)), vec![2, 3]);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+/// Whitney's rank expansion: T(G; x, y) = Σ (x - 1)^(r(E) - r(A)) (y - 1)^(|A| - r(A)) over subsets A of edges
+fn brute_force_tutte(graph: &Graph, x: i64, y: i64) -> i64 {
+	let m = graph.edges();
+	let n = graph.vertexes();
+	let full_rank = n - count_components(graph, |_| true, |_| true);
+	(0..1_usize << m)
+		.map(|subset| {
+			let rank = n - count_components(graph, |e| subset >> e & 1 == 1, |_| true);
+			let size = subset.count_ones() as usize;
+			(x - 1).pow((full_rank - rank) as u32) * (y - 1).pow((size - rank) as u32)
+		})
+		.sum()
+}
+
+/// Nowhere-zero flows with values in Z_k for a fixed orientation of every edge
+fn count_nowhere_zero_flows(graph: &Graph, k: i64) -> i64 {
+	let ends = graph.edge_ends();
+	let mut count = 0;
+	for code in 0..(k - 1).pow(ends.len() as u32) {
+		let mut excess = vec![0; graph.vertexes()];
+		let mut rest = code;
+		for &(from, to) in &ends {
+			let value = rest % (k - 1) + 1;
+			rest /= k - 1;
+			excess[from] += value;
+			excess[to] -= value;
+		}
+		if excess.iter().all(|x: &i64| x.rem_euclid(k) == 0) {
+			count += 1;
+		}
+	}
+	count
+}
+
+#[test]
+fn tutte_polynomial_matches_rank_expansion() {
+	let mut rng = Rng::new(23);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 6, 9, true);
+		let tutte = TuttePolynomial::new(&graph);
+		for x in -2..3 {
+			for y in -2..3 {
+				assert_eq!(tutte.evaluate(x, y), brute_force_tutte(&graph, x, y), "T({}, {})\n{}", x, y, describe(&graph));
+			}
+		}
+		assert_eq!(tutte.chromatic_polynomial(), chromatic_polynomial(&graph), "{}", describe(&graph));
+		assert_eq!(tutte.reliability_polynomial().evaluate(1), 1, "{}", describe(&graph));
+		for k in 2..4 {
+			assert_eq!(tutte.flow_polynomial().evaluate(k), count_nowhere_zero_flows(&graph, k), "k = {}\n{}", k, describe(&graph));
+		}
+	}
+}
+
+/// Orientations of the edges without a directed cycle (a loop is always one)
+fn count_acyclic_orientations(graph: &Graph) -> i64 {
+	let ends = graph.edge_ends();
+	(0..1_usize << ends.len())
+		.filter(|orientation| {
+			let mut oriented = Graph::new(graph.vertexes());
+			for (e, &(from, to)) in ends.iter().enumerate() {
+				if orientation >> e & 1 == 0 {
+					oriented.add_directed_edge(from, to);
+				} else {
+					oriented.add_directed_edge(to, from);
+				}
+			}
+			DFSSpace::new(&oriented).topological_sort(&oriented).1
+		})
+		.count() as i64
+}
+
+#[test]
+fn specializations_match_brute_force() {
+	let mut rng = Rng::new(25);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 6, 8, true);
+		let (n, m) = (graph.vertexes(), graph.edges());
+		let tutte = TuttePolynomial::new(&graph);
+		let components = count_components(&graph, |_| true, |_| true);
+		assert_eq!(tutte.rank(), n - components);
+		assert_eq!(tutte.nullity(), m + components - n);
+		assert_eq!(tutte.polynomial, tutte_polynomial(&graph));
+
+		let subsets = (0..1_usize << m)
+			.map(|subset| (subset, count_components(&graph, |e| subset >> e & 1 == 1, |_| true)))
+			.collect::<Vec<_>>();
+		let acyclic = subsets.iter().filter(|&&(subset, c)| subset.count_ones() as usize + c == n).count();
+		let spanning = subsets.iter().filter(|&&(_, c)| c == components).collect::<Vec<_>>();
+		let trees = spanning.iter().filter(|&&&(subset, _)| subset.count_ones() as usize == n - components).count();
+		assert_eq!(tutte.spanning_forests(), acyclic as i64, "{}", describe(&graph));
+		assert_eq!(tutte.connected_spanning_subgraphs(), spanning.len() as i64, "{}", describe(&graph));
+		assert_eq!(tutte.spanning_trees(), trees as i64, "{}", describe(&graph));
+		assert_eq!(tutte.acyclic_orientations(), count_acyclic_orientations(&graph), "{}", describe(&graph));
+
+		// R(p) = Σ p^|A| (1 - p)^(m - |A|) over the subsets A keeping the components
+		let p = Polynomial::monomial(1, 1);
+		let q = Polynomial::new(vec![1, -1]);
+		let reliability = spanning.iter()
+			.map(|&&(subset, _)| {
+				let size = subset.count_ones() as usize;
+				p.pow(size) * q.pow(m - size)
+			})
+			.fold(Polynomial::zero(), |acc, term| acc + term);
+		assert_eq!(tutte.reliability_polynomial(), reliability, "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn known_tutte_polynomials() {
+	// K₄: x³ + 3x² + 2x + 4xy + 2y + 3y² + y³
+	let k4 = tutte_polynomial(&complete_graph(4));
+	for (i, j, c) in [(3, 0, 1), (2, 0, 3), (1, 0, 2), (1, 1, 4), (0, 1, 2), (0, 2, 3), (0, 3, 1), (0, 0, 0)] {
+		assert_eq!(k4.coefficient(i, j), c);
+	}
+	// A cycle: x^(n-1) + … + x + y
+	let cycle = tutte_polynomial(&cycle_graph(5));
+	assert_eq!(cycle, BivariatePolynomial::new(vec![vec![0, 1], vec![1], vec![1], vec![1], vec![1]]));
+	// A loop is y, a bridge is x
+	let mut graph = Graph::new(2);
+	graph.add_undirected_edge(0, 1);
+	graph.add_undirected_edge(1, 1);
+	assert_eq!(tutte_polynomial(&graph), BivariatePolynomial::monomial(1, 1, 1));
+	assert_eq!(TuttePolynomial::new(&petersen_graph()).spanning_trees(), 2000);
+}
+
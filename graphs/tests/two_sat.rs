@@ -0,0 +1,46 @@
+mod common;
+
+use graphs::*;
+
+fn satisfies(clauses: &[(Literal, Literal)], assignment: &[bool]) -> bool {
+	clauses.iter().all(|&(a, b)| a.evaluate(assignment) || b.evaluate(assignment))
+}
+
+fn brute_force_satisfiable(variables: usize, clauses: &[(Literal, Literal)]) -> bool {
+	(0..1_usize << variables).any(|mask| {
+		let assignment = (0..variables).map(|x| mask >> x & 1 == 1).collect::<Vec<_>>();
+		satisfies(clauses, &assignment)
+	})
+}
+
+fn random_literal(rng: &mut Rng, variables: usize) -> Literal {
+	Literal { variable: rng.below(variables), negated: rng.below(2) == 1 }
+}
+
+#[test]
+fn two_sat_matches_brute_force() {
+	let mut rng = Rng::new(61);
+	for _ in 0..common::CASES * 3 {
+		let variables = rng.below(7) + 1;
+		let mut formula = TwoSat::new(variables);
+		for _ in 0..rng.below(3 * variables + 1) {
+			let (a, b) = (random_literal(&mut rng, variables), random_literal(&mut rng, variables));
+			formula.add_clause(a, b);
+		}
+		let satisfiable = brute_force_satisfiable(variables, &formula.clauses);
+		match formula.solve() {
+			TwoSatResult::Satisfiable(assignment) => {
+				assert!(satisfiable);
+				assert!(satisfies(&formula.clauses, &assignment), "{:?}", formula.clauses);
+			}
+			TwoSatResult::Unsatisfiable(core) => {
+				assert!(!satisfiable, "{:?}", formula.clauses);
+				let clauses = core.clauses.iter().map(|&i| formula.clauses[i]).collect::<Vec<_>>();
+				assert!(!brute_force_satisfiable(variables, &clauses), "Core {:?} of {:?}", core, formula.clauses);
+				assert_eq!(core.implications.first(), Some(&Literal::positive(core.variable)));
+				assert_eq!(core.implications.last(), Some(&Literal::positive(core.variable)));
+				assert!(core.implications.contains(&Literal::negative(core.variable)));
+			}
+		}
+	}
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use graphs::*;
+use common::*;
+
+fn arcs<G: GraphView>(graph: &G) -> Vec<(usize, usize, usize)> {
+	let mut arcs = (0..graph.vertexes())
+		.flat_map(|v| graph.neighbours(v).map(move |edge| (v, edge.to, edge.edge_index)))
+		.collect::<Vec<_>>();
+	arcs.sort();
+	arcs
+}
+
+#[test]
+fn reversed_view_matches_reversed() {
+	let mut rng = Rng::new(391);
+	for _ in 0..CASES {
+		let graph = random_directed_graph(&mut rng, 10, 15);
+		assert_eq!(arcs(&Reversed::new(&graph)), arcs(&graph.reversed()), "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn subgraph_views_match_copies() {
+	let mut rng = Rng::new(392);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 10, 14, true);
+		let removed_edges = (0..graph.edges()).filter(|_| rng.chance(0.3)).collect::<Vec<_>>();
+		let edge_subgraph = EdgeSubgraph::new(&graph, |e| !removed_edges.contains(&e));
+		// `without_edges` renumbers the edges, so only the ends are compared
+		let ends = |arcs: Vec<(usize, usize, usize)>| arcs.into_iter().map(|(from, to, _)| (from, to)).collect::<Vec<_>>();
+		assert_eq!(ends(arcs(&edge_subgraph)), ends(arcs(&graph.without_edges(&removed_edges))));
+		assert_eq!(DFSSpace::new(&edge_subgraph).find_connected_components(&edge_subgraph).component_list.len(),
+		           count_components(&graph, |e| !removed_edges.contains(&e), |_| true),
+		           "{}", describe(&graph));
+
+		let kept = (0..graph.vertexes()).map(|_| rng.chance(0.7)).collect::<Vec<_>>();
+		let vertex_subgraph = VertexSubgraph::new(&graph, |v| kept[v]);
+		let components = DFSSpace::new(&vertex_subgraph).find_connected_components(&vertex_subgraph);
+		let isolated = kept.iter().filter(|&&k| !k).count();
+		assert_eq!(components.component_list.len() - isolated, count_components(&graph, |_| true, |v| kept[v]),
+		           "{}", describe(&graph));
+	}
+}
+
+#[test]
+fn removed_edges_keep_their_indexes_in_bounds() {
+	let mut rng = Rng::new(395);
+	for _ in 0..CASES {
+		let mut graph = random_multigraph(&mut rng, 9, 14, true);
+		let removed_edges = (0..graph.edges()).filter(|_| rng.chance(0.3)).collect::<Vec<_>>();
+		let compact = graph.without_edges(&removed_edges);
+		// Edge `i` of `compact` is edge `kept[i]` of `graph`
+		let kept = (0..graph.edges()).filter(|e| !removed_edges.contains(e)).collect::<Vec<_>>();
+		let old_indexes = |edges: Vec<usize>| {
+			let mut edges = edges.into_iter().map(|e| kept[e]).collect::<Vec<_>>();
+			edges.sort();
+			edges
+		};
+		graph.remove_edges(&removed_edges);
+		assert_eq!((graph.edges(), graph.edge_index_bound()), (kept.len(), kept.len() + removed_edges.len()));
+		assert!(graph.edges.iter().flatten().all(|edge| edge.edge_index < graph.edge_index_bound()));
+
+		let mut bridges = DFSSpace::new(&graph).find_bridges(&graph);
+		bridges.sort();
+		assert_eq!(bridges, old_indexes(DFSSpace::new(&compact).find_bridges(&compact)), "{}", describe(&compact));
+		assert_eq!(BridgeTree::new(&graph).bridges, old_indexes(BridgeTree::new(&compact).bridges));
+		let blocks = BlockCutTree::new(&graph).blocks.into_iter().map(|block| block.edges).collect::<Vec<_>>();
+		let compact_blocks = BlockCutTree::new(&compact).blocks.into_iter().map(|block| old_indexes(block.edges)).collect::<Vec<_>>();
+		assert_eq!(blocks, compact_blocks, "{}", describe(&compact));
+		assert_eq!(cycle_basis(&graph).len(), cycle_basis(&compact).len());
+	}
+}
+
+#[test]
+fn algorithms_ignore_weights() {
+	let mut rng = Rng::new(393);
+	for _ in 0..CASES {
+		let graph = random_multigraph(&mut rng, 8, 10, true);
+		let weighted = with_random_weights(&graph, -5, 5, &mut rng);
+		assert_eq!(DFSSpace::new(&weighted).find_bridges(&weighted), DFSSpace::new(&graph).find_bridges(&graph));
+		assert_eq!(find_edge_biconnected_components(&weighted).component_map,
+		           find_edge_biconnected_components(&graph).component_map);
+		assert_eq!(euler_path(&weighted, false), euler_path(&graph, false));
+		assert_eq!(maximum_matching(&weighted).matched_edges, maximum_matching(&graph).matched_edges);
+		assert_eq!(bipartition(&weighted).map(|sides| sides.component_map),
+		           bipartition(&graph).map(|sides| sides.component_map));
+
+		let tree = with_random_weights(&random_tree(1 + rng.below(8), &mut rng), 1, 9, &mut rng);
+		assert!(is_tree(&tree));
+		assert_eq!(tree_diameter(&tree).len(), tree_diameter(&CsrGraph::from_graph(&tree)).len());
+	}
+}
+
+#[test]
+fn grid_view_matches_grid_graph() {
+	let mut rng = Rng::new(394);
+	for _ in 0..CASES {
+		let (width, height) = (1 + rng.below(6), 1 + rng.below(6));
+		let open = (0..width * height).map(|_| rng.chance(0.75)).collect::<Vec<_>>();
+		let grid = grid_view(width, height, |cell| open[cell]);
+		let stored = grid_graph(height, width);
+		let stored = VertexSubgraph::new(&stored, |cell| open[cell]);
+		for source in (0..width * height).filter(|&cell| open[cell]) {
+			assert_eq!(bfs(&grid, source).distance, bfs(&stored, source).distance);
+		}
+		// Edges are seen from both ends with the same index
+		let mut ends = vec![Vec::new(); grid.edge_index_bound()];
+		for (from, to, edge_index) in arcs(&grid) {
+			ends[edge_index].push((from.min(to), from.max(to)));
+		}
+		assert!(ends.iter().all(|e| e.is_empty() || (e.len() == 2 && e[0] == e[1])));
+	}
+}
+
+#[test]
+fn implicit_state_space() {
+	// Jugs of 3 and 5 litres: a state is the pair of volumes, a move fills, empties or pours
+	let (a, b) = (3, 5);
+	let state = |x: usize, y: usize| x * (b + 1) + y;
+	let states = (a + 1) * (b + 1);
+	// Move `i` from state `s` is the arc `6 s + i`
+	let graph = ImplicitGraph::new(states, 6 * states, move |s: usize| {
+		let (x, y) = (s / (b + 1), s % (b + 1));
+		let pour_to_b = x.min(b - y);
+		let pour_to_a = y.min(a - x);
+		[(a, y), (0, y), (x, b), (x, 0), (x - pour_to_b, y + pour_to_b), (x + pour_to_a, y - pour_to_a)]
+			.into_iter()
+			.enumerate()
+			.map(move |(i, (x, y))| WeightedEdge { to: state(x, y), edge_index: 6 * s + i, weight: () })
+	});
+	assert!((0..states).flat_map(|s| graph.neighbours(s)).all(|edge| edge.edge_index < graph.edge_index_bound()));
+	let paths = bfs(&graph, state(0, 0));
+	// Fill b, pour into a, empty a, pour into a, fill b, pour into a
+	assert_eq!(paths.distance[state(3, 4)], Some(6));
+	assert_eq!(paths.path_to(state(3, 4)).unwrap().len(), 7);
+	assert_eq!(paths.distance[state(0, 4)], Some(7));
+}